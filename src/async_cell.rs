@@ -0,0 +1,617 @@
+use alloc::sync::Arc;
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use crate::once_cell::{InfallibleInit, Infallibly, Initialize, Initializer};
+use crate::sync_mutex::SyncMutex;
+use crate::OnceCell;
+
+/// A monotonic clock deciding when the value in an [`AsyncCell`] expires.
+///
+/// This trait is implemented for closures returning the current time, which makes it easy to
+/// plug in a runtime's clock or a mocked one in tests.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::AsyncCell;
+/// use std::sync::atomic::{AtomicU64, Ordering};
+/// use std::time::Duration;
+///
+/// static NOW: AtomicU64 = AtomicU64::new(0);
+///
+/// let cell = AsyncCell::with_clock(Duration::from_secs(10), || {
+///     Duration::from_secs(NOW.load(Ordering::SeqCst))
+/// });
+/// # futures_lite::future::block_on(async {
+/// cell.get_or_refresh(|| async { 1 }).await;
+/// assert_eq!(cell.get().as_deref(), Some(&1));
+///
+/// NOW.store(10, Ordering::SeqCst);
+/// assert_eq!(cell.get(), None);
+/// # });
+/// ```
+pub trait Clock {
+    /// Returns the time elapsed since some fixed point in the past.
+    ///
+    /// The returned time must never go backwards.
+    fn now(&self) -> Duration;
+}
+
+impl<F: Fn() -> Duration> Clock for F {
+    #[inline]
+    fn now(&self) -> Duration {
+        self()
+    }
+}
+
+/// A cached value that can be refreshed and invalidated through a shared reference.
+///
+/// Unlike [`OnceCell`], the value can be thrown away with [`invalidate()`][AsyncCell::invalidate]
+/// or expire after a time-to-live, after which the next call to
+/// [`get_or_refresh()`][AsyncCell::get_or_refresh] computes it again. This makes `AsyncCell`
+/// suitable for caching values like authentication tokens or DNS results.
+///
+/// Concurrent refreshes are coalesced: only one task runs its refresh closure, while the others
+/// wait for its result. If the refreshing task fails, is cancelled or panics, the next waiting
+/// task runs its own closure instead.
+///
+/// With [`stale_while_revalidate()`][AsyncCell::stale_while_revalidate], tasks finding an expired
+/// value that someone else is already refreshing get the expired value right away instead of
+/// waiting.
+///
+/// Values are handed out as [`Arc`]s, so that they stay alive while the cell moves on.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::AsyncCell;
+///
+/// # futures_lite::future::block_on(async {
+/// let token = AsyncCell::new();
+///
+/// assert_eq!(*token.get_or_refresh(|| async { "first" }).await, "first");
+/// assert_eq!(*token.get_or_refresh(|| async { "second" }).await, "first");
+///
+/// token.invalidate();
+/// assert_eq!(*token.get_or_refresh(|| async { "third" }).await, "third");
+/// # });
+/// ```
+pub struct AsyncCell<T, C = fn() -> Duration> {
+    /// The current generation of the value.
+    ///
+    /// This lock is only held for as long as it takes to clone or replace the `Arc`.
+    current: SyncMutex<Arc<Generation<T>>>,
+
+    /// How long values stay fresh after a refresh.
+    ttl: Option<Duration>,
+
+    /// Whether expired values are served while a refresh is running.
+    serve_stale: bool,
+
+    /// The clock deciding when values expire.
+    clock: C,
+}
+
+/// One value of the cell, from the refresh computing it until it is invalidated or expires.
+struct Generation<T> {
+    /// The value, once refreshed.
+    entry: OnceCell<Entry<T>>,
+
+    /// The expired value of the previous generation, kept only while this one is refreshing and
+    /// if the cell serves stale values.
+    stale: SyncMutex<Option<Arc<T>>>,
+}
+
+/// A refreshed value.
+struct Entry<T> {
+    /// The value.
+    value: Arc<T>,
+
+    /// The time the value expires at, according to the cell's clock.
+    expires: Option<Duration>,
+}
+
+impl<T> Generation<T> {
+    fn new(stale: Option<Arc<T>>) -> Arc<Generation<T>> {
+        Arc::new(Generation {
+            entry: OnceCell::new(),
+            stale: SyncMutex::new(stale),
+        })
+    }
+}
+
+impl<T> Borrow<OnceCell<Entry<T>>> for Arc<Generation<T>> {
+    #[inline]
+    fn borrow(&self) -> &OnceCell<Entry<T>> {
+        &self.entry
+    }
+}
+
+impl<T> AsyncCell<T> {
+    /// Creates a new, empty cell whose value doesn't expire.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    ///
+    /// let cell = AsyncCell::<i32>::new();
+    /// assert_eq!(cell.get(), None);
+    /// ```
+    pub fn new() -> AsyncCell<T> {
+        AsyncCell::with_clock_inner(None, never as fn() -> Duration)
+    }
+
+    /// Creates a new, empty cell whose value expires `ttl` after each refresh.
+    ///
+    /// Time is measured with [`std::time::Instant`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    /// use std::time::Duration;
+    ///
+    /// let cell = AsyncCell::with_ttl(Duration::from_secs(60));
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+    /// # });
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn with_ttl(ttl: Duration) -> AsyncCell<T> {
+        AsyncCell::with_clock_inner(Some(ttl), monotonic as fn() -> Duration)
+    }
+}
+
+impl<T, C: Clock> AsyncCell<T, C> {
+    /// Creates a new, empty cell whose value expires `ttl` after each refresh, as measured by
+    /// `clock`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let start = Instant::now();
+    /// let cell = AsyncCell::with_clock(Duration::from_secs(60), move || start.elapsed());
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+    /// # });
+    /// ```
+    pub fn with_clock(ttl: Duration, clock: C) -> AsyncCell<T, C> {
+        AsyncCell::with_clock_inner(Some(ttl), clock)
+    }
+
+    fn with_clock_inner(ttl: Option<Duration>, clock: C) -> AsyncCell<T, C> {
+        AsyncCell {
+            current: SyncMutex::new(Generation::new(None)),
+            ttl,
+            serve_stale: false,
+            clock,
+        }
+    }
+
+    /// Serves expired values while they are being refreshed.
+    ///
+    /// When the value has expired and another task is already refreshing it,
+    /// [`get_or_refresh()`][AsyncCell::get_or_refresh] returns the expired value instead of
+    /// waiting for the refresh to complete. Invalidated values are never served.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    /// use std::time::Duration;
+    ///
+    /// let cell = AsyncCell::<i32>::with_ttl(Duration::from_secs(60)).stale_while_revalidate();
+    /// ```
+    pub fn stale_while_revalidate(mut self) -> AsyncCell<T, C> {
+        self.serve_stale = true;
+        self
+    }
+
+    /// Returns the value if it is present and hasn't expired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = AsyncCell::new();
+    /// assert_eq!(cell.get(), None);
+    ///
+    /// cell.get_or_refresh(|| async { 1 }).await;
+    /// assert_eq!(cell.get().as_deref(), Some(&1));
+    /// # });
+    /// ```
+    pub fn get(&self) -> Option<Arc<T>> {
+        let generation = self.current();
+        match generation.entry.get() {
+            Some(entry) if !self.is_expired(entry) => Some(entry.value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Throws away the value, so that the next call to
+    /// [`get_or_refresh()`][AsyncCell::get_or_refresh] refreshes it.
+    ///
+    /// A refresh that is already running completes for the tasks waiting on it, but its value
+    /// isn't stored in the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = AsyncCell::new();
+    /// cell.get_or_refresh(|| async { 1 }).await;
+    ///
+    /// cell.invalidate();
+    /// assert_eq!(cell.get(), None);
+    /// # });
+    /// ```
+    pub fn invalidate(&self) {
+        self.store(Generation::new(None));
+    }
+
+    /// Returns the value, refreshing it with the given closure if it is missing or has expired.
+    ///
+    /// If another task is already refreshing the value, this waits for it rather than running
+    /// `refresh`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = AsyncCell::new();
+    /// assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+    /// assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 1);
+    /// # });
+    /// ```
+    pub fn get_or_refresh<F, Fut>(&self, refresh: F) -> GetOrRefresh<'_, T, C, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        GetOrRefresh {
+            inner: Refreshing::new(self, Infallibly(refresh)),
+        }
+    }
+
+    /// Returns the value, refreshing it with the given closure if it is missing or has expired.
+    ///
+    /// If another task is already refreshing the value, this waits for it rather than running
+    /// `refresh`. If `refresh` fails, its error is returned and the cell stays empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AsyncCell;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = AsyncCell::new();
+    ///
+    /// assert!(cell.get_or_try_refresh(|| async { "x".parse::<i32>() }).await.is_err());
+    /// assert_eq!(cell.get(), None);
+    ///
+    /// let value = cell.get_or_try_refresh(|| async { "1".parse::<i32>() }).await;
+    /// assert_eq!(value.as_deref(), Ok(&1));
+    /// # });
+    /// ```
+    pub fn get_or_try_refresh<E, F, Fut>(&self, refresh: F) -> GetOrTryRefresh<'_, T, C, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        GetOrTryRefresh {
+            inner: Refreshing::new(self, refresh),
+        }
+    }
+
+    /// Returns the value if it is fresh or a stale one can be served, and otherwise the
+    /// generation to refresh.
+    fn lookup(&self) -> Result<Arc<T>, Arc<Generation<T>>> {
+        let mut generation = self.current();
+
+        loop {
+            match generation.entry.get() {
+                Some(entry) if !self.is_expired(entry) => return Ok(entry.value.clone()),
+
+                // Move on to a new generation, keeping the expired value around if it is served.
+                Some(entry) => {
+                    let stale = if self.serve_stale {
+                        Some(entry.value.clone())
+                    } else {
+                        None
+                    };
+                    let next = Generation::new(stale);
+                    generation = self.replace(&generation, next);
+                }
+
+                None => break,
+            }
+        }
+
+        if self.serve_stale && generation.entry.is_initializing() {
+            if let Some(stale) = generation.stale.lock().clone() {
+                return Ok(stale);
+            }
+        }
+
+        Err(generation)
+    }
+
+    /// Returns the time a value refreshed now expires at.
+    fn expires(&self) -> Option<Duration> {
+        self.ttl.and_then(|ttl| self.clock.now().checked_add(ttl))
+    }
+
+    /// Tells whether `entry` has expired.
+    fn is_expired(&self, entry: &Entry<T>) -> bool {
+        match entry.expires {
+            Some(expires) => self.clock.now() >= expires,
+            None => false,
+        }
+    }
+
+    /// Returns the current generation.
+    fn current(&self) -> Arc<Generation<T>> {
+        self.current.lock().clone()
+    }
+
+    /// Makes `next` the current generation.
+    fn store(&self, next: Arc<Generation<T>>) {
+        // Drop the previous generation once the lock is released.
+        let _previous = core::mem::replace(&mut *self.current.lock(), next);
+    }
+
+    /// Makes `next` the current generation if `generation` still is, and returns the new
+    /// current generation.
+    fn replace(
+        &self,
+        generation: &Arc<Generation<T>>,
+        next: Arc<Generation<T>>,
+    ) -> Arc<Generation<T>> {
+        let mut current = self.current.lock();
+        if Arc::ptr_eq(&current, generation) {
+            *current = next;
+        }
+        current.clone()
+    }
+}
+
+impl<T> Default for AsyncCell<T> {
+    #[inline]
+    fn default() -> AsyncCell<T> {
+        AsyncCell::new()
+    }
+}
+
+impl<T: fmt::Debug, C: Clock> fmt::Debug for AsyncCell<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Value<T>(Option<Arc<T>>);
+
+        impl<T: fmt::Debug> fmt::Debug for Value<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.0 {
+                    Some(value) => fmt::Debug::fmt(value, f),
+                    None => f.write_str("<empty>"),
+                }
+            }
+        }
+
+        f.debug_struct("AsyncCell")
+            .field("value", &Value(self.get()))
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+/// The clock of cells whose values never expire.
+fn never() -> Duration {
+    Duration::ZERO
+}
+
+/// The time elapsed since this clock was first read.
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+fn monotonic() -> Duration {
+    use std::time::Instant;
+
+    static START: OnceCell<Instant> = OnceCell::new();
+    START.get_or_init_blocking(Instant::now).elapsed()
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`AsyncCell::get_or_refresh`].
+    pub struct GetOrRefresh<'a, T, C, F, Fut> {
+        #[pin]
+        inner: Refreshing<'a, T, C, Infallibly<F>, InfallibleInit<Fut>>,
+    }
+}
+
+impl<T, C, F, Fut> fmt::Debug for GetOrRefresh<'_, T, C, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrRefresh { .. }")
+    }
+}
+
+impl<T, C, F, Fut> Future for GetOrRefresh<'_, T, C, F, Fut>
+where
+    C: Clock,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = Arc<T>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match ready!(self.project().inner.poll(cx)) {
+            Ok(value) => Poll::Ready(value),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`AsyncCell::get_or_try_refresh`].
+    pub struct GetOrTryRefresh<'a, T, C, F, Fut> {
+        #[pin]
+        inner: Refreshing<'a, T, C, F, Fut>,
+    }
+}
+
+impl<T, C, F, Fut> fmt::Debug for GetOrTryRefresh<'_, T, C, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrTryRefresh { .. }")
+    }
+}
+
+impl<T, E, C, F, Fut> Future for GetOrTryRefresh<'_, T, C, F, Fut>
+where
+    C: Clock,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Arc<T>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future that either refreshes the value of a cell or waits for someone else to.
+    struct Refreshing<'a, T, C, I, Fut> {
+        // The cell to refresh.
+        cell: &'a AsyncCell<T, C>,
+
+        // The refresh, until the generation to refresh is known.
+        refresh: Option<I>,
+
+        // The refresh of the current generation, or the wait for it.
+        #[pin]
+        init: Option<Initialize<Arc<Generation<T>>, Entry<T>, Refresh<'a, T, C, I>, RefreshEntry<'a, T, C, Fut>>>,
+    }
+}
+
+impl<'a, T, C, I, Fut> Refreshing<'a, T, C, I, Fut> {
+    /// Creates a future refreshing `cell` with `refresh` if needed.
+    #[inline]
+    fn new(cell: &'a AsyncCell<T, C>, refresh: I) -> Self {
+        Refreshing {
+            cell,
+            refresh: Some(refresh),
+            init: None,
+        }
+    }
+}
+
+impl<T, E, C, I, Fut> Future for Refreshing<'_, T, C, I, Fut>
+where
+    C: Clock,
+    I: Initializer<Fut>,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Arc<T>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if this.init.is_none() {
+            let generation = match this.cell.lookup() {
+                Ok(value) => return Poll::Ready(Ok(value)),
+                Err(generation) => generation,
+            };
+
+            // Either refresh the value or wait for whoever is refreshing it.
+            let refresh = Refresh {
+                cell: *this.cell,
+                generation: generation.clone(),
+                refresh: this.refresh.take().expect("future polled after completion"),
+            };
+            this.init.set(Some(Initialize::new(generation, refresh)));
+        }
+
+        let mut init = this.init.as_pin_mut().unwrap();
+        ready!(init.as_mut().poll(cx))?;
+
+        // SAFETY: We know that the entry is initialized, so it is safe to
+        // read it.
+        let entry = unsafe { init.cell().entry.get_unchecked() };
+        Poll::Ready(Ok(entry.value.clone()))
+    }
+}
+
+/// Starts refreshing a generation.
+struct Refresh<'a, T, C, I> {
+    /// The cell the generation belongs to.
+    cell: &'a AsyncCell<T, C>,
+
+    /// The generation to refresh.
+    generation: Arc<Generation<T>>,
+
+    /// The refresh.
+    refresh: I,
+}
+
+impl<'a, T, C, I, Fut> Initializer<RefreshEntry<'a, T, C, Fut>> for Refresh<'a, T, C, I>
+where
+    I: Initializer<Fut>,
+{
+    #[inline]
+    fn start(self) -> RefreshEntry<'a, T, C, Fut> {
+        RefreshEntry {
+            cell: self.cell,
+            generation: self.generation,
+            future: self.refresh.start(),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Turns the refreshed value into the entry of its generation.
+    struct RefreshEntry<'a, T, C, Fut> {
+        // The cell the generation belongs to.
+        cell: &'a AsyncCell<T, C>,
+
+        // The generation being refreshed.
+        generation: Arc<Generation<T>>,
+
+        // The running refresh.
+        #[pin]
+        future: Fut,
+    }
+}
+
+impl<T, E, C, Fut> Future for RefreshEntry<'_, T, C, Fut>
+where
+    C: Clock,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<Entry<T>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let value = ready!(this.future.poll(cx))?;
+
+        // The expired value isn't served anymore.
+        this.generation.stale.lock().take();
+
+        Poll::Ready(Ok(Entry {
+            value: Arc::new(value),
+            expires: this.cell.expires(),
+        }))
+    }
+}
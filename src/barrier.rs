@@ -1,25 +1,209 @@
 use event_listener::{Event, EventListener};
-use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+use event_listener_strategy::{EventListenerFuture, NonBlocking, Strategy};
 
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use core::cell::UnsafeCell;
 use core::fmt;
+use core::future::{Future, Ready};
 use core::pin::Pin;
-use core::task::Poll;
+use core::ptr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{Duration, Instant};
 
-use crate::futures::Lock;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use event_listener::Listener;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use event_listener_strategy::Blocking;
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::deadline::BlockingDeadline;
+use crate::timer::{TimedOut, Timer};
 use crate::Mutex;
 
+/// The state is 64 bits wide where possible, so that the number of parties fits in its own half
+/// even on 32-bit targets.
+#[cfg(target_has_atomic = "64")]
+type AtomicState = core::sync::atomic::AtomicU64;
+#[cfg(target_has_atomic = "64")]
+type State = u64;
+
+#[cfg(not(target_has_atomic = "64"))]
+type AtomicState = AtomicUsize;
+#[cfg(not(target_has_atomic = "64"))]
+type State = usize;
+
+/// Set while the number of parties is being changed.
+const LOCKED: State = 1;
+
+/// Set while the leader runs the completion, holding back tasks arriving for the next phase.
+const COMPLETING: State = 1 << 1;
+
+/// Set while the leader waits for the number of parties to settle, leaving it to the task
+/// changing it to move on to the next phase.
+const RELEASING: State = 1 << 2;
+
+/// Half of the state counts the parties the current phase still waits for.
+///
+/// While the leader runs the completion, these bits instead count the parties that joined in the
+/// meantime, which the phase waits for again if the leader gives up.
+const ONE_REMAINING: State = 1 << 3;
+const REMAINING_BITS: u32 = State::BITS / 2;
+const REMAINING_MASK: State = ((1 << REMAINING_BITS) - 1) * ONE_REMAINING;
+
+/// The bits above hold the current phase, wrapping around.
+const ONE_PHASE: State = ONE_REMAINING << REMAINING_BITS;
+const PHASE_MASK: State = !(ONE_PHASE - 1);
+
+/// The maximum number of parties.
+const MAX_PARTIES: usize = (REMAINING_MASK / ONE_REMAINING) as usize;
+
+/// Returns the phase of `state`.
+#[inline]
+#[allow(clippy::unnecessary_cast)]
+fn phase(state: State) -> u64 {
+    (state / ONE_PHASE) as u64
+}
+
+/// Returns the number of parties `state` waits for.
+#[inline]
+fn remaining(state: State) -> usize {
+    ((state & REMAINING_MASK) / ONE_REMAINING) as usize
+}
+
+/// Returns the state of the phase after `state`, waiting for `n` parties.
+#[inline]
+fn next_phase(state: State, n: usize) -> State {
+    (state & PHASE_MASK).wrapping_add(ONE_PHASE) | (n as State * ONE_REMAINING)
+}
+
 /// A counter to synchronize multiple tasks at the same time.
-#[derive(Debug)]
-pub struct Barrier {
-    n: usize,
-    state: Mutex<State>,
+///
+/// The number of tasks, or parties, can change over time with [`register()`] and
+/// [`arrive_and_deregister()`]. Each time all parties arrive, the barrier moves on to the next
+/// phase, numbered from 0. Phase numbers wrap around after 2<sup>29</sup> phases, or
+/// 2<sup>13</sup> on targets without 64-bit atomics.
+///
+/// A barrier created with [`with_completion()`] also runs a [`BarrierCompletion`] before moving
+/// on to the next phase.
+///
+/// [`register()`]: Barrier::register()
+/// [`arrive_and_deregister()`]: Barrier::arrive_and_deregister()
+/// [`with_completion()`]: Barrier::with_completion()
+pub struct Barrier<C: BarrierCompletion = ()> {
+    // The current phase, the parties it still waits for, and the flags above.
+    state: AtomicState,
+
+    // The number of parties, only changed while `LOCKED` is set.
+    n: AtomicUsize,
+
+    // Notified when the barrier moves on, or the leader gives up on the completion.
     event: Event,
+
+    // Notified when `LOCKED` is cleared.
+    unlocked: Event,
+
+    // The action run by the leader while `COMPLETING` is set.
+    completion: Mutex<C>,
+
+    // The record of the current phase, or null for `first`.
+    current: AtomicPtr<Record<C::Output>>,
+
+    // The record of the first phase.
+    first: Record<C::Output>,
+
+    // The records allocated for later phases while the earlier ones were still being read, only
+    // used by the leader.
+    records: UnsafeCell<Vec<Box<Record<C::Output>>>>,
+}
+
+unsafe impl<C: BarrierCompletion + Send> Sync for Barrier<C> where C::Output: Send + Sync {}
+
+impl<C: BarrierCompletion> fmt::Debug for Barrier<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.load(Ordering::Acquire);
+        let n = self.n.load(Ordering::Relaxed);
+        let count = if state & COMPLETING != 0 {
+            n
+        } else {
+            n.saturating_sub(remaining(state))
+        };
+
+        f.debug_struct("Barrier")
+            .field("n", &n)
+            .field("count", &count)
+            .field("phase", &phase(state))
+            .finish()
+    }
+}
+
+/// An action run by the leader of a [`Barrier`] before the other tasks are released.
+///
+/// This is implemented for closures returning a future, like `|| async { .. }`, for
+/// [`SyncCompletion`], and for `()`, which does nothing. See [`Barrier::with_completion()`].
+pub trait BarrierCompletion {
+    /// The value handed to every task released from the barrier.
+    type Output: Clone;
+
+    /// The future running the action.
+    type Future: Future<Output = Self::Output>;
+
+    /// Starts the action for the phase that all parties just arrived at.
+    fn complete(&mut self) -> Self::Future;
+}
+
+impl BarrierCompletion for () {
+    type Output = ();
+    type Future = Ready<()>;
+
+    #[inline]
+    fn complete(&mut self) -> Ready<()> {
+        core::future::ready(())
+    }
+}
+
+impl<F, Fut> BarrierCompletion for F
+where
+    F: FnMut() -> Fut,
+    Fut: Future,
+    Fut::Output: Clone,
+{
+    type Output = Fut::Output;
+    type Future = Fut;
+
+    #[inline]
+    fn complete(&mut self) -> Fut {
+        self()
+    }
+}
+
+/// A synchronous closure run as a [`BarrierCompletion`].
+///
+/// See [`Barrier::with_sync_completion()`].
+pub struct SyncCompletion<F>(F);
+
+impl<F> fmt::Debug for SyncCompletion<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SyncCompletion { .. }")
+    }
 }
 
-#[derive(Debug)]
-struct State {
-    count: usize,
-    generation_id: u64,
+impl<F, T> BarrierCompletion for SyncCompletion<F>
+where
+    F: FnMut() -> T,
+    T: Clone,
+{
+    type Output = T;
+    type Future = Ready<T>;
+
+    #[inline]
+    fn complete(&mut self) -> Ready<T> {
+        core::future::ready((self.0)())
+    }
 }
 
 impl Barrier {
@@ -30,6 +214,10 @@ impl Barrier {
     ///
     /// [`wait()`]: `Barrier::wait()`
     ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds `u32::MAX`, or `u16::MAX` on targets without 64-bit atomics.
+    ///
     /// # Examples
     ///
     /// ```
@@ -38,13 +226,223 @@ impl Barrier {
     /// let barrier = Barrier::new(5);
     /// ```
     pub const fn new(n: usize) -> Barrier {
+        assert!(n <= MAX_PARTIES, "too many parties in a barrier");
+
+        Barrier {
+            state: AtomicState::new(n as State * ONE_REMAINING),
+            n: AtomicUsize::new(n),
+            event: Event::new(),
+            unlocked: Event::new(),
+            completion: Mutex::new(()),
+            current: AtomicPtr::new(ptr::null_mut()),
+            first: Record::new(),
+            records: UnsafeCell::new(Vec::new()),
+        }
+    }
+
+    /// Arrives at the barrier without waiting for the other parties.
+    ///
+    /// If this was the last party to arrive, the barrier moves on to the next phase. Returns the
+    /// phase this party arrived at, which can be passed to [`await_phase()`] later.
+    ///
+    /// This is only available on barriers without a completion, which would have to be run here.
+    ///
+    /// [`await_phase()`]: Barrier::await_phase()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// assert_eq!(barrier.arrive(), 0);
+    /// assert_eq!(barrier.arrive(), 0);
+    ///
+    /// // Both parties arrived.
+    /// assert_eq!(barrier.await_phase(0).await, 1);
+    /// # });
+    /// ```
+    pub fn arrive(&self) -> u64 {
+        let mut listener = None;
+
+        loop {
+            match self.try_arrive(false) {
+                Arrival::Counted(phase, _) => return phase,
+                Arrival::Leader(phase) => {
+                    self.release(());
+                    return phase;
+                }
+
+                // The leader of the previous phase is done with an empty completion in a moment.
+                Arrival::Blocked => pause(&self.event, &mut listener),
+            }
+        }
+    }
+
+    /// Arrives at the barrier and removes this party from it.
+    ///
+    /// The other parties no longer wait for this one, in the current phase or the next ones. If
+    /// everyone else already arrived, the barrier moves on to the next phase. Returns the phase
+    /// this party arrived at.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the barrier has no parties left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// barrier.arrive_and_deregister();
+    ///
+    /// // The remaining party doesn't wait for the one that left.
+    /// assert!(barrier.wait().await.is_leader());
+    /// assert!(barrier.wait().await.is_leader());
+    /// # });
+    /// ```
+    pub fn arrive_and_deregister(&self) -> u64 {
+        let mut state = self.lock_parties();
+        let n = self.n.load(Ordering::Relaxed);
+        if n == 0 {
+            self.unlock_parties(0);
+            panic!("deregistered from a barrier without parties");
+        }
+        self.n.store(n - 1, Ordering::Relaxed);
+
+        loop {
+            // The current phase is already complete if an extra task arrived in our place. Arrive
+            // at the next one instead, which then waits for one party less.
+            if state & COMPLETING != 0 {
+                self.unlock_parties(0);
+                return phase(next_phase(state, 0));
+            }
+
+            let remaining = remaining(state);
+
+            // Don't release a phase nobody arrived at.
+            if remaining <= 1 && remaining < n {
+                // Complete the phase as the leader, moving on once `LOCKED` is cleared.
+                let new = (state & !REMAINING_MASK) | COMPLETING;
+                match self.state.compare_exchange_weak(
+                    state,
+                    new,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => {
+                        self.release(());
+                        self.unlock_parties(0);
+                        return phase(state);
+                    }
+                    Err(s) => state = s,
+                }
+            } else {
+                match self.state.compare_exchange_weak(
+                    state,
+                    (state - ONE_REMAINING) & !LOCKED,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => {
+                        self.unlocked.notify(1);
+                        return phase(state);
+                    }
+                    Err(s) => state = s,
+                }
+            }
+        }
+    }
+}
+
+impl<F, T> Barrier<SyncCompletion<F>>
+where
+    F: FnMut() -> T,
+    T: Clone,
+{
+    /// Creates a barrier that runs the synchronous `completion` each time all `n` tasks arrive.
+    ///
+    /// This works like [`with_completion()`], with a closure returning the output directly.
+    ///
+    /// [`with_completion()`]: Barrier::with_completion()
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds `u32::MAX`, or `u16::MAX` on targets without 64-bit atomics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// // Swaps double buffers once every task is done with the current ones.
+    /// let front = AtomicUsize::new(0);
+    /// let barrier = Barrier::with_sync_completion(2, || front.fetch_xor(1, Ordering::Relaxed) ^ 1);
+    ///
+    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+    /// assert_eq!(*a.output(), 1);
+    /// assert_eq!(*b.output(), 1);
+    /// # });
+    /// ```
+    pub fn with_sync_completion(n: usize, completion: F) -> Barrier<SyncCompletion<F>> {
+        Barrier::with_completion(n, SyncCompletion(completion))
+    }
+}
+
+impl<C: BarrierCompletion> Barrier<C> {
+    /// Creates a barrier that runs `completion` each time all `n` tasks arrive.
+    ///
+    /// The completion is run by the leader, the last task to arrive, while the other tasks are
+    /// still held. Its output is then handed to every task through
+    /// [`BarrierWaitResult::output()`]. Tasks arriving for the next phase meanwhile wait for the
+    /// completion to finish first.
+    ///
+    /// The completion is a closure returning a future. Synchronous closures can be used with
+    /// [`with_sync_completion()`] instead. The completion must not use the barrier itself.
+    ///
+    /// [`with_sync_completion()`]: Barrier::with_sync_completion()
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` exceeds `u32::MAX`, or `u16::MAX` on targets without 64-bit atomics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    ///
+    /// let (tx, rx) = flume::unbounded();
+    /// let barrier = Barrier::with_completion(2, || {
+    ///     let rx = rx.clone();
+    ///     async move { rx.recv_async().await.unwrap() }
+    /// });
+    ///
+    /// tx.send("done").unwrap();
+    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+    /// assert_eq!(*a.output(), "done");
+    /// assert_eq!(*b.output(), "done");
+    /// # });
+    /// ```
+    pub fn with_completion(n: usize, completion: C) -> Barrier<C> {
+        assert!(n <= MAX_PARTIES, "too many parties in a barrier");
+
         Barrier {
-            n,
-            state: Mutex::new(State {
-                count: 0,
-                generation_id: 0,
-            }),
+            state: AtomicState::new(n as State * ONE_REMAINING),
+            n: AtomicUsize::new(n),
             event: Event::new(),
+            unlocked: Event::new(),
+            completion: Mutex::new(completion),
+            current: AtomicPtr::new(ptr::null_mut()),
+            first: Record::new(),
+            records: UnsafeCell::new(Vec::new()),
         }
     }
 
@@ -55,6 +453,13 @@ impl Barrier {
     /// Returns a [`BarrierWaitResult`] indicating whether this task is the "leader", meaning the
     /// last task to call this method.
     ///
+    /// If the returned future is dropped before all tasks arrived, this task is no longer counted
+    /// as having reached the barrier. The same goes for the leader running the completion of a
+    /// barrier created with [`with_completion()`], which is dropped along with it. The other tasks
+    /// are already past the barrier once the leader started the completion.
+    ///
+    /// [`with_completion()`]: Barrier::with_completion()
+    ///
     /// # Examples
     ///
     /// ```
@@ -78,13 +483,47 @@ impl Barrier {
     ///     });
     /// }
     /// ```
-    pub fn wait(&self) -> BarrierWait<'_> {
-        BarrierWait::_new(BarrierWaitInner {
-            barrier: self,
-            lock: Some(self.state.lock()),
-            evl: None,
-            state: WaitState::Initial,
-        })
+    pub fn wait(&self) -> BarrierWait<'_, C> {
+        BarrierWait {
+            arrival: BarrierWaitInner {
+                barrier: self,
+                evl: None,
+                state: WaitState::Initial,
+            },
+            completion: None,
+            completing: false,
+        }
+    }
+
+    /// Blocks the current task until all tasks reach this point, giving up once `timer` expires.
+    ///
+    /// Returns a [`BarrierWaitResult`] indicating whether this task is the "leader", or
+    /// [`TimedOut`] if the timer expired first. A task that gives up is no longer counted as
+    /// having reached the barrier. Once all tasks arrived, the others wait for the leader to
+    /// finish the completion even if the timer expires. See [`Timer`] for the timers that can be
+    /// used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::Barrier;
+    /// use std::time::Duration;
+    ///
+    /// let barrier = Barrier::new(2);
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(barrier.wait_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    pub fn wait_timeout<Tm: Timer>(&self, timer: Tm) -> BarrierWaitTimeout<'_, Tm, C> {
+        BarrierWaitTimeout {
+            wait: self.wait(),
+            timer,
+            unbounded: false,
+        }
     }
 
     /// Blocks the current thread until all tasks reach this point.
@@ -127,161 +566,915 @@ impl Barrier {
     /// # std::thread::sleep(std::time::Duration::from_secs(1));
     /// ```
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    pub fn wait_blocking(&self) -> BarrierWaitResult {
-        self.wait().wait()
-    }
-}
-
-easy_wrapper! {
-    /// The future returned by [`Barrier::wait()`].
-    pub struct BarrierWait<'a>(BarrierWaitInner<'a> => BarrierWaitResult);
-    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-    pub(crate) wait();
-}
-
-pin_project_lite::pin_project! {
-    /// The future returned by [`Barrier::wait()`].
-    struct BarrierWaitInner<'a> {
-        // The barrier to wait on.
-        barrier: &'a Barrier,
-
-        // The ongoing mutex lock operation we are blocking on.
-        #[pin]
-        lock: Option<Lock<'a, State>>,
-
-        // An event listener for the `barrier.event` event.
-        evl: Option<EventListener>,
-
-        // The current state of the future.
-        state: WaitState,
-    }
-}
-
-impl fmt::Debug for BarrierWait<'_> {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("BarrierWait { .. }")
-    }
-}
-
-enum WaitState {
-    /// We are getting the original values of the state.
-    Initial,
-
-    /// We are waiting for the listener to complete.
-    Waiting { local_gen: u64 },
-
-    /// Waiting to re-acquire the lock to check the state again.
-    Reacquiring { local_gen: u64 },
-}
-
-impl EventListenerFuture for BarrierWaitInner<'_> {
-    type Output = BarrierWaitResult;
-
-    fn poll_with_strategy<'a, S: Strategy<'a>>(
-        self: Pin<&mut Self>,
-        strategy: &mut S,
-        cx: &mut S::Context,
-    ) -> Poll<Self::Output> {
-        let mut this = self.project();
-
-        loop {
-            match this.state {
-                WaitState::Initial => {
-                    // See if the lock is ready yet.
-                    let mut state = ready!(this
-                        .lock
-                        .as_mut()
-                        .as_pin_mut()
-                        .unwrap()
-                        .poll_with_strategy(strategy, cx));
-                    this.lock.as_mut().set(None);
-
-                    let local_gen = state.generation_id;
-                    state.count += 1;
-
-                    if state.count < this.barrier.n {
-                        // We need to wait for the event.
-                        *this.evl = Some(this.barrier.event.listen());
-                        *this.state = WaitState::Waiting { local_gen };
-                    } else {
-                        // We are the last one.
-                        state.count = 0;
-                        state.generation_id = state.generation_id.wrapping_add(1);
-                        this.barrier.event.notify(core::usize::MAX);
-                        return Poll::Ready(BarrierWaitResult { is_leader: true });
-                    }
-                }
-
-                WaitState::Waiting { local_gen } => {
-                    ready!(strategy.poll(this.evl, cx));
-
-                    // We are now re-acquiring the mutex.
-                    this.lock.as_mut().set(Some(this.barrier.state.lock()));
-                    *this.state = WaitState::Reacquiring {
-                        local_gen: *local_gen,
-                    };
-                }
+    pub fn wait_blocking(&self) -> BarrierWaitResult<C::Output> {
+        let wait = self.wait();
+        pin!(wait);
 
-                WaitState::Reacquiring { local_gen } => {
-                    // Acquire the local state again.
-                    let state = ready!(this
-                        .lock
-                        .as_mut()
-                        .as_pin_mut()
-                        .unwrap()
-                        .poll_with_strategy(strategy, cx));
-                    this.lock.set(None);
-
-                    if *local_gen == state.generation_id && state.count < this.barrier.n {
-                        // We need to wait for the event again.
-                        *this.evl = Some(this.barrier.event.listen());
-                        *this.state = WaitState::Waiting {
-                            local_gen: *local_gen,
-                        };
-                    } else {
-                        // We are ready, but not the leader.
-                        return Poll::Ready(BarrierWaitResult { is_leader: false });
-                    }
-                }
-            }
+        match wait.wait_deadline(None) {
+            Some(result) => result,
+            None => unreachable!(),
         }
     }
-}
-
-/// Returned by [`Barrier::wait()`] when all tasks have called it.
-///
-/// # Examples
-///
-/// ```
-/// # futures_lite::future::block_on(async {
-/// use async_lock::Barrier;
-///
-/// let barrier = Barrier::new(1);
-/// let barrier_wait_result = barrier.wait().await;
-/// # });
-/// ```
-#[derive(Debug, Clone)]
-pub struct BarrierWaitResult {
-    is_leader: bool,
-}
 
-impl BarrierWaitResult {
-    /// Returns `true` if this task was the last to call to [`Barrier::wait()`].
+    /// Blocks the current thread until all tasks reach this point, giving up after `timeout`.
+    ///
+    /// Returns a [`BarrierWaitResult`] indicating whether this task is the "leader", or [`None`]
+    /// if the other tasks did not arrive in time. A task that gives up is no longer counted as
+    /// having reached the barrier. Once all tasks arrived, the others wait for the leader to
+    /// finish the completion even past the timeout.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][`Barrier::wait`] method,
+    /// this method will block the current thread until the wait is complete or the timeout
+    /// expires.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a barrier can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
     ///
     /// # Examples
     ///
     /// ```
-    /// # futures_lite::future::block_on(async {
     /// use async_lock::Barrier;
-    /// use futures_lite::future;
+    /// use std::time::Duration;
     ///
     /// let barrier = Barrier::new(2);
-    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
-    /// assert_eq!(a.is_leader(), false);
-    /// assert_eq!(b.is_leader(), true);
-    /// # });
+    /// assert!(barrier.wait_blocking_timeout(Duration::from_millis(10)).is_none());
     /// ```
-    pub fn is_leader(&self) -> bool {
-        self.is_leader
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking_timeout(&self, timeout: Duration) -> Option<BarrierWaitResult<C::Output>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_blocking_deadline(deadline),
+            None => Some(self.wait_blocking()),
+        }
+    }
+
+    /// Blocks the current thread until all tasks reach this point, giving up at `deadline`.
+    ///
+    /// Returns a [`BarrierWaitResult`] indicating whether this task is the "leader", or [`None`]
+    /// if the other tasks did not arrive in time. A task that gives up is no longer counted as
+    /// having reached the barrier. Once all tasks arrived, the others wait for the leader to
+    /// finish the completion even past the deadline.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][`Barrier::wait`] method,
+    /// this method will block the current thread until the wait is complete or the deadline
+    /// is reached.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a barrier can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Barrier;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let barrier = Barrier::new(2);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert!(barrier.wait_blocking_deadline(deadline).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking_deadline(
+        &self,
+        deadline: Instant,
+    ) -> Option<BarrierWaitResult<C::Output>> {
+        let wait = self.wait();
+        pin!(wait);
+        wait.wait_deadline(Some(deadline))
+    }
+
+    /// Adds a party to the barrier.
+    ///
+    /// The current phase now also waits for the new party to arrive. Returns the current phase.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there would be more than `u32::MAX` parties, or `u16::MAX` on targets without
+    /// 64-bit atomics.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    ///
+    /// let barrier = Barrier::new(1);
+    /// assert_eq!(barrier.register(), 0);
+    ///
+    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+    /// assert_ne!(a.is_leader(), b.is_leader());
+    /// # });
+    /// ```
+    pub fn register(&self) -> u64 {
+        let state = self.lock_parties();
+        let n = self.n.load(Ordering::Relaxed);
+        if n == MAX_PARTIES {
+            self.unlock_parties(0);
+            panic!("too many parties in a barrier");
+        }
+        self.n.store(n + 1, Ordering::Relaxed);
+
+        // Count the new party while clearing `LOCKED`. While the leader runs the completion, this
+        // only matters if it gives up.
+        self.unlock_parties(ONE_REMAINING);
+        phase(state)
+    }
+
+    /// Waits until the barrier moves on from `phase`.
+    ///
+    /// Completes right away if the barrier is already past `phase`. Returns the new phase.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// let phase = barrier.arrive();
+    ///
+    /// let (new_phase, _) = future::zip(barrier.await_phase(phase), barrier.wait()).await;
+    /// assert_eq!(new_phase, phase + 1);
+    /// # });
+    /// ```
+    pub fn await_phase(&self, phase: u64) -> AwaitPhase<'_, C> {
+        AwaitPhase {
+            inner: AwaitPhaseInner {
+                barrier: self,
+                phase,
+                listener: None,
+            },
+        }
+    }
+
+    /// Waits until the barrier moves on from `phase`.
+    ///
+    /// Returns the new phase.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`await_phase`][`Barrier::await_phase`]
+    /// method, this method will block the current thread until the barrier moves on.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a barrier can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Barrier;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let barrier = Arc::new(Barrier::new(2));
+    /// let phase = barrier.arrive();
+    ///
+    /// let b = barrier.clone();
+    /// thread::spawn(move || b.arrive());
+    /// assert_eq!(barrier.await_phase_blocking(phase), phase + 1);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn await_phase_blocking(&self, phase: u64) -> u64 {
+        self.await_phase(phase).inner.wait()
+    }
+
+    /// Returns the current phase.
+    #[inline]
+    fn current_phase(&self) -> u64 {
+        phase(self.state.load(Ordering::Acquire))
+    }
+
+    /// Returns the record of the current phase.
+    #[inline]
+    fn current_record(&self) -> &Record<C::Output> {
+        let record = self.current.load(Ordering::Acquire);
+        if record.is_null() {
+            &self.first
+        } else {
+            // SAFETY: Records are only freed along with the barrier.
+            unsafe { &*record }
+        }
+    }
+
+    /// Tries to arrive at the current phase.
+    ///
+    /// If `read` is `true`, the arrival is counted as a reader of the record of the phase, unless
+    /// it is the leader.
+    fn try_arrive(&self, read: bool) -> Arrival<'_, C::Output> {
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            if state & COMPLETING != 0 {
+                return Arrival::Blocked;
+            }
+
+            // The record can't change unless the state does, which fails the exchange below.
+            let record = self.current_record();
+            if read {
+                record.readers.fetch_add(1, Ordering::Relaxed);
+            }
+
+            let (new, arrival) = if remaining(state) <= 1 {
+                // Hold everyone back while running the completion.
+                (
+                    (state & !REMAINING_MASK) | COMPLETING,
+                    Arrival::Leader(phase(state)),
+                )
+            } else {
+                (
+                    state - ONE_REMAINING,
+                    Arrival::Counted(phase(state), record),
+                )
+            };
+
+            match self
+                .state
+                .compare_exchange_weak(state, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    // The leader gets the output directly.
+                    if read && matches!(arrival, Arrival::Leader(_)) {
+                        record.leave();
+                    }
+                    return arrival;
+                }
+                Err(s) => {
+                    if read {
+                        record.leave();
+                    }
+                    state = s;
+                }
+            }
+        }
+    }
+
+    /// Takes back an arrival at `phase`.
+    ///
+    /// Returns `false` if the leader already started the completion, or the barrier moved on in
+    /// the meantime.
+    fn withdraw(&self, phase_id: u64) -> bool {
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            if phase(state) != phase_id || state & COMPLETING != 0 {
+                return false;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state + ONE_REMAINING,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return true,
+                Err(s) => state = s,
+            }
+        }
+    }
+
+    /// Moves on to the next phase as the leader, handing `output` to the released tasks.
+    fn release(&self, output: C::Output) -> BarrierWaitResult<C::Output> {
+        // SAFETY: Only the leader writes the output, and the tasks arrived at this phase only read
+        // it once the barrier moved on.
+        let record = self.current_record();
+        unsafe {
+            *record.output.get() = Some(output.clone());
+        }
+
+        let next = self.spare_record(record);
+        let next = if ptr::eq(next, &self.first) {
+            ptr::null_mut()
+        } else {
+            next as *const Record<C::Output> as *mut Record<C::Output>
+        };
+        self.current.store(next, Ordering::Release);
+
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            // Leave moving on to the task changing the number of parties, instead of waiting for it.
+            let new = if state & LOCKED != 0 {
+                state | RELEASING
+            } else {
+                next_phase(state, self.n.load(Ordering::Relaxed))
+            };
+
+            match self
+                .state
+                .compare_exchange_weak(state, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    if new & RELEASING == 0 {
+                        self.event.notify(usize::MAX);
+                    }
+                    break;
+                }
+                Err(s) => state = s,
+            }
+        }
+
+        BarrierWaitResult {
+            is_leader: true,
+            output,
+        }
+    }
+
+    /// Returns a record that no task reads anymore, other than `current`, for the next phase.
+    ///
+    /// A record is only reused once every task released from its phase took the output, so slow
+    /// tasks never see the output of a later phase.
+    fn spare_record(&self, current: &Record<C::Output>) -> &Record<C::Output> {
+        let is_spare =
+            |record: &Record<C::Output>| -> bool { !ptr::eq(record, current) && record.is_spare() };
+
+        if is_spare(&self.first) {
+            return &self.first;
+        }
+
+        // SAFETY: Only the leader uses the list of records.
+        let records = unsafe { &mut *self.records.get() };
+        let record = match records.iter().position(|record| is_spare(record)) {
+            Some(i) => &records[i],
+            None => {
+                records.push(Box::new(Record::new()));
+                &records[records.len() - 1]
+            }
+        };
+
+        // SAFETY: The boxed records stay in place until the barrier is dropped.
+        unsafe { &*(&**record as *const Record<C::Output>) }
+    }
+
+    /// Stops running the completion as the leader, which is no longer counted as having arrived.
+    fn abandon_completion(&self) {
+        // Clear `COMPLETING` and count the leader as missing again at once.
+        self.state
+            .fetch_add(ONE_REMAINING - COMPLETING, Ordering::AcqRel);
+
+        // Let the tasks held back by the completion arrive.
+        self.event.notify(usize::MAX);
+    }
+
+    /// Sets `LOCKED` to change the number of parties.
+    ///
+    /// Returns the locked state.
+    fn lock_parties(&self) -> State {
+        let mut listener = None;
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            // Another task is changing the number of parties, which only takes a moment.
+            if state & LOCKED != 0 {
+                pause(&self.unlocked, &mut listener);
+                state = self.state.load(Ordering::Acquire);
+                continue;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state | LOCKED,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return state | LOCKED,
+                Err(s) => state = s,
+            }
+        }
+    }
+
+    /// Clears `LOCKED`, adding `add` to the state.
+    ///
+    /// If the leader left moving on to the next phase to us, this does so instead.
+    fn unlock_parties(&self, add: State) {
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            let new = if state & RELEASING != 0 {
+                next_phase(state, self.n.load(Ordering::Relaxed))
+            } else {
+                (state + add) & !LOCKED
+            };
+
+            match self
+                .state
+                .compare_exchange_weak(state, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break,
+                Err(s) => state = s,
+            }
+        }
+
+        if state & RELEASING != 0 {
+            self.event.notify(usize::MAX);
+        }
+        self.unlocked.notify(1);
+    }
+}
+
+/// Waits a moment for another task to notify `event`.
+///
+/// This blocks the current thread on the first call after `listener` was registered, which is
+/// only done while another task is in the middle of an update that can't be awaited.
+#[inline]
+fn pause(event: &Event, listener: &mut Option<EventListener>) {
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    match listener.take() {
+        None => *listener = Some(event.listen()),
+        Some(listener) => listener.wait(),
+    }
+
+    // Without `std`, the thread can't be blocked.
+    #[cfg(not(all(feature = "std", not(target_family = "wasm"))))]
+    {
+        let _ = (event, listener);
+        core::hint::spin_loop();
+    }
+}
+
+/// The output of a phase, kept until every task released from it took a copy.
+struct Record<T> {
+    // The tasks counted as having arrived at the phase that will read the output, along with the
+    // ones trying to arrive.
+    readers: AtomicUsize,
+
+    // Written by the leader before the barrier moves on.
+    output: UnsafeCell<Option<T>>,
+}
+
+unsafe impl<T: Send + Sync> Sync for Record<T> {}
+
+impl<T> Record<T> {
+    const fn new() -> Record<T> {
+        Record {
+            readers: AtomicUsize::new(0),
+            output: UnsafeCell::new(None),
+        }
+    }
+
+    /// Returns `true` if no task will read the output.
+    #[inline]
+    fn is_spare(&self) -> bool {
+        self.readers.load(Ordering::Acquire) == 0
+    }
+
+    /// Stops counting a task as a reader.
+    #[inline]
+    fn leave(&self) {
+        self.readers.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T: Clone> Record<T> {
+    /// Takes a copy of the output as a task released from the phase.
+    fn read(&self) -> T {
+        // SAFETY: The output isn't written again until every reader left.
+        let output = unsafe { (*self.output.get()).clone() };
+        self.leave();
+        output.expect("barrier released without a completion output")
+    }
+}
+
+/// The outcome of trying to arrive at the barrier.
+enum Arrival<'a, T> {
+    /// We are counted as having arrived at this phase, whose output is kept in the record.
+    Counted(u64, &'a Record<T>),
+
+    /// We are the last one, and must run the completion for this phase.
+    Leader(u64),
+
+    /// The leader of the previous phase is still running the completion.
+    Blocked,
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Barrier::wait()`].
+    pub struct BarrierWait<'a, C: BarrierCompletion = ()> {
+        // Arriving at the barrier and waiting for it to be released.
+        arrival: BarrierWaitInner<'a, C>,
+
+        // The completion run as the leader.
+        #[pin]
+        completion: Option<C::Future>,
+
+        // We are the leader, holding everyone back while running the completion.
+        completing: bool,
+    }
+
+    impl<C: BarrierCompletion> PinnedDrop for BarrierWait<'_, C> {
+        fn drop(this: Pin<&mut Self>) {
+            let mut this = this.project();
+
+            if *this.completing {
+                this.completion.set(None);
+                this.arrival.barrier.abandon_completion();
+            }
+        }
+    }
+}
+
+impl<C: BarrierCompletion> fmt::Debug for BarrierWait<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BarrierWait { .. }")
+    }
+}
+
+impl<C: BarrierCompletion> BarrierWait<'_, C> {
+    /// Starts the completion as the leader.
+    fn lead(self: Pin<&mut Self>) {
+        let mut this = self.project();
+        *this.completing = true;
+
+        let completion = match this.arrival.barrier.completion.try_lock() {
+            Some(mut completion) => completion.complete(),
+            None => unreachable!("the completion is only run by the leader"),
+        };
+        this.completion.set(Some(completion));
+    }
+
+    /// Polls the completion, releasing the other tasks once it finishes.
+    fn poll_completion(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<BarrierWaitResult<C::Output>> {
+        let mut this = self.project();
+
+        let completion = this.completion.as_mut().as_pin_mut().unwrap();
+        let output = ready!(completion.poll(cx));
+        this.completion.set(None);
+        *this.completing = false;
+
+        Poll::Ready(this.arrival.barrier.release(output))
+    }
+
+    /// Stops waiting, no longer counting this task as having reached the barrier.
+    ///
+    /// Returns `false` if all tasks arrived already, in which case the future has to be polled
+    /// until the leader releases it. Otherwise, the future must not be polled afterwards.
+    fn withdraw(self: Pin<&mut Self>) -> bool {
+        let mut this = self.project();
+
+        if *this.completing {
+            this.completion.set(None);
+            *this.completing = false;
+            this.arrival.barrier.abandon_completion();
+            return true;
+        }
+
+        this.arrival.withdraw()
+    }
+
+    /// Blocks the current thread until the barrier is released, giving up at `deadline`.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    fn wait_deadline(
+        mut self: Pin<&mut Self>,
+        deadline: Option<Instant>,
+    ) -> Option<BarrierWaitResult<C::Output>> {
+        let arrival = self.as_mut().project().arrival;
+        let arrived = match deadline {
+            Some(deadline) => Pin::new(&mut *arrival)
+                .poll_with_strategy(&mut BlockingDeadline::new(deadline), &mut ()),
+            None => Pin::new(&mut *arrival).poll_with_strategy(&mut Blocking::default(), &mut ()),
+        };
+
+        match arrived {
+            Poll::Ready(Arrived::Released(result)) => return Some(result),
+            Poll::Ready(Arrived::Lead) => {}
+            Poll::Pending if arrival.withdraw() => return None,
+
+            // The leader started the completion before the deadline, so wait for it regardless.
+            Poll::Pending => {
+                return match Pin::new(&mut *arrival)
+                    .poll_with_strategy(&mut Blocking::default(), &mut ())
+                {
+                    Poll::Ready(Arrived::Released(result)) => Some(result),
+                    _ => unreachable!("only the leader runs the completion"),
+                };
+            }
+        }
+
+        // A completion that runs past the deadline is abandoned when the future is dropped.
+        self.as_mut().lead();
+        crate::block_on::block_on(deadline, |cx| self.as_mut().poll_completion(cx))
+    }
+}
+
+impl<C: BarrierCompletion> Future for BarrierWait<'_, C> {
+    type Output = BarrierWaitResult<C::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if !self.completing {
+            let arrival = Pin::new(self.as_mut().project().arrival);
+            match ready!(arrival.poll_with_strategy(&mut NonBlocking::default(), cx)) {
+                Arrived::Released(result) => return Poll::Ready(result),
+                Arrived::Lead => self.as_mut().lead(),
+            }
+        }
+
+        self.poll_completion(cx)
+    }
+}
+
+struct BarrierWaitInner<'a, C: BarrierCompletion> {
+    // The barrier to wait on.
+    barrier: &'a Barrier<C>,
+
+    // An event listener for the `barrier.event` event.
+    evl: Option<EventListener>,
+
+    // The current state of the future.
+    state: WaitState<'a, C::Output>,
+}
+
+impl<C: BarrierCompletion> Drop for BarrierWaitInner<'_, C> {
+    fn drop(&mut self) {
+        // Take our arrival back unless the barrier was released in the meantime.
+        if let WaitState::Waiting { phase, record } = self.state {
+            self.barrier.withdraw(phase);
+            record.leave();
+        }
+    }
+}
+
+/// The outcome of waiting at the barrier.
+enum Arrived<T> {
+    /// The barrier was released by the leader.
+    Released(BarrierWaitResult<T>),
+
+    /// We are the last one, and must run the completion.
+    Lead,
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Barrier::wait_timeout()`].
+    pub struct BarrierWaitTimeout<'a, Tm, C: BarrierCompletion = ()> {
+        // The wait operation.
+        #[pin]
+        wait: BarrierWait<'a, C>,
+
+        // The timer bounding the wait operation.
+        #[pin]
+        timer: Tm,
+
+        // The timer expired after all tasks arrived, so the wait is no longer bounded.
+        unbounded: bool,
+    }
+}
+
+impl<Tm, C: BarrierCompletion> fmt::Debug for BarrierWaitTimeout<'_, Tm, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("BarrierWaitTimeout { .. }")
+    }
+}
+
+impl<Tm: Timer, C: BarrierCompletion> Future for BarrierWaitTimeout<'_, Tm, C> {
+    type Output = Result<BarrierWaitResult<C::Output>, TimedOut>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if let Poll::Ready(result) = this.wait.as_mut().poll(cx) {
+            return Poll::Ready(Ok(result));
+        }
+
+        if *this.unbounded {
+            return Poll::Pending;
+        }
+
+        ready!(this.timer.poll_expired(cx));
+        if this.wait.as_mut().withdraw() {
+            return Poll::Ready(Err(TimedOut::new()));
+        }
+
+        // The leader started the completion in time, so wait for it to finish.
+        *this.unbounded = true;
+        this.wait.poll(cx).map(Ok)
+    }
+}
+
+enum WaitState<'a, T> {
+    /// We are arriving at the barrier.
+    Initial,
+
+    /// We are counted as having arrived at `phase`, and wait for the barrier to move on.
+    Waiting { phase: u64, record: &'a Record<T> },
+
+    /// The barrier was released, or we stopped waiting.
+    Done,
+}
+
+impl<C: BarrierCompletion> BarrierWaitInner<'_, C> {
+    /// Stops waiting, no longer counting this task as having reached the barrier.
+    ///
+    /// Returns `false` if all tasks arrived already, in which case the future has to be polled
+    /// until the leader releases it. Otherwise, the future must not be polled afterwards.
+    fn withdraw(&mut self) -> bool {
+        if let WaitState::Waiting { phase, record } = self.state {
+            if !self.barrier.withdraw(phase) {
+                return false;
+            }
+            record.leave();
+        }
+
+        self.evl = None;
+        self.state = WaitState::Done;
+        true
+    }
+}
+
+impl<C: BarrierCompletion> EventListenerFuture for BarrierWaitInner<'_, C> {
+    type Output = Arrived<C::Output>;
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            match this.state {
+                WaitState::Initial => match this.barrier.try_arrive(true) {
+                    Arrival::Counted(phase, record) => {
+                        this.evl = None;
+                        this.state = WaitState::Waiting { phase, record };
+                    }
+
+                    Arrival::Leader(_) => {
+                        this.state = WaitState::Done;
+                        return Poll::Ready(Arrived::Lead);
+                    }
+
+                    // Wait for the leader of the previous phase to finish the completion.
+                    Arrival::Blocked => {
+                        if this.evl.is_none() {
+                            this.evl = Some(this.barrier.event.listen());
+                        } else {
+                            ready!(strategy.poll(&mut this.evl, cx));
+                        }
+                    }
+                },
+
+                WaitState::Waiting { phase, record } => {
+                    if this.barrier.current_phase() != phase {
+                        // We are ready, but not the leader.
+                        this.state = WaitState::Done;
+                        return Poll::Ready(Arrived::Released(BarrierWaitResult {
+                            is_leader: false,
+                            output: record.read(),
+                        }));
+                    }
+
+                    if this.evl.is_none() {
+                        this.evl = Some(this.barrier.event.listen());
+                    } else {
+                        ready!(strategy.poll(&mut this.evl, cx));
+                    }
+                }
+
+                WaitState::Done => panic!("`BarrierWait` polled after completion"),
+            }
+        }
+    }
+}
+
+/// The future returned by [`Barrier::await_phase()`].
+pub struct AwaitPhase<'a, C: BarrierCompletion = ()> {
+    inner: AwaitPhaseInner<'a, C>,
+}
+
+pin_project_lite::pin_project! {
+    struct AwaitPhaseInner<'a, C: BarrierCompletion> {
+        // The barrier to wait on.
+        barrier: &'a Barrier<C>,
+
+        // The phase to wait out.
+        phase: u64,
+
+        // An event listener for the `barrier.event` event.
+        listener: Option<EventListener>,
+    }
+}
+
+impl<C: BarrierCompletion> fmt::Debug for AwaitPhase<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AwaitPhase { .. }")
+    }
+}
+
+impl<C: BarrierCompletion> Future for AwaitPhase<'_, C> {
+    type Output = u64;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<u64> {
+        let inner = Pin::new(&mut self.inner);
+        inner.poll_with_strategy(&mut NonBlocking::default(), cx)
+    }
+}
+
+impl<C: BarrierCompletion> EventListenerFuture for AwaitPhaseInner<'_, C> {
+    type Output = u64;
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<u64> {
+        let this = self.project();
+
+        loop {
+            let phase = this.barrier.current_phase();
+            if phase != *this.phase {
+                return Poll::Ready(phase);
+            }
+
+            if this.listener.is_none() {
+                *this.listener = Some(this.barrier.event.listen());
+            } else {
+                ready!(strategy.poll(this.listener, cx));
+            }
+        }
+    }
+}
+
+/// Returned by [`Barrier::wait()`] when all tasks have called it.
+///
+/// # Examples
+///
+/// ```
+/// # futures_lite::future::block_on(async {
+/// use async_lock::Barrier;
+///
+/// let barrier = Barrier::new(1);
+/// let barrier_wait_result = barrier.wait().await;
+/// # });
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarrierWaitResult<T = ()> {
+    is_leader: bool,
+    output: T,
+}
+
+impl<T> BarrierWaitResult<T> {
+    /// Returns `true` if this task was the last to call to [`Barrier::wait()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    ///
+    /// let barrier = Barrier::new(2);
+    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+    /// assert_eq!(a.is_leader(), false);
+    /// assert_eq!(b.is_leader(), true);
+    /// # });
+    /// ```
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+
+    /// Returns the output of the barrier's completion.
+    ///
+    /// This is `()` for barriers created without a completion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    /// use futures_lite::future;
+    ///
+    /// let barrier = Barrier::with_completion(2, || future::ready(7));
+    /// let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+    /// assert_eq!(*a.output(), 7);
+    /// assert_eq!(*b.output(), 7);
+    /// # });
+    /// ```
+    pub fn output(&self) -> &T {
+        &self.output
+    }
+
+    /// Returns the output of the barrier's completion, consuming the result.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Barrier;
+    ///
+    /// let barrier = Barrier::with_completion(1, || async { String::from("done") });
+    /// assert_eq!(barrier.wait().await.into_output(), "done");
+    /// # });
+    /// ```
+    pub fn into_output(self) -> T {
+        self.output
     }
 }
@@ -0,0 +1,62 @@
+//! A minimal executor running futures on the current thread, for the blocking methods that need
+//! to poll one.
+
+use core::future::Future;
+use core::task::{Context, Poll, Waker};
+
+use std::sync::Arc;
+use std::task::Wake;
+use std::thread::{self, Thread};
+use std::time::Instant;
+
+/// Polls with `poll` until it's ready, parking the current thread in between.
+///
+/// Returns `None` if `deadline` passed first.
+pub(crate) fn block_on<T>(
+    deadline: Option<Instant>,
+    mut poll: impl FnMut(&mut Context<'_>) -> Poll<T>,
+) -> Option<T> {
+    let waker = Waker::from(Arc::new(Unparker(thread::current())));
+    let cx = &mut Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = poll(cx) {
+            return Some(output);
+        }
+
+        match deadline {
+            None => thread::park(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return None;
+                }
+
+                thread::park_timeout(deadline - now);
+            }
+        }
+    }
+}
+
+/// Runs `future` to completion, parking the current thread while it's pending.
+pub(crate) fn block_on_future<F: Future>(future: F) -> F::Output {
+    pin!(future);
+
+    match block_on(None, |cx| future.as_mut().poll(cx)) {
+        Some(output) => output,
+        None => unreachable!("no deadline to pass"),
+    }
+}
+
+/// Wakes up a thread parked in [`block_on()`].
+struct Unparker(Thread);
+
+impl Wake for Unparker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
@@ -0,0 +1,727 @@
+use core::fmt;
+use core::pin::Pin;
+use core::task::Poll;
+
+use alloc::sync::Arc;
+
+use event_listener::{Event, EventListener};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+
+use crate::futures::{Lock, LockArc};
+use crate::{Mutex, MutexGuard, MutexGuardArc};
+
+/// An async condition variable.
+///
+/// Condition variables let a task release a [`Mutex`] and wait until another task signals that
+/// the data protected by the mutex may have changed. The mutex is reacquired before the wait
+/// completes.
+///
+/// Both [`MutexGuard`] and [`MutexGuardArc`] can be used with a condition variable.
+///
+/// # Examples
+///
+/// ```
+/// # futures_lite::future::block_on(async {
+/// use async_lock::{Condvar, Mutex};
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+/// let pair2 = pair.clone();
+///
+/// thread::spawn(move || {
+///     futures_lite::future::block_on(async {
+///         let (lock, cvar) = &*pair2;
+///         *lock.lock().await = true;
+///         cvar.notify_one();
+///     })
+/// });
+///
+/// let (lock, cvar) = &*pair;
+/// let mut started = lock.lock().await;
+/// while !*started {
+///     started = cvar.wait(started).await;
+/// }
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct Condvar {
+    /// Tasks waiting for a notification.
+    event: Event,
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// ```
+    pub const fn new() -> Condvar {
+        Condvar {
+            event: Event::new(),
+        }
+    }
+
+    /// Releases the mutex and waits for a notification.
+    ///
+    /// The mutex is released the first time the returned future is polled, and is reacquired
+    /// before the future completes. Spurious wakeups are possible, so the condition should be
+    /// checked again once the guard is returned. [`wait_while`][Condvar::wait_while] does this
+    /// automatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Condvar, Mutex};
+    /// use futures_lite::future;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let cvar = Condvar::new();
+    ///
+    /// let waiter = async {
+    ///     let mut guard = mutex.lock().await;
+    ///     while *guard == 0 {
+    ///         guard = cvar.wait(guard).await;
+    ///     }
+    ///     *guard
+    /// };
+    /// let notifier = async {
+    ///     *mutex.lock().await = 1;
+    ///     cvar.notify_all();
+    /// };
+    ///
+    /// assert_eq!(future::zip(waiter, notifier).await.0, 1);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn wait<'a, T: ?Sized>(&'a self, guard: MutexGuard<'a, T>) -> CondvarWait<'a, T> {
+        CondvarWait::_new(WaitInner::new(self, guard))
+    }
+
+    /// Releases the mutex and waits for a notification using the blocking strategy.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][Condvar::wait] method,
+    /// this method will block the current thread until a notification is received and the
+    /// mutex is reacquired.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a condition variable can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Condvar, Mutex};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     let (lock, cvar) = &*pair2;
+    ///     *lock.lock_blocking() = true;
+    ///     cvar.notify_one();
+    /// });
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let mut started = lock.lock_blocking();
+    /// while !*started {
+    ///     started = cvar.wait_blocking(started);
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking<'a, T: ?Sized>(&'a self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        self.wait(guard).wait()
+    }
+
+    /// Releases the mutex and waits until the condition returns `false`.
+    ///
+    /// The condition is checked before waiting and after every notification, always while the
+    /// mutex is held. The returned guard is the one under which the condition returned `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Condvar, Mutex};
+    /// use futures_lite::future;
+    ///
+    /// let mutex = Mutex::new(0);
+    /// let cvar = Condvar::new();
+    ///
+    /// let waiter = async {
+    ///     let guard = cvar.wait_while(mutex.lock().await, |n| *n < 3).await;
+    ///     *guard
+    /// };
+    /// let notifier = async {
+    ///     for _ in 0..3 {
+    ///         *mutex.lock().await += 1;
+    ///         cvar.notify_one();
+    ///         future::yield_now().await;
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(future::zip(waiter, notifier).await.0, 3);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn wait_while<'a, T: ?Sized, F>(
+        &'a self,
+        guard: MutexGuard<'a, T>,
+        condition: F,
+    ) -> CondvarWaitWhile<'a, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        CondvarWaitWhile::_new(WaitWhileInner::new(self, guard, condition))
+    }
+
+    /// Releases the mutex and waits until the condition returns `false` using the blocking
+    /// strategy.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait_while`][Condvar::wait_while]
+    /// method, this method will block the current thread until the condition returns `false`.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a condition variable can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Condvar, Mutex};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// thread::spawn(move || {
+    ///     let (lock, cvar) = &*pair2;
+    ///     *lock.lock_blocking() = true;
+    ///     cvar.notify_one();
+    /// });
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let started = cvar.wait_while_blocking(lock.lock_blocking(), |started| !*started);
+    /// assert!(*started);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_while_blocking<'a, T: ?Sized, F>(
+        &'a self,
+        guard: MutexGuard<'a, T>,
+        condition: F,
+    ) -> MutexGuard<'a, T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.wait_while(guard, condition).wait()
+    }
+
+    /// Releases an owned mutex guard and waits for a notification.
+    ///
+    /// This behaves like [`wait`][Condvar::wait], but works with a [`MutexGuardArc`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Condvar, Mutex};
+    /// use futures_lite::future;
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new(0));
+    /// let cvar = Condvar::new();
+    ///
+    /// let waiter = async {
+    ///     let mut guard = mutex.lock_arc().await;
+    ///     while *guard == 0 {
+    ///         guard = cvar.wait_arc(guard).await;
+    ///     }
+    ///     *guard
+    /// };
+    /// let notifier = async {
+    ///     *mutex.lock().await = 1;
+    ///     cvar.notify_all();
+    /// };
+    ///
+    /// assert_eq!(future::zip(waiter, notifier).await.0, 1);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn wait_arc<T: ?Sized>(&self, guard: MutexGuardArc<T>) -> CondvarWaitArc<'_, T> {
+        CondvarWaitArc::_new(WaitInner::new(self, guard))
+    }
+
+    /// Releases an owned mutex guard and waits for a notification using the blocking strategy.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait_arc`][Condvar::wait_arc] method,
+    /// this method will block the current thread until a notification is received and the
+    /// mutex is reacquired.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a condition variable can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Condvar, Mutex};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let mutex = Arc::new(Mutex::new(false));
+    /// let cvar = Arc::new(Condvar::new());
+    ///
+    /// let (mutex2, cvar2) = (mutex.clone(), cvar.clone());
+    /// thread::spawn(move || {
+    ///     *mutex2.lock_blocking() = true;
+    ///     cvar2.notify_one();
+    /// });
+    ///
+    /// let mut started = mutex.lock_arc_blocking();
+    /// while !*started {
+    ///     started = cvar.wait_arc_blocking(started);
+    /// }
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_arc_blocking<T: ?Sized>(&self, guard: MutexGuardArc<T>) -> MutexGuardArc<T> {
+        self.wait_arc(guard).wait()
+    }
+
+    /// Releases an owned mutex guard and waits until the condition returns `false`.
+    ///
+    /// This behaves like [`wait_while`][Condvar::wait_while], but works with a
+    /// [`MutexGuardArc`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Condvar, Mutex};
+    /// use futures_lite::future;
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new(0));
+    /// let cvar = Condvar::new();
+    ///
+    /// let waiter = async {
+    ///     let guard = cvar.wait_while_arc(mutex.lock_arc().await, |n| *n < 3).await;
+    ///     *guard
+    /// };
+    /// let notifier = async {
+    ///     for _ in 0..3 {
+    ///         *mutex.lock().await += 1;
+    ///         cvar.notify_one();
+    ///         future::yield_now().await;
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(future::zip(waiter, notifier).await.0, 3);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn wait_while_arc<T: ?Sized, F>(
+        &self,
+        guard: MutexGuardArc<T>,
+        condition: F,
+    ) -> CondvarWaitWhileArc<'_, T, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        CondvarWaitWhileArc::_new(WaitWhileInner::new(self, guard, condition))
+    }
+
+    /// Releases an owned mutex guard and waits until the condition returns `false` using the
+    /// blocking strategy.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`wait_while_arc`][Condvar::wait_while_arc] method, this method will block the current
+    /// thread until the condition returns `false`.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a condition variable can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Condvar, Mutex};
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let mutex = Arc::new(Mutex::new(false));
+    /// let cvar = Arc::new(Condvar::new());
+    ///
+    /// let (mutex2, cvar2) = (mutex.clone(), cvar.clone());
+    /// thread::spawn(move || {
+    ///     *mutex2.lock_blocking() = true;
+    ///     cvar2.notify_one();
+    /// });
+    ///
+    /// let started = cvar.wait_while_arc_blocking(mutex.lock_arc_blocking(), |s| !*s);
+    /// assert!(*started);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_while_arc_blocking<T: ?Sized, F>(
+        &self,
+        guard: MutexGuardArc<T>,
+        condition: F,
+    ) -> MutexGuardArc<T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.wait_while_arc(guard, condition).wait()
+    }
+
+    /// Wakes up one task waiting on this condition variable.
+    ///
+    /// Every call wakes up a different waiter, if there is one. Notifications are not stored:
+    /// if no task is waiting, this does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// cvar.notify_one();
+    /// ```
+    #[inline]
+    pub fn notify_one(&self) {
+        self.event.notify_additional(1);
+    }
+
+    /// Wakes up all tasks waiting on this condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// cvar.notify_all();
+    /// ```
+    #[inline]
+    pub fn notify_all(&self) {
+        self.event.notify(usize::MAX);
+    }
+}
+
+impl Default for Condvar {
+    #[inline]
+    fn default() -> Condvar {
+        Condvar::new()
+    }
+}
+
+/// A mutex guard that can be released and reacquired by a [`Condvar`].
+trait CondvarGuard: Sized {
+    /// A handle to the mutex that is kept while the lock is released.
+    type Mutex;
+
+    /// The future that reacquires the mutex.
+    type Relock;
+
+    /// Releases the lock, returning a handle to the mutex.
+    fn unlock(self) -> Self::Mutex;
+
+    /// Starts reacquiring the mutex.
+    fn relock(mutex: Self::Mutex) -> Self::Relock;
+
+    /// Polls the future that reacquires the mutex.
+    fn poll_relock<'x, S: Strategy<'x>>(
+        relock: Pin<&mut Self::Relock>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self>;
+}
+
+impl<'a, T: ?Sized> CondvarGuard for MutexGuard<'a, T> {
+    type Mutex = &'a Mutex<T>;
+    type Relock = Lock<'a, T>;
+
+    #[inline]
+    fn unlock(self) -> Self::Mutex {
+        MutexGuard::source(&self)
+    }
+
+    #[inline]
+    fn relock(mutex: Self::Mutex) -> Self::Relock {
+        mutex.lock()
+    }
+
+    #[inline]
+    fn poll_relock<'x, S: Strategy<'x>>(
+        relock: Pin<&mut Self::Relock>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self> {
+        relock.poll_with_strategy(strategy, cx)
+    }
+}
+
+impl<T: ?Sized> CondvarGuard for MutexGuardArc<T> {
+    type Mutex = Arc<Mutex<T>>;
+    type Relock = LockArc<T>;
+
+    #[inline]
+    fn unlock(self) -> Self::Mutex {
+        let mutex = MutexGuardArc::into_arc(self);
+
+        // SAFETY: the guard held the lock and has been consumed without unlocking.
+        unsafe {
+            mutex.unlock_unchecked();
+        }
+
+        mutex
+    }
+
+    #[inline]
+    fn relock(mutex: Self::Mutex) -> Self::Relock {
+        mutex.lock_arc()
+    }
+
+    #[inline]
+    fn poll_relock<'x, S: Strategy<'x>>(
+        relock: Pin<&mut Self::Relock>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self> {
+        relock.poll_with_strategy(strategy, cx)
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Condvar::wait`].
+    pub struct CondvarWait<'a, T: ?Sized>(WaitInner<'a, MutexGuard<'a, T>> => MutexGuard<'a, T>);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl<T: ?Sized> fmt::Debug for CondvarWait<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CondvarWait { .. }")
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Condvar::wait_arc`].
+    pub struct CondvarWaitArc<'a, T: ?Sized>(WaitInner<'a, MutexGuardArc<T>> => MutexGuardArc<T>);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl<T: ?Sized> fmt::Debug for CondvarWaitArc<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CondvarWaitArc { .. }")
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Releases a mutex guard, waits for a notification and then reacquires the mutex.
+    struct WaitInner<'a, G: CondvarGuard> {
+        // The condition variable being waited on.
+        condvar: &'a Condvar,
+
+        // The current state of the future.
+        #[pin]
+        state: WaitState<G>,
+    }
+}
+
+pin_project_lite::pin_project! {
+    #[project = WaitStateProj]
+    enum WaitState<G: CondvarGuard> {
+        // The future hasn't been polled yet, so we still hold the lock.
+        Locked { guard: Option<G> },
+
+        // The lock has been released and we are waiting for a notification.
+        Waiting { mutex: Option<G::Mutex>, listener: Option<EventListener> },
+
+        // We were notified and are reacquiring the lock.
+        Relocking { #[pin] relock: G::Relock },
+
+        // The future has completed.
+        Done,
+    }
+}
+
+impl<'a, G: CondvarGuard> WaitInner<'a, G> {
+    #[inline]
+    fn new(condvar: &'a Condvar, guard: G) -> Self {
+        WaitInner {
+            condvar,
+            state: WaitState::Locked { guard: Some(guard) },
+        }
+    }
+}
+
+impl<G: CondvarGuard> EventListenerFuture for WaitInner<'_, G> {
+    type Output = G;
+
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            match this.state.as_mut().project() {
+                WaitStateProj::Locked { guard } => {
+                    let guard = guard.take().unwrap();
+
+                    // Start listening before releasing the lock, so that a notification sent
+                    // right after the lock is released is not missed.
+                    let listener = this.condvar.event.listen();
+                    let mutex = guard.unlock();
+
+                    this.state.as_mut().set(WaitState::Waiting {
+                        mutex: Some(mutex),
+                        listener: Some(listener),
+                    });
+                }
+
+                WaitStateProj::Waiting { mutex, listener } => {
+                    ready!(strategy.poll(listener, cx));
+
+                    let mutex = mutex.take().unwrap();
+                    this.state.as_mut().set(WaitState::Relocking {
+                        relock: G::relock(mutex),
+                    });
+                }
+
+                WaitStateProj::Relocking { relock } => {
+                    let guard = ready!(G::poll_relock(relock, strategy, cx));
+                    this.state.as_mut().set(WaitState::Done);
+                    return Poll::Ready(guard);
+                }
+
+                WaitStateProj::Done => panic!("future polled after completion"),
+            }
+        }
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Condvar::wait_while`].
+    pub struct CondvarWaitWhile<'a, T: ?Sized, F>(
+        WaitWhileInner<'a, MutexGuard<'a, T>, F> => MutexGuard<'a, T>
+    )
+    where
+        F: FnMut(&mut T) -> bool;
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl<T: ?Sized, F> fmt::Debug for CondvarWaitWhile<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CondvarWaitWhile { .. }")
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Condvar::wait_while_arc`].
+    pub struct CondvarWaitWhileArc<'a, T: ?Sized, F>(
+        WaitWhileInner<'a, MutexGuardArc<T>, F> => MutexGuardArc<T>
+    )
+    where
+        F: FnMut(&mut T) -> bool;
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl<T: ?Sized, F> fmt::Debug for CondvarWaitWhileArc<'_, T, F>
+where
+    F: FnMut(&mut T) -> bool,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CondvarWaitWhileArc { .. }")
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Waits on a condition variable until the condition returns `false`.
+    struct WaitWhileInner<'a, G: CondvarGuard, F> {
+        // The condition variable being waited on.
+        condvar: &'a Condvar,
+
+        // The guard, if we are currently holding the lock.
+        guard: Option<G>,
+
+        // The ongoing wait, if we are not holding the lock.
+        #[pin]
+        wait: Option<WaitInner<'a, G>>,
+
+        // The condition to check.
+        condition: F,
+    }
+}
+
+impl<'a, G: CondvarGuard, F> WaitWhileInner<'a, G, F> {
+    #[inline]
+    fn new(condvar: &'a Condvar, guard: G, condition: F) -> Self {
+        WaitWhileInner {
+            condvar,
+            guard: Some(guard),
+            wait: None,
+            condition,
+        }
+    }
+}
+
+impl<G, F> EventListenerFuture for WaitWhileInner<'_, G, F>
+where
+    G: CondvarGuard + core::ops::DerefMut,
+    F: FnMut(&mut G::Target) -> bool,
+{
+    type Output = G;
+
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        loop {
+            // Finish waiting for a notification.
+            if let Some(wait) = this.wait.as_mut().as_pin_mut() {
+                let guard = ready!(wait.poll_with_strategy(strategy, cx));
+                this.wait.set(None);
+                *this.guard = Some(guard);
+            }
+
+            let mut guard = this.guard.take().expect("future polled after completion");
+
+            // Check the condition while holding the lock.
+            if !(this.condition)(&mut *guard) {
+                return Poll::Ready(guard);
+            }
+
+            this.wait.set(Some(WaitInner::new(this.condvar, guard)));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Blocking waits that give up once a deadline passes.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::time::Instant;
+
+use event_listener::{EventListener, Listener};
+use event_listener_strategy::Strategy;
+
+/// A strategy that blocks the current thread until the listener is notified or the deadline
+/// passes.
+///
+/// Once the deadline passes, the polled future returns `Poll::Pending` and must not be polled
+/// again. Dropping it then cancels the operation.
+pub(crate) struct BlockingDeadline {
+    deadline: Instant,
+}
+
+impl BlockingDeadline {
+    #[inline]
+    pub(crate) fn new(deadline: Instant) -> BlockingDeadline {
+        BlockingDeadline { deadline }
+    }
+}
+
+impl Strategy<'_> for BlockingDeadline {
+    type Context = ();
+    type Future = Expired;
+
+    #[inline]
+    fn wait(&mut self, evl: EventListener) -> Self::Future {
+        Expired(evl.wait_deadline(self.deadline).is_none())
+    }
+
+    #[inline]
+    fn poll<T, L: Listener<T> + Unpin>(
+        &mut self,
+        event_listener: &mut Option<L>,
+        _context: &mut Self::Context,
+    ) -> Poll<T> {
+        match event_listener
+            .take()
+            .expect("`event_listener` should never be `None`")
+            .wait_deadline(self.deadline)
+        {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
+/// The future returned by [`BlockingDeadline::wait()`].
+///
+/// It is never ready if the deadline passed.
+pub(crate) struct Expired(bool);
+
+impl Future for Expired {
+    type Output = ();
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Pending
+        } else {
+            Poll::Ready(())
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use event_listener::Event;
+use event_listener_strategy::{easy_wrapper, EventListenerFuture};
+
+use crate::wait_group::CountWaitInner;
+
+/// A one-shot latch that opens once counted down to zero.
+///
+/// [`count_down()`][Latch::count_down] never waits, and [`wait()`][Latch::wait] completes once
+/// the count reaches zero. Unlike a [`Barrier`][crate::Barrier], the tasks counting down and the
+/// tasks waiting can be different, and the latch never resets: once open, it stays open.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Latch;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let ready = Arc::new(Latch::new(3));
+///
+/// for _ in 0..3 {
+///     let ready = ready.clone();
+///     thread::spawn(move || {
+///         // Start a subsystem, then signal that it is ready.
+///         ready.count_down();
+///     });
+/// }
+///
+/// # futures_lite::future::block_on(async {
+/// ready.wait().await;
+/// assert!(ready.is_open());
+/// # });
+/// ```
+pub struct Latch {
+    /// The number of counts left before the latch opens.
+    count: AtomicUsize,
+
+    /// Tasks waiting for the latch to open.
+    event: Event,
+}
+
+impl Latch {
+    /// Creates a new latch that opens after `n` counts.
+    ///
+    /// A latch created with a count of zero is open from the start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    ///
+    /// let latch = Latch::new(5);
+    /// assert!(!latch.is_open());
+    /// assert!(Latch::new(0).is_open());
+    /// ```
+    pub const fn new(n: usize) -> Latch {
+        Latch {
+            count: AtomicUsize::new(n),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the number of counts left before the latch opens.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    ///
+    /// let latch = Latch::new(2);
+    /// latch.count_down();
+    /// assert_eq!(latch.count(), 1);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if the latch is open.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    ///
+    /// let latch = Latch::new(1);
+    /// assert!(!latch.is_open());
+    ///
+    /// latch.count_down();
+    /// assert!(latch.is_open());
+    /// ```
+    pub fn is_open(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Decrements the count, opening the latch and waking up all waiting tasks once it reaches
+    /// zero.
+    ///
+    /// Counting down an open latch does nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    ///
+    /// let latch = Latch::new(1);
+    /// latch.count_down();
+    /// latch.count_down();
+    /// assert_eq!(latch.count(), 0);
+    /// ```
+    pub fn count_down(&self) {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            if count == 0 {
+                return;
+            }
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(c) => count = c,
+            }
+        }
+
+        if count == 1 {
+            self.event.notify(usize::MAX);
+        }
+    }
+
+    /// Waits until the latch is open.
+    ///
+    /// Completes right away if it already is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    /// use futures_lite::future;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let latch = Latch::new(1);
+    /// assert!(future::poll_once(latch.wait()).await.is_none());
+    ///
+    /// latch.count_down();
+    /// latch.wait().await;
+    /// # });
+    /// ```
+    pub fn wait(&self) -> LatchWait<'_> {
+        LatchWait::_new(CountWaitInner::new(&self.count, &self.event))
+    }
+
+    /// Waits until the latch is open.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][Latch::wait] method,
+    /// this method will block the current thread until the latch is open.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Latch` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Latch;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let latch = Arc::new(Latch::new(1));
+    /// let latch2 = latch.clone();
+    ///
+    /// thread::spawn(move || latch2.count_down());
+    /// latch.wait_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking(&self) {
+        self.wait().wait();
+    }
+}
+
+impl fmt::Debug for Latch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Latch")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Latch::wait`].
+    pub struct LatchWait<'a>(CountWaitInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl fmt::Debug for LatchWait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LatchWait { .. }")
+    }
+}
@@ -0,0 +1,363 @@
+use alloc::boxed::Box;
+
+use core::fmt;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::once_cell::{InfallibleInit, Infallibly, Initialize, Initializer};
+use crate::{Mutex, OnceCell};
+
+/// A value that is initialized asynchronously on first access.
+///
+/// The initializer is a closure returning a future. It is run by the first task to call
+/// [`force()`][Lazy::force], while every other task waits for it to finish. If that task is
+/// cancelled or its initializer panics, the value is left uninitialized and the next caller of
+/// [`force()`][Lazy::force] runs the initializer again, so a `Lazy` is never poisoned.
+///
+/// The default initializer type is a function pointer returning a boxed future, which lets a
+/// `Lazy` be used in `static` items.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Lazy;
+///
+/// static GREETING: Lazy<String> = Lazy::new(|| Box::pin(async { "Hello".to_string() }));
+///
+/// # futures_lite::future::block_on(async {
+/// assert_eq!(GREETING.get(), None);
+/// assert_eq!(GREETING.force().await, "Hello");
+/// assert_eq!(GREETING.get().map(|s| s.as_str()), Some("Hello"));
+/// # });
+/// ```
+pub struct Lazy<T, F = fn() -> Pin<Box<dyn Future<Output = T> + Send>>> {
+    /// The value, once initialized.
+    cell: OnceCell<T>,
+
+    /// The initializer.
+    ///
+    /// Only the task initializing `cell` locks it, so locking never has to wait.
+    init: Mutex<F>,
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new `Lazy` with the given initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 1 + 1 });
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(lazy.force().await, &2);
+    /// # });
+    /// ```
+    pub const fn new(init: F) -> Lazy<T, F> {
+        Lazy {
+            cell: OnceCell::new(),
+            init: Mutex::new(init),
+        }
+    }
+
+    /// Returns the value if it has already been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 1 + 1 });
+    /// assert_eq!(lazy.get(), None);
+    /// # futures_lite::future::block_on(async {
+    /// lazy.force().await;
+    /// # });
+    /// assert_eq!(lazy.get(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+impl<T, F, Fut> Lazy<T, F>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    /// Returns the value, initializing it first if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 1 + 1 });
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(lazy.force().await, &2);
+    /// # });
+    /// ```
+    pub fn force(&self) -> LazyForce<'_, T, F, Fut> {
+        LazyForce {
+            inner: Initialize::new(&self.cell, Infallibly(Start(&self.init))),
+        }
+    }
+
+    /// Returns the value, initializing it first if needed.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than awaiting, this method blocks the current thread of execution, both while
+    /// waiting for another task to initialize the value and while running the initializer.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be used
+    /// in synchronous code that shares a `Lazy` with asynchronous code. Calling this method in an
+    /// asynchronous context may result in deadlocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 1 + 1 });
+    /// assert_eq!(lazy.force_blocking(), &2);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn force_blocking(&self) -> &T {
+        self.cell.get_or_init_blocking(|| {
+            let init = (*self.init.lock_blocking())();
+            crate::block_on::block_on_future(init)
+        })
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Lazy").field(&self.cell).finish()
+    }
+}
+
+/// A value that is initialized asynchronously on first access, with an initializer that may fail.
+///
+/// This works like [`Lazy`], except that the initializer's future resolves to a `Result`. When
+/// it fails, the error is returned to the task that ran it and the value is left uninitialized,
+/// so the next call to [`force()`][TryLazy::force] tries again.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::TryLazy;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static ATTEMPTS: AtomicUsize = AtomicUsize::new(0);
+/// static VALUE: TryLazy<usize, &str> = TryLazy::new(|| {
+///     Box::pin(async {
+///         match ATTEMPTS.fetch_add(1, Ordering::SeqCst) {
+///             0 => Err("not yet"),
+///             n => Ok(n),
+///         }
+///     })
+/// });
+///
+/// # futures_lite::future::block_on(async {
+/// assert_eq!(VALUE.force().await, Err("not yet"));
+/// assert_eq!(VALUE.force().await, Ok(&1));
+/// assert_eq!(VALUE.force().await, Ok(&1));
+/// # });
+/// ```
+pub struct TryLazy<T, E, F = fn() -> Pin<Box<dyn Future<Output = Result<T, E>> + Send>>> {
+    /// The value, once initialized.
+    cell: OnceCell<T>,
+
+    /// The initializer.
+    ///
+    /// Only the task initializing `cell` locks it, so locking never has to wait.
+    init: Mutex<F>,
+
+    /// The error type of the initializer.
+    _marker: PhantomData<fn() -> E>,
+}
+
+impl<T, E, F> TryLazy<T, E, F> {
+    /// Creates a new `TryLazy` with the given initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::TryLazy;
+    ///
+    /// let lazy = TryLazy::new(|| async { "1".parse::<i32>() });
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(lazy.force().await, Ok(&1));
+    /// # });
+    /// ```
+    pub const fn new(init: F) -> TryLazy<T, E, F> {
+        TryLazy {
+            cell: OnceCell::new(),
+            init: Mutex::new(init),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the value if it has already been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::TryLazy;
+    ///
+    /// let lazy = TryLazy::new(|| async { "1".parse::<i32>() });
+    /// assert_eq!(lazy.get(), None);
+    /// # futures_lite::future::block_on(async {
+    /// lazy.force().await.unwrap();
+    /// # });
+    /// assert_eq!(lazy.get(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+impl<T, E, F, Fut> TryLazy<T, E, F>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    /// Returns the value, initializing it first if needed.
+    ///
+    /// If the initializer fails, its error is returned and the value stays uninitialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::TryLazy;
+    ///
+    /// let lazy = TryLazy::new(|| async { "x".parse::<i32>() });
+    /// # futures_lite::future::block_on(async {
+    /// assert!(lazy.force().await.is_err());
+    /// assert_eq!(lazy.get(), None);
+    /// # });
+    /// ```
+    pub fn force(&self) -> TryLazyForce<'_, T, F, Fut> {
+        TryLazyForce {
+            inner: Initialize::new(&self.cell, Start(&self.init)),
+        }
+    }
+
+    /// Returns the value, initializing it first if needed.
+    ///
+    /// If the initializer fails, its error is returned and the value stays uninitialized.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than awaiting, this method blocks the current thread of execution, both while
+    /// waiting for another task to initialize the value and while running the initializer.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be used
+    /// in synchronous code that shares a `TryLazy` with asynchronous code. Calling this method
+    /// in an asynchronous context may result in deadlocks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::TryLazy;
+    ///
+    /// let lazy = TryLazy::new(|| async { "1".parse::<i32>() });
+    /// assert_eq!(lazy.force_blocking(), Ok(&1));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn force_blocking(&self) -> Result<&T, E> {
+        self.cell.get_or_try_init_blocking(|| {
+            let init = (*self.init.lock_blocking())();
+            crate::block_on::block_on_future(init)
+        })
+    }
+}
+
+impl<T: fmt::Debug, E, F> fmt::Debug for TryLazy<T, E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TryLazy").field(&self.cell).finish()
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Lazy::force`].
+    pub struct LazyForce<'a, T, F, Fut> {
+        #[pin]
+        inner: Initialize<&'a OnceCell<T>, T, Infallibly<Start<'a, F>>, InfallibleInit<Fut>>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for LazyForce<'_, T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LazyForce { .. }")
+    }
+}
+
+impl<'a, T, F, Fut> Future for LazyForce<'a, T, F, Fut>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = &'a T;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        match ready!(inner.as_mut().poll(cx)) {
+            // SAFETY: We know that the value is initialized, so it is safe to
+            // read it.
+            Ok(()) => Poll::Ready(unsafe { inner.cell().get_unchecked() }),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`TryLazy::force`].
+    pub struct TryLazyForce<'a, T, F, Fut> {
+        #[pin]
+        inner: Initialize<&'a OnceCell<T>, T, Start<'a, F>, Fut>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for TryLazyForce<'_, T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TryLazyForce { .. }")
+    }
+}
+
+impl<'a, T, E, F, Fut> Future for TryLazyForce<'a, T, F, Fut>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<&'a T, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        ready!(inner.as_mut().poll(cx))?;
+
+        // SAFETY: We know that the value is initialized, so it is safe to
+        // read it.
+        Poll::Ready(Ok(unsafe { inner.cell().get_unchecked() }))
+    }
+}
+
+/// Starts the initializer of a `Lazy` or `TryLazy`.
+struct Start<'a, F>(&'a Mutex<F>);
+
+impl<F: FnMut() -> Fut, Fut> Initializer<Fut> for Start<'_, F> {
+    #[inline]
+    fn start(self) -> Fut {
+        let mut init = self
+            .0
+            .try_lock()
+            .expect("the initializer is only locked while initializing the cell");
+        (*init)()
+    }
+}
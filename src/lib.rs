@@ -2,10 +2,21 @@
 //!
 //! This crate provides the following primitives:
 //!
+//! * [`AsyncCell`] - a cached value that can be refreshed, invalidated or expire.
+//! * [`AutoResetEvent`] - an event that lets a single task through each time it is set.
 //! * [`Barrier`] - enables tasks to synchronize all together at the same time.
+//! * [`Condvar`] - lets tasks wait until the data protected by a [`Mutex`] changes.
+//! * [`Latch`] - a one-shot latch that opens once counted down to zero.
+//! * [`Lazy`] - a value initialized asynchronously on first access.
+//! * [`ManualResetEvent`] - an event that lets all tasks through while it is set.
 //! * [`Mutex`] - a mutual exclusion lock.
+//! * [`Notify`] - wakes up tasks waiting for something to happen.
+//! * [`OnceMap`] - a map whose values are initialized at most once per key.
 //! * [`RwLock`] - a reader-writer lock, allowing any number of readers or a single writer.
 //! * [`Semaphore`] - limits the number of concurrent operations.
+//! * [`Singleflight`] - deduplicates concurrent calls with the same key.
+//! * [`WaitGroup`] - waits for a set of tasks to finish.
+//! * [`Watch`] - a single value broadcast to tasks watching for changes.
 //!
 //! ## Relationship with `std::sync`
 //!
@@ -71,30 +82,77 @@ macro_rules! pin {
     }
 }
 
+mod async_cell;
 mod barrier;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+mod block_on;
+mod condvar;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+mod deadline;
+mod latch;
+mod lazy;
 mod mutex;
+mod notify;
 mod once_cell;
+mod once_map;
+mod reset_event;
 mod rwlock;
 mod semaphore;
+mod singleflight;
+mod sync_mutex;
+mod timer;
+mod wait_group;
+mod watch;
 
-pub use barrier::{Barrier, BarrierWaitResult};
-pub use mutex::{Mutex, MutexGuard, MutexGuardArc};
-pub use once_cell::OnceCell;
+pub use async_cell::{AsyncCell, Clock};
+pub use barrier::{Barrier, BarrierCompletion, BarrierWaitResult, SyncCompletion};
+pub use condvar::Condvar;
+pub use latch::Latch;
+pub use lazy::{Lazy, TryLazy};
+pub use mutex::{
+    Fairness, MappedMutexGuard, MappedMutexGuardArc, Mutex, MutexGuard, MutexGuardArc,
+};
+pub use notify::Notify;
+pub use once_cell::{OnceCell, OnceCellGuardArc};
+pub use once_map::OnceMap;
+pub use reset_event::{AutoResetEvent, ManualResetEvent};
 pub use rwlock::{
-    RwLock, RwLockReadGuard, RwLockReadGuardArc, RwLockUpgradableReadGuard,
-    RwLockUpgradableReadGuardArc, RwLockWriteGuard, RwLockWriteGuardArc,
+    MappedRwLockReadGuardArc, MappedRwLockWriteGuardArc, RwLock, RwLockPolicy, RwLockReadGuard,
+    RwLockReadGuardArc, RwLockUpgradableReadGuard, RwLockUpgradableReadGuardArc, RwLockWriteGuard,
+    RwLockWriteGuardArc,
 };
-pub use semaphore::{Semaphore, SemaphoreGuard, SemaphoreGuardArc};
+pub use semaphore::{AcquireError, Semaphore, SemaphoreGuard, SemaphoreGuardArc, TooManyPermits};
+pub use singleflight::Singleflight;
+pub use timer::{TimedOut, Timer};
+pub use wait_group::{WaitGroup, WaitGroupToken, WaitGroupTokenArc};
+pub use watch::{Watch, WatchReceiver, WatchReceiverArc};
 
 pub mod futures {
     //! Named futures for use with `async_lock` primitives.
 
-    pub use crate::barrier::BarrierWait;
-    pub use crate::mutex::{Lock, LockArc};
+    pub use crate::async_cell::{GetOrRefresh, GetOrTryRefresh};
+    pub use crate::barrier::{AwaitPhase, BarrierWait, BarrierWaitTimeout};
+    pub use crate::condvar::{CondvarWait, CondvarWaitArc, CondvarWaitWhile, CondvarWaitWhileArc};
+    pub use crate::latch::LatchWait;
+    pub use crate::lazy::{LazyForce, TryLazyForce};
+    pub use crate::mutex::{Lock, LockArc, LockArcTimeout, LockTimeout};
+    pub use crate::notify::Notified;
+    pub use crate::once_cell::{
+        GetOrInit, GetOrInitArc, GetOrTryInit, GetOrTryInitArc, Set, SetArc, Wait, WaitArc,
+    };
+    pub use crate::once_map::{OnceMapGetOrInit, OnceMapGetOrTryInit};
+    pub use crate::reset_event::{AutoResetEventWait, ManualResetEventWait};
     pub use crate::rwlock::futures::{
-        Read, ReadArc, UpgradableRead, UpgradableReadArc, Upgrade, UpgradeArc, Write, WriteArc,
+        Read, ReadArc, ReadArcTimeout, ReadTimeout, UpgradableRead, UpgradableReadArc,
+        UpgradableReadArcTimeout, UpgradableReadTimeout, Upgrade, UpgradeArc, Write, WriteArc,
+        WriteArcTimeout, WriteTimeout,
+    };
+    pub use crate::semaphore::{
+        Acquire, AcquireArc, AcquireArcChecked, AcquireArcTimeout, AcquireChecked, AcquireTimeout,
+        ReducePermits,
     };
-    pub use crate::semaphore::{Acquire, AcquireArc};
+    pub use crate::wait_group::WaitGroupWait;
+    pub use crate::watch::Changed;
 }
 
 #[cold]
@@ -1,12 +1,14 @@
 use core::borrow::Borrow;
 use core::cell::UnsafeCell;
 use core::fmt;
+use core::future::Future;
 use core::marker::{PhantomData, PhantomPinned};
+use core::mem::ManuallyDrop;
 use core::ops::{Deref, DerefMut};
 use core::pin::Pin;
+use core::ptr;
 use core::sync::atomic::{AtomicUsize, Ordering};
-use core::task::Poll;
-use core::usize;
+use core::task::{Context, Poll};
 
 use alloc::sync::Arc;
 
@@ -16,11 +18,19 @@ use std::time::{Duration, Instant};
 use event_listener::{Event, EventListener};
 use event_listener_strategy::{easy_wrapper, EventListenerFuture};
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::deadline::BlockingDeadline;
+use crate::timer::{TimedOut, Timeout, Timer};
+
 /// An async mutex.
 ///
-/// The locking mechanism uses eventual fairness to ensure locking will be fair on average without
-/// sacrificing performance. This is done by forcing a fair lock whenever a lock operation is
-/// starved for longer than 0.5 milliseconds.
+/// By default, the locking mechanism uses eventual fairness to ensure locking will be fair on
+/// average without sacrificing performance. This is done by forcing a fair lock whenever a lock
+/// operation is starved for longer than 0.5 milliseconds. On targets without a clock (`no_std` or
+/// WebAssembly), a lock operation is considered starved after failing to acquire the lock a few
+/// times instead.
+///
+/// A different policy can be picked with [`Mutex::with_fairness()`].
 ///
 /// # Examples
 ///
@@ -39,14 +49,9 @@ use event_listener_strategy::{easy_wrapper, EventListenerFuture};
 /// # })
 /// ```
 pub struct Mutex<T: ?Sized> {
-    /// Current state of the mutex.
-    ///
-    /// The least significant bit is set to 1 if the mutex is locked.
-    /// The other bits hold the number of starved lock operations.
-    state: AtomicUsize,
-
-    /// Lock operations waiting for the mutex to be released.
-    lock_ops: Event,
+    /// The underlying locking implementation.
+    /// Doesn't depend on `T`.
+    raw: RawMutex,
 
     /// The value inside the mutex.
     data: UnsafeCell<T>,
@@ -66,9 +71,23 @@ impl<T> Mutex<T> {
     /// let mutex = Mutex::new(0);
     /// ```
     pub const fn new(data: T) -> Mutex<T> {
+        Mutex::with_fairness(data, Fairness::DEFAULT)
+    }
+
+    /// Creates a new async mutex with the given fairness policy.
+    ///
+    /// See [`Fairness`] for the available policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Fairness, Mutex};
+    ///
+    /// let mutex = Mutex::with_fairness(0, Fairness::fifo());
+    /// ```
+    pub const fn with_fairness(data: T, fairness: Fairness) -> Mutex<T> {
         Mutex {
-            state: AtomicUsize::new(0),
-            lock_ops: Event::new(),
+            raw: RawMutex::new(fairness),
             data: UnsafeCell::new(data),
         }
     }
@@ -140,6 +159,106 @@ impl<T: ?Sized> Mutex<T> {
         self.lock().wait()
     }
 
+    /// Acquires the mutex using the blocking strategy, giving up after `timeout`.
+    ///
+    /// Returns a guard that releases the mutex when dropped, or [`None`] if the mutex could not
+    /// be acquired in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`lock`][Mutex::lock] method,
+    /// this method will block the current thread until the lock is acquired or the timeout
+    /// expires.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a mutex can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Mutex;
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Mutex::new(10);
+    /// let guard = mutex.lock_blocking_timeout(Duration::from_millis(10)).unwrap();
+    /// assert!(mutex.lock_blocking_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn lock_blocking_timeout(&self, timeout: Duration) -> Option<MutexGuard<'_, T>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.lock_blocking_deadline(deadline),
+            None => Some(self.lock_blocking()),
+        }
+    }
+
+    /// Acquires the mutex using the blocking strategy, giving up at `deadline`.
+    ///
+    /// Returns a guard that releases the mutex when dropped, or [`None`] if the mutex could not
+    /// be acquired in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`lock`][Mutex::lock] method,
+    /// this method will block the current thread until the lock is acquired or the deadline
+    /// is reached.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a mutex can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Mutex;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let mutex = Mutex::new(10);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let guard = mutex.lock_blocking_deadline(deadline).unwrap();
+    /// assert!(mutex.lock_blocking_deadline(deadline).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn lock_blocking_deadline(&self, deadline: Instant) -> Option<MutexGuard<'_, T>> {
+        let lock = self.lock();
+        pin!(lock);
+
+        // Dropping the future on expiry withdraws it from the line of waiters.
+        match lock.poll_with_strategy(&mut BlockingDeadline::new(deadline), &mut ()) {
+            Poll::Ready(guard) => Some(guard),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Acquires the mutex, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the mutex when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::Mutex;
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Mutex::new(10);
+    /// let guard = mutex.lock().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(mutex.lock_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn lock_timeout<Tm: Timer>(&self, timer: Tm) -> LockTimeout<'_, T, Tm> {
+        LockTimeout {
+            inner: Timeout::new(self.lock(), timer),
+        }
+    }
+
     /// Attempts to acquire the mutex.
     ///
     /// If the mutex could not be acquired at this time, then [`None`] is returned. Otherwise, a
@@ -158,11 +277,7 @@ impl<T: ?Sized> Mutex<T> {
     /// ```
     #[inline]
     pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
-        if self
-            .state
-            .compare_exchange(0, 1, Ordering::Acquire, Ordering::Acquire)
-            .is_ok()
-        {
+        if self.raw.try_lock() {
             Some(MutexGuard(self))
         } else {
             None
@@ -197,9 +312,7 @@ impl<T: ?Sized> Mutex<T> {
     /// and the guard is subsequently forgotten. Calling this while you don't hold a lock
     /// on the mutex will likely lead to UB.
     pub(crate) unsafe fn unlock_unchecked(&self) {
-        // Remove the last bit and notify a waiting lock operation.
-        self.state.fetch_sub(1, Ordering::Release);
-        self.lock_ops.notify(1);
+        self.raw.unlock();
     }
 }
 
@@ -227,6 +340,35 @@ impl<T: ?Sized> Mutex<T> {
         })
     }
 
+    /// Acquires the mutex and clones a reference to it, giving up once `timer` expires.
+    ///
+    /// Returns an owned guard that releases the mutex when dropped, or [`TimedOut`] if the timer
+    /// expired first. See [`Timer`] for the timers that can be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::Mutex;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Arc::new(Mutex::new(10));
+    /// let guard = mutex.lock().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(mutex.lock_arc_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn lock_arc_timeout<Tm: Timer>(self: &Arc<Self>, timer: Tm) -> LockArcTimeout<T, Tm> {
+        LockArcTimeout {
+            inner: Timeout::new(self.lock_arc(), timer),
+        }
+    }
+
     /// Acquires the mutex and clones a reference to it using the blocking strategy.
     ///
     /// Returns an owned guard that releases the mutex when dropped.
@@ -275,11 +417,7 @@ impl<T: ?Sized> Mutex<T> {
     /// ```
     #[inline]
     pub fn try_lock_arc(self: &Arc<Self>) -> Option<MutexGuardArc<T>> {
-        if self
-            .state
-            .compare_exchange(0, 1, Ordering::Acquire, Ordering::Acquire)
-            .is_ok()
-        {
+        if self.raw.try_lock() {
             Some(MutexGuardArc(self.clone()))
         } else {
             None
@@ -287,6 +425,273 @@ impl<T: ?Sized> Mutex<T> {
     }
 }
 
+/// Set while the mutex is locked.
+const LOCKED: usize = 1;
+
+/// Set while a locked mutex is being handed over to a starved lock operation.
+const HANDED_OVER: usize = 1 << 1;
+
+/// The bits above the flags count the starved lock operations.
+const ONE_STARVED: usize = 1 << 2;
+
+/// The locking state of a [`Mutex`], which doesn't depend on the data it protects.
+///
+/// Splitting this out lets mapped guards release the lock without knowing the type of the
+/// original value.
+struct RawMutex {
+    /// Current state of the mutex.
+    ///
+    /// The least significant bit is set to 1 if the mutex is locked, and the next one while it
+    /// is handed over. The other bits hold the number of starved lock operations.
+    state: AtomicUsize,
+
+    /// Lock operations waiting for the mutex to be released.
+    lock_ops: Event,
+
+    /// When waiting lock operations switch to the fair locking strategy.
+    fairness: Fairness,
+}
+
+impl RawMutex {
+    #[inline]
+    const fn new(fairness: Fairness) -> RawMutex {
+        RawMutex {
+            state: AtomicUsize::new(0),
+            lock_ops: Event::new(),
+            fairness,
+        }
+    }
+
+    /// Returns `true` iff the lock was successfully acquired.
+    #[inline]
+    fn try_lock(&self) -> bool {
+        self.state
+            .compare_exchange(0, LOCKED, Ordering::Acquire, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// # Safety
+    ///
+    /// Caller must hold the lock.
+    /// This will unlock that lock.
+    #[inline]
+    unsafe fn unlock(&self) {
+        if self.fairness.0 == FairnessKind::Fifo {
+            // Hand the mutex over to a starved lock operation without unlocking it, so that newer
+            // lock operations can't barge ahead.
+            let mut state = self.state.load(Ordering::Acquire);
+            while state >= ONE_STARVED {
+                match self.state.compare_exchange_weak(
+                    state,
+                    state | HANDED_OVER,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => {
+                        self.lock_ops.notify(1);
+                        return;
+                    }
+                    Err(s) => state = s,
+                }
+            }
+        }
+
+        // Remove the last bit and notify a waiting lock operation.
+        self.state.fetch_sub(LOCKED, Ordering::Release);
+        self.lock_ops.notify(1);
+    }
+
+    /// Attempts to acquire the mutex for a lock operation that just started starving.
+    ///
+    /// This only succeeds if no other lock operation is starved.
+    fn try_lock_starved(&self) -> bool {
+        match self
+            .state
+            .compare_exchange(
+                ONE_STARVED,
+                ONE_STARVED | LOCKED,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            )
+            .unwrap_or_else(|x| x)
+        {
+            // Lock acquired!
+            ONE_STARVED => true,
+
+            // Lock is held by someone.
+            s if s & LOCKED != 0 => false,
+
+            // Lock is available.
+            _ => {
+                // Be fair: notify the first listener and then go wait in line.
+                self.lock_ops.notify(1);
+                false
+            }
+        }
+    }
+
+    /// Attempts to acquire the mutex for a starved lock operation that was woken up.
+    fn try_lock_woken(&self) -> bool {
+        // Take the mutex if it was handed over.
+        if self.state.fetch_and(!HANDED_OVER, Ordering::Acquire) & HANDED_OVER != 0 {
+            return true;
+        }
+
+        // Try acquiring the lock without waiting for others.
+        self.state.fetch_or(LOCKED, Ordering::Acquire) & LOCKED == 0
+    }
+
+    /// Stops counting a lock operation as starved.
+    fn stop_starving(&self) {
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            let mut new_state = state - ONE_STARVED;
+
+            // Nobody is left to take the mutex that was handed over, so unlock it instead.
+            if new_state < ONE_STARVED && new_state & HANDED_OVER != 0 {
+                new_state = 0;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                new_state,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) if new_state == 0 && state & HANDED_OVER != 0 => {
+                    self.lock_ops.notify(1);
+                    return;
+                }
+                Ok(_) => return,
+                Err(s) => state = s,
+            }
+        }
+    }
+}
+
+/// The fairness policy of a [`Mutex`].
+///
+/// Lock operations that can't acquire the mutex right away first compete with newer lock
+/// operations for it. Once a lock operation is considered starved, it switches to a fair strategy
+/// where it waits in line and newer lock operations can no longer barge ahead of it. The policy
+/// decides when that switch happens.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::{Fairness, Mutex};
+///
+/// // Favor throughput over fairness.
+/// let mutex = Mutex::with_fairness(0, Fairness::barging());
+///
+/// // Switch to fair locking after failing to acquire the lock three times.
+/// let mutex = Mutex::with_fairness(0, Fairness::eventual_attempts(3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fairness(FairnessKind);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FairnessKind {
+    /// Starved after waiting for this long.
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    Eventual(Duration),
+
+    /// Starved after failing to acquire the lock this many times.
+    EventualAttempts(usize),
+
+    /// Starved right away.
+    Fifo,
+
+    /// Never starved.
+    Barging,
+}
+
+impl Fairness {
+    /// The policy used by [`Mutex::new()`].
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    const DEFAULT: Fairness = Fairness(FairnessKind::Eventual(Duration::from_micros(500)));
+
+    /// The policy used by [`Mutex::new()`].
+    #[cfg(not(all(feature = "std", not(target_family = "wasm"))))]
+    const DEFAULT: Fairness = Fairness(FairnessKind::EventualAttempts(4));
+
+    /// Eventual fairness, where a lock operation is starved after waiting for `threshold`.
+    ///
+    /// This is the default policy, with a threshold of 0.5 milliseconds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Fairness, Mutex};
+    /// use std::time::Duration;
+    ///
+    /// let mutex = Mutex::with_fairness(0, Fairness::eventual(Duration::from_millis(1)));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub const fn eventual(threshold: Duration) -> Fairness {
+        Fairness(FairnessKind::Eventual(threshold))
+    }
+
+    /// Eventual fairness, where a lock operation is starved after being woken up `attempts` times
+    /// without acquiring the lock.
+    ///
+    /// This doesn't need a clock, so it is available on all targets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Fairness, Mutex};
+    ///
+    /// let mutex = Mutex::with_fairness(0, Fairness::eventual_attempts(8));
+    /// ```
+    pub const fn eventual_attempts(attempts: usize) -> Fairness {
+        Fairness(FairnessKind::EventualAttempts(attempts))
+    }
+
+    /// Strict first-in, first-out fairness.
+    ///
+    /// Lock operations that have to wait are woken up in the order they started waiting. When the
+    /// mutex is released while any of them is waiting, it is handed over directly to the one
+    /// woken up, without being unlocked in between, so newer lock operations can't barge ahead.
+    /// While any lock operation is waiting, [`Mutex::try_lock()`] fails and new lock operations
+    /// go to the back of the line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Fairness, Mutex};
+    ///
+    /// let mutex = Mutex::with_fairness(0, Fairness::fifo());
+    /// ```
+    pub const fn fifo() -> Fairness {
+        Fairness(FairnessKind::Fifo)
+    }
+
+    /// No fairness at all.
+    ///
+    /// Whoever tries to lock the mutex right after it is released gets it, which gives the best
+    /// throughput under contention but can starve lock operations indefinitely.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Fairness, Mutex};
+    ///
+    /// let mutex = Mutex::with_fairness(0, Fairness::barging());
+    /// ```
+    pub const fn barging() -> Fairness {
+        Fairness(FairnessKind::Barging)
+    }
+}
+
+impl Default for Fairness {
+    #[inline]
+    fn default() -> Fairness {
+        Fairness::DEFAULT
+    }
+}
+
 impl<T: fmt::Debug + ?Sized> fmt::Debug for Mutex<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         struct Locked;
@@ -309,7 +714,7 @@ impl<T> From<T> for Mutex<T> {
     }
 }
 
-impl<T: Default + ?Sized> Default for Mutex<T> {
+impl<T: Default> Default for Mutex<T> {
     fn default() -> Mutex<T> {
         Mutex::new(Default::default())
     }
@@ -373,6 +778,52 @@ impl<'a, T: ?Sized> EventListenerFuture for LockInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`Mutex::lock_timeout`].
+    pub struct LockTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<Lock<'a, T>, Tm>,
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for LockTimeout<'_, T, Tm> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LockTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for LockTimeout<'a, T, Tm> {
+    type Output = Result<MutexGuard<'a, T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Mutex::lock_arc_timeout`].
+    pub struct LockArcTimeout<T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<LockArc<T>, Tm>,
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for LockArcTimeout<T, Tm> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LockArcTimeout { .. }")
+    }
+}
+
+impl<T: ?Sized, Tm: Timer> Future for LockArcTimeout<T, Tm> {
+    type Output = Result<MutexGuardArc<T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`Mutex::lock_arc`].
     pub struct LockArc<T: ?Sized>(LockArcInnards<T> => MutexGuardArc<T>);
@@ -450,6 +901,9 @@ pin_project_lite::pin_project! {
         // The point at which the mutex lock was started.
         start: Start,
 
+        // The number of times this lock operation was woken up without acquiring the lock.
+        attempts: usize,
+
         // This lock operation is starving.
         starved: bool,
 
@@ -487,6 +941,7 @@ impl<T: ?Sized, B: Borrow<Mutex<T>>> AcquireSlow<B, T> {
                 #[cfg(all(feature = "std", not(target_family = "wasm")))]
                 start: None,
             },
+            attempts: 0,
             starved: false,
             _marker: PhantomData,
             _pin: PhantomPinned,
@@ -501,7 +956,7 @@ impl<T: ?Sized, B: Borrow<Mutex<T>>> AcquireSlow<B, T> {
         if *this.starved {
             if let Some(mutex) = mutex.as_ref() {
                 // Decrement this counter before we exit.
-                mutex.borrow().state.fetch_sub(2, Ordering::Release);
+                mutex.borrow().raw.stop_starving();
             }
         }
 
@@ -521,73 +976,92 @@ impl<T: ?Sized, B: Unpin + Borrow<Mutex<T>>> EventListenerFuture for AcquireSlow
         let this = self.as_mut().project();
         #[cfg(all(feature = "std", not(target_family = "wasm")))]
         let start = *this.start.start.get_or_insert_with(Instant::now);
-        let mutex = Borrow::<Mutex<T>>::borrow(
+        let mutex = &Borrow::<Mutex<T>>::borrow(
             this.mutex.as_ref().expect("future polled after completion"),
-        );
+        )
+        .raw;
 
-        // Only use this hot loop if we aren't currently starved.
+        // Only use this hot loop if we aren't currently starved. FIFO lock operations skip it and
+        // wait in line right away.
         if !*this.starved {
-            loop {
-                // Start listening for events.
-                if this.listener.is_none() {
-                    *this.listener = Some(mutex.lock_ops.listen());
-
-                    // Try locking if nobody is being starved.
-                    match mutex
-                        .state
-                        .compare_exchange(0, 1, Ordering::Acquire, Ordering::Acquire)
-                        .unwrap_or_else(|x| x)
-                    {
-                        // Lock acquired!
-                        0 => return Poll::Ready(self.take_mutex().unwrap()),
-
-                        // Lock is held and nobody is starved.
-                        1 => {}
-
-                        // Somebody is starved.
-                        _ => break,
-                    }
-                } else {
-                    ready!(strategy.poll(this.listener, context));
-
-                    // Try locking if nobody is being starved.
-                    match mutex
-                        .state
-                        .compare_exchange(0, 1, Ordering::Acquire, Ordering::Acquire)
-                        .unwrap_or_else(|x| x)
-                    {
-                        // Lock acquired!
-                        0 => return Poll::Ready(self.take_mutex().unwrap()),
-
-                        // Lock is held and nobody is starved.
-                        1 => {}
-
-                        // Somebody is starved.
-                        _ => {
-                            // Notify the first listener in line because we probably received a
-                            // notification that was meant for a starved task.
-                            mutex.lock_ops.notify(1);
-                            break;
+            if mutex.fairness.0 != FairnessKind::Fifo {
+                loop {
+                    // Start listening for events.
+                    if this.listener.is_none() {
+                        *this.listener = Some(mutex.lock_ops.listen());
+
+                        // Try locking if nobody is being starved.
+                        match mutex
+                            .state
+                            .compare_exchange(0, LOCKED, Ordering::Acquire, Ordering::Acquire)
+                            .unwrap_or_else(|x| x)
+                        {
+                            // Lock acquired!
+                            0 => return Poll::Ready(self.take_mutex().unwrap()),
+
+                            // Lock is held and nobody is starved.
+                            LOCKED => {}
+
+                            // Somebody is starved.
+                            _ => break,
+                        }
+                    } else {
+                        ready!(strategy.poll(this.listener, context));
+
+                        // Try locking if nobody is being starved.
+                        match mutex
+                            .state
+                            .compare_exchange(0, LOCKED, Ordering::Acquire, Ordering::Acquire)
+                            .unwrap_or_else(|x| x)
+                        {
+                            // Lock acquired!
+                            0 => return Poll::Ready(self.take_mutex().unwrap()),
+
+                            // Lock is held and nobody is starved.
+                            LOCKED => {}
+
+                            // Somebody is starved.
+                            _ => {
+                                // Notify the first listener in line because we probably received a
+                                // notification that was meant for a starved task.
+                                mutex.lock_ops.notify(1);
+                                break;
+                            }
                         }
-                    }
 
-                    // If waiting for too long, fall back to a fairer locking strategy that will prevent
-                    // newer lock operations from starving us forever.
-                    #[cfg(all(feature = "std", not(target_family = "wasm")))]
-                    if start.elapsed() > Duration::from_micros(500) {
-                        break;
+                        // If waiting for too long, fall back to a fairer locking strategy that will prevent
+                        // newer lock operations from starving us forever.
+                        *this.attempts += 1;
+                        let starved = match mutex.fairness.0 {
+                            #[cfg(all(feature = "std", not(target_family = "wasm")))]
+                            FairnessKind::Eventual(threshold) => start.elapsed() > threshold,
+                            FairnessKind::EventualAttempts(attempts) => *this.attempts >= attempts,
+                            FairnessKind::Fifo => true,
+                            FairnessKind::Barging => false,
+                        };
+                        if starved {
+                            break;
+                        }
                     }
                 }
             }
+            // Listen before being counted as starved, so that a handover can't be missed.
+            if this.listener.is_none() {
+                *this.listener = Some(mutex.lock_ops.listen());
+            }
 
             // Increment the number of starved lock operations.
-            if mutex.state.fetch_add(2, Ordering::Release) > usize::MAX / 2 {
+            if mutex.state.fetch_add(ONE_STARVED, Ordering::Release) > usize::MAX / 2 {
                 // In case of potential overflow, abort.
                 crate::abort();
             }
 
             // Indicate that we are now starving and will use a fairer locking strategy.
             *this.starved = true;
+
+            if mutex.try_lock_starved() {
+                return Poll::Ready(self.take_mutex().unwrap());
+            }
         }
 
         // Fairer locking loop.
@@ -596,30 +1070,17 @@ impl<T: ?Sized, B: Unpin + Borrow<Mutex<T>>> EventListenerFuture for AcquireSlow
                 // Start listening for events.
                 *this.listener = Some(mutex.lock_ops.listen());
 
-                // Try locking if nobody else is being starved.
-                match mutex
-                    .state
-                    .compare_exchange(2, 2 | 1, Ordering::Acquire, Ordering::Acquire)
-                    .unwrap_or_else(|x| x)
-                {
-                    // Lock acquired!
-                    2 => return Poll::Ready(self.take_mutex().unwrap()),
-
-                    // Lock is held by someone.
-                    s if s % 2 == 1 => {}
-
-                    // Lock is available.
-                    _ => {
-                        // Be fair: notify the first listener and then go wait in line.
-                        mutex.lock_ops.notify(1);
-                    }
+                if mutex.try_lock_starved() {
+                    return Poll::Ready(self.take_mutex().unwrap());
                 }
             } else {
                 // Wait for a notification.
                 ready!(strategy.poll(this.listener, context));
 
-                // Try acquiring the lock without waiting for others.
-                if mutex.state.fetch_or(1, Ordering::Acquire) % 2 == 0 {
+                // Keep listening while trying, so that a handover can't be missed if this fails.
+                *this.listener = Some(mutex.lock_ops.listen());
+
+                if mutex.try_lock_woken() {
                     return Poll::Ready(self.take_mutex().unwrap());
                 }
             }
@@ -651,6 +1112,86 @@ impl<'a, T: ?Sized> MutexGuard<'a, T> {
     pub fn source(guard: &MutexGuard<'a, T>) -> &'a Mutex<T> {
         guard.0
     }
+
+    /// Makes a new [`MappedMutexGuard`] for a component of the locked data.
+    ///
+    /// The mutex stays locked until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `MutexGuard::map(...)`. A method
+    /// would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((1, 2));
+    ///
+    /// let mut second = MutexGuard::map(mutex.lock().await, |pair| &mut pair.1);
+    /// *second = 3;
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// drop(second);
+    /// assert_eq!(*mutex.lock().await, (1, 3));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> MappedMutexGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // If `f` panics, `guard` is dropped and the mutex is unlocked.
+        let value: *mut U = f(unsafe { &mut *guard.0.data.get() });
+        let guard = ManuallyDrop::new(guard);
+
+        MappedMutexGuard {
+            raw: &guard.0.raw,
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Attempts to make a new [`MappedMutexGuard`] for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as `MutexGuard::try_map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new(vec![1, 2, 3]);
+    ///
+    /// let guard = MutexGuard::try_map(mutex.lock().await, |v| v.get_mut(5)).unwrap_err();
+    /// let mut first = MutexGuard::try_map(guard, |v| v.first_mut()).unwrap();
+    /// *first = 10;
+    ///
+    /// drop(first);
+    /// assert_eq!(*mutex.lock().await, [10, 2, 3]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<MappedMutexGuard<'a, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let value: *mut U = match f(unsafe { &mut *guard.0.data.get() }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+        let guard = ManuallyDrop::new(guard);
+
+        Ok(MappedMutexGuard {
+            raw: &guard.0.raw,
+            value,
+            _marker: PhantomData,
+        })
+    }
 }
 
 impl<T: ?Sized> Drop for MutexGuard<'_, T> {
@@ -719,6 +1260,92 @@ impl<T: ?Sized> MutexGuardArc<T> {
     {
         &guard.0
     }
+
+    /// Consumes the guard (without unlocking) and returns the underlying `Arc`.
+    #[inline]
+    pub(crate) fn into_arc(guard: Self) -> Arc<Mutex<T>> {
+        let guard = ManuallyDrop::new(guard);
+        // SAFETY: `guard` is not used after this
+        unsafe { ptr::read(&guard.0) }
+    }
+
+    /// Makes a new [`MappedMutexGuardArc`] for a component of the locked data.
+    ///
+    /// The mutex stays locked until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `MutexGuardArc::map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Mutex, MutexGuardArc};
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new((1, 2)));
+    ///
+    /// let mut second = MutexGuardArc::map(mutex.lock_arc().await, |pair| &mut pair.1);
+    /// *second = 3;
+    /// assert!(mutex.try_lock().is_none());
+    ///
+    /// drop(second);
+    /// assert_eq!(*mutex.lock().await, (1, 3));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> MappedMutexGuardArc<T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // If `f` panics, `guard` is dropped and the mutex is unlocked.
+        let value: *mut U = f(unsafe { &mut *guard.0.data.get() });
+
+        MappedMutexGuardArc {
+            mutex: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedMutexGuardArc`] for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as `MutexGuardArc::try_map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{Mutex, MutexGuardArc};
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new(vec![1, 2, 3]));
+    ///
+    /// let guard = MutexGuardArc::try_map(mutex.lock_arc().await, |v| v.get_mut(5)).unwrap_err();
+    /// let mut first = MutexGuardArc::try_map(guard, |v| v.first_mut()).unwrap();
+    /// *first = 10;
+    ///
+    /// drop(first);
+    /// assert_eq!(*mutex.lock().await, [10, 2, 3]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<MappedMutexGuardArc<T, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let value: *mut U = match f(unsafe { &mut *guard.0.data.get() }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedMutexGuardArc {
+            mutex: Self::into_arc(guard),
+            value,
+        })
+    }
 }
 
 impl<T: ?Sized> Drop for MutexGuardArc<T> {
@@ -756,3 +1383,271 @@ impl<T: ?Sized> DerefMut for MutexGuardArc<T> {
         unsafe { &mut *self.0.data.get() }
     }
 }
+
+/// A guard that releases the mutex when dropped, pointing to a component of the locked data.
+///
+/// Created by [`MutexGuard::map`] and [`MutexGuard::try_map`].
+#[clippy::has_significant_drop]
+pub struct MappedMutexGuard<'a, T: ?Sized> {
+    /// Reference to underlying locking implementation.
+    /// Doesn't depend on the type of the original value.
+    raw: &'a RawMutex,
+
+    /// Pointer to the component of the locked data.
+    value: *mut T,
+
+    /// Borrows the component mutably for `'a`. Invariant in `T`.
+    _marker: PhantomData<&'a mut T>,
+}
+
+unsafe impl<T: Send + ?Sized> Send for MappedMutexGuard<'_, T> {}
+unsafe impl<T: Sync + ?Sized> Sync for MappedMutexGuard<'_, T> {}
+
+impl<'a, T: ?Sized> MappedMutexGuard<'a, T> {
+    /// Makes a new [`MappedMutexGuard`] for a component of the already mapped data.
+    ///
+    /// This is an associated function that needs to be used as `MappedMutexGuard::map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{MappedMutexGuard, Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((1, (2, 3)));
+    ///
+    /// let inner = MutexGuard::map(mutex.lock().await, |pair| &mut pair.1);
+    /// let mut third = MappedMutexGuard::map(inner, |pair| &mut pair.1);
+    /// *third = 4;
+    ///
+    /// drop(third);
+    /// assert_eq!(*mutex.lock().await, (1, (2, 4)));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> MappedMutexGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // If `f` panics, `guard` is dropped and the mutex is unlocked.
+        let value: *mut U = f(unsafe { &mut *guard.value });
+        let guard = ManuallyDrop::new(guard);
+
+        MappedMutexGuard {
+            raw: guard.raw,
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Attempts to make a new [`MappedMutexGuard`] for a component of the already mapped data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as `MappedMutexGuard::try_map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{MappedMutexGuard, Mutex, MutexGuard};
+    ///
+    /// let mutex = Mutex::new((1, vec![2]));
+    ///
+    /// let inner = MutexGuard::map(mutex.lock().await, |pair| &mut pair.1);
+    /// let inner = MappedMutexGuard::try_map(inner, |v| v.get_mut(1)).unwrap_err();
+    /// assert_eq!(*inner, [2]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<MappedMutexGuard<'a, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let value: *mut U = match f(unsafe { &mut *guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+        let guard = ManuallyDrop::new(guard);
+
+        Ok(MappedMutexGuard {
+            raw: guard.raw,
+            value,
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T: ?Sized> Drop for MappedMutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: we are dropping the mutex guard, therefore unlocking the mutex.
+        unsafe {
+            self.raw.unlock();
+        }
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display + ?Sized> fmt::Display for MappedMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Deref for MappedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MappedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.value }
+    }
+}
+
+/// An owned guard that releases the mutex when dropped, pointing to a component of the locked
+/// data.
+///
+/// Created by [`MutexGuardArc::map`] and [`MutexGuardArc::try_map`].
+#[clippy::has_significant_drop]
+pub struct MappedMutexGuardArc<T: ?Sized, U: ?Sized> {
+    /// The mutex being held. Keeps the original value alive.
+    mutex: Arc<Mutex<T>>,
+
+    /// Pointer to the component of the locked data.
+    value: *mut U,
+}
+
+unsafe impl<T: Send + ?Sized, U: Send + ?Sized> Send for MappedMutexGuardArc<T, U> {}
+unsafe impl<T: ?Sized, U: Sync + ?Sized> Sync for MappedMutexGuardArc<T, U> {}
+
+impl<T: ?Sized, U: ?Sized> MappedMutexGuardArc<T, U> {
+    /// Consumes the guard (without unlocking) and returns the underlying `Arc`.
+    #[inline]
+    fn into_arc(guard: Self) -> Arc<Mutex<T>> {
+        let guard = ManuallyDrop::new(guard);
+        // SAFETY: `guard` is not used after this
+        unsafe { ptr::read(&guard.mutex) }
+    }
+
+    /// Makes a new [`MappedMutexGuardArc`] for a component of the already mapped data.
+    ///
+    /// This is an associated function that needs to be used as `MappedMutexGuardArc::map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{MappedMutexGuardArc, Mutex, MutexGuardArc};
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new((1, (2, 3))));
+    ///
+    /// let inner = MutexGuardArc::map(mutex.lock_arc().await, |pair| &mut pair.1);
+    /// let mut third = MappedMutexGuardArc::map(inner, |pair| &mut pair.1);
+    /// *third = 4;
+    ///
+    /// drop(third);
+    /// assert_eq!(*mutex.lock().await, (1, (2, 4)));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<V: ?Sized, F>(guard: Self, f: F) -> MappedMutexGuardArc<T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        // If `f` panics, `guard` is dropped and the mutex is unlocked.
+        let value: *mut V = f(unsafe { &mut *guard.value });
+
+        MappedMutexGuardArc {
+            mutex: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedMutexGuardArc`] for a component of the already mapped data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `MappedMutexGuardArc::try_map(...)`. A method would interfere with methods of the same
+    /// name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{MappedMutexGuardArc, Mutex, MutexGuardArc};
+    /// use std::sync::Arc;
+    ///
+    /// let mutex = Arc::new(Mutex::new((1, vec![2])));
+    ///
+    /// let inner = MutexGuardArc::map(mutex.lock_arc().await, |pair| &mut pair.1);
+    /// let inner = MappedMutexGuardArc::try_map(inner, |v| v.get_mut(1)).unwrap_err();
+    /// assert_eq!(*inner, [2]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<V: ?Sized, F>(guard: Self, f: F) -> Result<MappedMutexGuardArc<T, V>, Self>
+    where
+        F: FnOnce(&mut U) -> Option<&mut V>,
+    {
+        let value: *mut V = match f(unsafe { &mut *guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedMutexGuardArc {
+            mutex: Self::into_arc(guard),
+            value,
+        })
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedMutexGuardArc<T, U> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: we are dropping the mutex guard, therefore unlocking the mutex.
+        unsafe {
+            self.mutex.unlock_unchecked();
+        }
+    }
+}
+
+impl<T: ?Sized, U: fmt::Debug + ?Sized> fmt::Debug for MappedMutexGuardArc<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: fmt::Display + ?Sized> fmt::Display for MappedMutexGuardArc<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedMutexGuardArc<T, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedMutexGuardArc<T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
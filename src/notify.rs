@@ -0,0 +1,437 @@
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::Poll;
+
+use event_listener::{Event, EventListener, IntoNotification};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+
+/// The state is 64 bits wide where possible, so that permits and waiters each get 32 bits even on
+/// 32-bit targets.
+#[cfg(target_has_atomic = "64")]
+type AtomicState = core::sync::atomic::AtomicU64;
+#[cfg(target_has_atomic = "64")]
+type State = u64;
+
+#[cfg(not(target_has_atomic = "64"))]
+type AtomicState = AtomicUsize;
+#[cfg(not(target_has_atomic = "64"))]
+type State = usize;
+
+/// The number of bits counting permits in the state; the rest count waiters.
+const PERMIT_BITS: u32 = State::BITS / 2;
+
+/// A mask selecting the permit count in the state.
+const PERMIT_MASK: State = (1 << PERMIT_BITS) - 1;
+
+/// A single waiter in the state.
+const ONE_WAITER: State = 1 << PERMIT_BITS;
+
+/// The most waiters the state can count.
+const MAX_WAITERS: State = PERMIT_MASK;
+
+/// Notifies tasks waiting for something to happen.
+///
+/// [`notify_one()`][Notify::notify_one] wakes up a single waiting task. If no task is waiting, a
+/// permit is stored instead, and the next task to wait completes right away by consuming it. At
+/// most one permit is stored, however many times `notify_one()` is called.
+///
+/// [`notify_waiters()`][Notify::notify_waiters] wakes up all tasks waiting at the time it is
+/// called, and doesn't store a permit.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Notify;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let notify = Arc::new(Notify::new());
+/// let notify2 = notify.clone();
+///
+/// thread::spawn(move || {
+///     // The permit is stored if the main thread isn't waiting yet.
+///     notify2.notify_one();
+/// });
+///
+/// # futures_lite::future::block_on(async {
+/// notify.notified().await;
+/// # });
+/// ```
+pub struct Notify {
+    /// The number of stored permits and of tasks registered as waiting.
+    ///
+    /// The permits are capped at the number of waiters, or one if there are none.
+    state: AtomicState,
+
+    /// The number of calls to `notify_waiters()`.
+    generation: AtomicUsize,
+
+    /// The event waited on by registered tasks.
+    event: Event,
+}
+
+impl Notify {
+    /// Creates a new `Notify` without a stored permit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    ///
+    /// let notify = Notify::new();
+    /// ```
+    pub const fn new() -> Notify {
+        Notify {
+            state: AtomicState::new(0),
+            generation: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Wakes up one waiting task, or stores a permit for the next task to wait if none is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let notify = Notify::new();
+    /// notify.notify_one();
+    /// notify.notify_one();
+    ///
+    /// // Only one permit was stored.
+    /// notify.notified().await;
+    /// assert!(futures_lite::future::poll_once(notify.notified()).await.is_none());
+    /// # });
+    /// ```
+    pub fn notify_one(&self) {
+        let mut state = self.state.load(Ordering::Acquire);
+
+        loop {
+            // Every waiter already has a permit coming, and so does the next one.
+            let permits = state & PERMIT_MASK;
+            if permits >= (state / ONE_WAITER).max(1) {
+                return;
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(s) => state = s,
+            }
+        }
+
+        self.event.notify(1.additional());
+    }
+
+    /// Wakes up all tasks currently waiting, without storing a permit.
+    ///
+    /// A [`Notified`] future is waiting from the time it is created by
+    /// [`notified()`][Notify::notified], even before it is first polled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    /// use futures_lite::future;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let notify = Notify::new();
+    /// let a = notify.notified();
+    /// let b = notify.notified();
+    ///
+    /// notify.notify_waiters();
+    /// future::zip(a, b).await;
+    ///
+    /// // No permit was stored.
+    /// assert!(future::poll_once(notify.notified()).await.is_none());
+    /// # });
+    /// ```
+    pub fn notify_waiters(&self) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        self.event.notify(usize::MAX);
+    }
+
+    /// Waits for a notification.
+    ///
+    /// The returned future completes once it consumes a permit stored by
+    /// [`notify_one()`][Notify::notify_one], or once
+    /// [`notify_waiters()`][Notify::notify_waiters] is called after it was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let notify = Notify::new();
+    /// notify.notify_one();
+    /// notify.notified().await;
+    /// # });
+    /// ```
+    pub fn notified(&self) -> Notified<'_> {
+        Notified::_new(NotifiedInner {
+            notify: self,
+            generation: self.generation.load(Ordering::Acquire),
+            status: Status::Idle,
+            listener: None,
+        })
+    }
+
+    /// Waits for a notification.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`notified`][Notify::notified] method,
+    /// this method will block the current thread until notified.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Notify` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    ///
+    /// let notify = Notify::new();
+    /// notify.notify_one();
+    /// notify.notified_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn notified_blocking(&self) {
+        self.notified().wait();
+    }
+}
+
+impl Default for Notify {
+    #[inline]
+    fn default() -> Notify {
+        Notify::new()
+    }
+}
+
+impl fmt::Debug for Notify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.state.load(Ordering::Relaxed);
+        f.debug_struct("Notify")
+            .field("permits", &(state & PERMIT_MASK))
+            .field("waiters", &(state / ONE_WAITER))
+            .finish()
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Notify::notified`].
+    pub struct Notified<'a>(NotifiedInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl Notified<'_> {
+    /// Registers this future as waiting, without polling it.
+    ///
+    /// A registered future is woken up by [`Notify::notify_one()`] like a future that was
+    /// already polled. This way, no permit gets stored for a notification that happens between
+    /// enabling the future and awaiting it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Notify;
+    /// use futures_lite::{future, pin};
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let notify = Notify::new();
+    /// let notified = notify.notified();
+    /// pin!(notified);
+    /// notified.as_mut().enable();
+    ///
+    /// // The notification goes to the enabled future rather than being stored.
+    /// notify.notify_one();
+    /// assert!(future::poll_once(notify.notified()).await.is_none());
+    /// notified.await;
+    /// # });
+    /// ```
+    pub fn enable(self: Pin<&mut Self>) {
+        self.project()._inner.get_pin_mut().get_mut().enable();
+    }
+}
+
+impl fmt::Debug for Notified<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Notified { .. }")
+    }
+}
+
+/// Inner future for waiting on a notification.
+struct NotifiedInner<'a> {
+    /// The `Notify` to wait on.
+    notify: &'a Notify,
+
+    /// The number of calls to `notify_waiters()` when this future was created.
+    generation: usize,
+
+    /// Whether this future is counted as a waiter.
+    status: Status,
+
+    /// An event listener for the `notify.event` event.
+    listener: Option<EventListener>,
+}
+
+/// Where a [`NotifiedInner`] future is at.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// The future isn't counted as a waiter yet.
+    Idle,
+
+    /// The future is counted as a waiter.
+    Waiting,
+
+    /// The future has completed.
+    Done,
+}
+
+impl NotifiedInner<'_> {
+    /// Starts listening for notifications and counts this future as a waiter.
+    ///
+    /// If a stored permit isn't coming to any other waiter, it is consumed instead.
+    fn enable(&mut self) {
+        if self.status != Status::Idle {
+            return;
+        }
+
+        // Listen before counting, so that notifications for the new waiter can't be missed.
+        self.listener = Some(self.notify.event.listen());
+        let mut state = self.notify.state.load(Ordering::Acquire);
+
+        loop {
+            // Waiters are woken up in order, so the first permits are theirs.
+            let spare = state & PERMIT_MASK > state / ONE_WAITER;
+
+            // Make sure the number of waiters doesn't overflow.
+            if !spare && state / ONE_WAITER == MAX_WAITERS {
+                crate::abort();
+            }
+
+            let new_state = if spare { state - 1 } else { state + ONE_WAITER };
+
+            match self.notify.state.compare_exchange_weak(
+                state,
+                new_state,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) if spare => return self.finish(false),
+                Ok(_) => {
+                    self.status = Status::Waiting;
+                    return;
+                }
+                Err(s) => state = s,
+            }
+        }
+    }
+
+    /// Consumes a permit on behalf of a woken up waiter, and stops counting it as a waiter.
+    fn try_consume(&mut self) -> bool {
+        let mut state = self.notify.state.load(Ordering::Acquire);
+
+        while state & PERMIT_MASK > 0 {
+            match self.notify.state.compare_exchange_weak(
+                state,
+                state - ONE_WAITER - 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    self.finish(false);
+                    return true;
+                }
+                Err(s) => state = s,
+            }
+        }
+
+        false
+    }
+
+    /// Marks this future as completed, and stops counting it as a waiter if `leave` is set.
+    fn finish(&mut self, leave: bool) {
+        if leave && self.status == Status::Waiting {
+            let mut state = self.notify.state.load(Ordering::Acquire);
+
+            loop {
+                // Drop permits nobody is left to consume, beyond the one that is kept.
+                let waiters = state / ONE_WAITER - 1;
+                let permits = (state & PERMIT_MASK).min(waiters.max(1));
+
+                match self.notify.state.compare_exchange_weak(
+                    state,
+                    waiters * ONE_WAITER + permits,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => break,
+                    Err(s) => state = s,
+                }
+            }
+        }
+
+        // A notification this listener received is passed on to another one as it is dropped.
+        self.listener = None;
+        self.status = Status::Done;
+    }
+}
+
+impl Drop for NotifiedInner<'_> {
+    fn drop(&mut self) {
+        self.finish(true);
+    }
+}
+
+impl EventListenerFuture for NotifiedInner<'_> {
+    type Output = ();
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<()> {
+        let this = self.get_mut();
+
+        loop {
+            if this.status == Status::Done {
+                return Poll::Ready(());
+            }
+
+            // Woken up by `notify_waiters()`.
+            if this.notify.generation.load(Ordering::Acquire) != this.generation {
+                this.finish(true);
+                return Poll::Ready(());
+            }
+
+            match (this.status, &this.listener) {
+                (Status::Idle, _) => this.enable(),
+                (_, Some(_)) => ready!(strategy.poll(&mut this.listener, cx)),
+                (_, None) => {
+                    // Woken up, possibly without a permit left for this future.
+                    if this.try_consume() {
+                        return Poll::Ready(());
+                    }
+
+                    // Check again after listening, so that a new permit can't be missed.
+                    this.listener = Some(this.notify.event.listen());
+                    if this.try_consume() {
+                        return Poll::Ready(());
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,26 @@
+use core::borrow::Borrow;
 use core::cell::UnsafeCell;
 use core::convert::Infallible;
 use core::fmt;
-use core::future::Future;
-use core::mem::{forget, MaybeUninit};
+use core::future::{ready, Future, Ready};
+use core::marker::PhantomData;
+use core::mem::MaybeUninit;
+use core::ops::Deref;
+use core::pin::Pin;
 use core::ptr;
 use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll};
+
+use alloc::sync::Arc;
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{Duration, Instant};
 
 #[cfg(all(feature = "std", not(target_family = "wasm")))]
-use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use crate::block_on::block_on_future;
 
-use event_listener::Event;
-use event_listener_strategy::{NonBlocking, Strategy};
+use event_listener::{Event, EventListener};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, NonBlocking, Strategy};
 
 #[cfg(all(feature = "std", not(target_family = "wasm")))]
 use event_listener::Listener;
@@ -148,6 +158,11 @@ impl<T> OnceCell<T> {
         State::from(self.state.load(Ordering::Acquire)) == State::Initialized
     }
 
+    /// Tell whether or not the cell is being initialized right now.
+    pub(crate) fn is_initializing(&self) -> bool {
+        State::from(self.state.load(Ordering::Acquire)) == State::Initializing
+    }
+
     /// Get a reference to the inner value, or `None` if the value
     /// is not yet initialized.
     ///
@@ -270,26 +285,39 @@ impl<T> OnceCell<T> {
     /// assert_eq!(cell.wait().await, &1);
     /// # });
     /// ```
-    pub async fn wait(&self) -> &T {
-        // Fast path: see if the value is already initialized.
-        if let Some(value) = self.get() {
-            return value;
-        }
-
-        // Slow path: wait for the value to be initialized.
-        event_listener::listener!(self.passive_waiters => listener);
-
-        // Try again.
-        if let Some(value) = self.get() {
-            return value;
-        }
-
-        listener.await;
-        debug_assert!(self.is_initialized());
+    #[inline]
+    pub fn wait(&self) -> Wait<'_, T> {
+        Wait::_new(WaitInner {
+            cell: self,
+            listener: None,
+        })
+    }
 
-        // SAFETY: We know that the value is initialized, so it is safe to
-        // read it.
-        unsafe { self.get_unchecked() }
+    /// Wait for the cell to be initialized through a shared reference.
+    ///
+    /// This method is similar to [`wait()`][`OnceCell::wait()`], but the returned future is
+    /// `'static` and resolves to a [`OnceCellGuardArc`] holding on to the cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = Arc::new(OnceCell::new());
+    /// let wait = cell.wait_arc();
+    ///
+    /// cell.set(1).await.unwrap();
+    /// assert_eq!(*wait.await, 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn wait_arc(self: &Arc<Self>) -> WaitArc<T> {
+        WaitArc::_new(WaitArcInner {
+            cell: self.clone(),
+            listener: None,
+        })
     }
 
     /// Wait for the cell to be initialized, and then return a reference to the
@@ -323,10 +351,74 @@ impl<T> OnceCell<T> {
     /// assert_eq!(cell.wait_blocking(), &1);
     /// ```
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
     pub fn wait_blocking(&self) -> &T {
+        self.wait().wait()
+    }
+
+    /// Wait for the cell to be initialized, giving up after `timeout`.
+    ///
+    /// Returns a reference to the inner value, or [`None`] if the cell was not
+    /// initialized in time.
+    ///
+    /// # Blocking
+    ///
+    /// In contrast to the `wait` method, this method blocks the current thread of
+    /// execution instead of awaiting.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended
+    /// to be used such that a `OnceCell` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in deadlocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::time::Duration;
+    ///
+    /// let cell = OnceCell::<i32>::new();
+    /// assert_eq!(cell.wait_blocking_timeout(Duration::from_millis(10)), None);
+    ///
+    /// cell.set_blocking(1);
+    /// assert_eq!(cell.wait_blocking_timeout(Duration::from_millis(10)), Some(&1));
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking_timeout(&self, timeout: Duration) -> Option<&T> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.wait_blocking_deadline(deadline),
+            None => Some(self.wait_blocking()),
+        }
+    }
+
+    /// Wait for the cell to be initialized, giving up at `deadline`.
+    ///
+    /// Returns a reference to the inner value, or [`None`] if the cell was not
+    /// initialized in time.
+    ///
+    /// # Blocking
+    ///
+    /// In contrast to the `wait` method, this method blocks the current thread of
+    /// execution instead of awaiting.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended
+    /// to be used such that a `OnceCell` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in deadlocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let cell = OnceCell::<i32>::new();
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert_eq!(cell.wait_blocking_deadline(deadline), None);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn wait_blocking_deadline(&self, deadline: Instant) -> Option<&T> {
         // Fast path: see if the value is already initialized.
         if let Some(value) = self.get() {
-            return value;
+            return Some(value);
         }
 
         // Slow path: wait for the value to be initialized.
@@ -334,15 +426,15 @@ impl<T> OnceCell<T> {
 
         // Try again.
         if let Some(value) = self.get() {
-            return value;
+            return Some(value);
         }
 
-        listener.wait();
+        listener.wait_deadline(deadline)?;
         debug_assert!(self.is_initialized());
 
         // SAFETY: We know that the value is initialized, so it is safe to
         // read it.
-        unsafe { self.get_unchecked() }
+        Some(unsafe { self.get_unchecked() })
     }
 
     /// Either get the value or initialize it with the given closure.
@@ -372,23 +464,55 @@ impl<T> OnceCell<T> {
     /// assert_eq!(result.unwrap(), &1);
     /// # });
     /// ```
-    pub async fn get_or_try_init<E, Fut: Future<Output = Result<T, E>>>(
-        &self,
-        closure: impl FnOnce() -> Fut,
-    ) -> Result<&T, E> {
-        // Fast path: see if the value is already initialized.
-        if let Some(value) = self.get() {
-            return Ok(value);
+    #[inline]
+    pub fn get_or_try_init<E, F, Fut>(&self, closure: F) -> GetOrTryInit<'_, T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        GetOrTryInit {
+            inner: Initialize::new(self, closure),
         }
+    }
 
-        // Slow path: initialize the value.
-        self.initialize_or_wait(closure, &mut NonBlocking::default())
-            .await?;
-        debug_assert!(self.is_initialized());
-
-        // SAFETY: We know that the value is initialized, so it is safe to
-        // read it.
-        Ok(unsafe { self.get_unchecked() })
+    /// Either get the value or initialize it with the given closure, through a shared reference.
+    ///
+    /// This method is similar to [`get_or_try_init()`][`OnceCell::get_or_try_init()`], but the
+    /// returned future is `'static` and resolves to a [`OnceCellGuardArc`] holding on to the
+    /// cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::sync::Arc;
+    /// #
+    /// # // Prevent explicit value errors.
+    /// # fn _explicit<T>(_: &Result<T, ()>) {}
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = Arc::new(OnceCell::new());
+    ///
+    /// let result = cell.get_or_try_init_arc(|| async { Err(()) }).await;
+    /// assert!(result.is_err());
+    ///
+    /// let result = cell.get_or_try_init_arc(|| async { Ok(1) }).await;
+    /// # _explicit(&result);
+    /// assert_eq!(*result.unwrap(), 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn get_or_try_init_arc<E, F, Fut>(
+        self: &Arc<Self>,
+        closure: F,
+    ) -> GetOrTryInitArc<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        GetOrTryInitArc {
+            inner: Initialize::new(self.clone(), closure),
+        }
     }
 
     /// Either get the value or initialize it with the given closure.
@@ -430,22 +554,7 @@ impl<T> OnceCell<T> {
         &self,
         closure: impl FnOnce() -> Result<T, E>,
     ) -> Result<&T, E> {
-        // Fast path: see if the value is already initialized.
-        if let Some(value) = self.get() {
-            return Ok(value);
-        }
-
-        // Slow path: initialize the value.
-        // The futures provided should never block, so we can use `now_or_never`.
-        now_or_never(self.initialize_or_wait(
-            move || core::future::ready(closure()),
-            &mut event_listener_strategy::Blocking::default(),
-        ))?;
-        debug_assert!(self.is_initialized());
-
-        // SAFETY: We know that the value is initialized, so it is safe to
-        // read it.
-        Ok(unsafe { self.get_unchecked() })
+        block_on_future(self.get_or_try_init(move || ready(closure())))
     }
 
     /// Either get the value or initialize it with the given closure.
@@ -464,16 +573,42 @@ impl<T> OnceCell<T> {
     /// assert_eq!(cell.get_or_init(|| async { 2 }).await, &1);
     /// # });
     /// ```
-    pub async fn get_or_init<Fut: Future<Output = T>>(&self, closure: impl FnOnce() -> Fut) -> &T {
-        match self
-            .get_or_try_init(move || async move {
-                let result: Result<T, Infallible> = Ok(closure().await);
-                result
-            })
-            .await
-        {
-            Ok(value) => value,
-            Err(infallible) => match infallible {},
+    #[inline]
+    pub fn get_or_init<F, Fut>(&self, closure: F) -> GetOrInit<'_, T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        GetOrInit {
+            inner: Initialize::new(self, Infallibly(closure)),
+        }
+    }
+
+    /// Either get the value or initialize it with the given closure, through a shared reference.
+    ///
+    /// This method is similar to [`get_or_init()`][`OnceCell::get_or_init()`], but the returned
+    /// future is `'static` and resolves to a [`OnceCellGuardArc`] holding on to the cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = Arc::new(OnceCell::new());
+    /// assert_eq!(*cell.get_or_init_arc(|| async { 1 }).await, 1);
+    /// assert_eq!(*cell.get_or_init_arc(|| async { 2 }).await, 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn get_or_init_arc<F, Fut>(self: &Arc<Self>, closure: F) -> GetOrInitArc<T, F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        GetOrInitArc {
+            inner: Initialize::new(self.clone(), Infallibly(closure)),
         }
     }
 
@@ -530,16 +665,36 @@ impl<T> OnceCell<T> {
     /// assert_eq!(cell.set(2).await, Err(2));
     /// # });
     /// ```
-    pub async fn set(&self, value: T) -> Result<&T, T> {
-        let mut value = Some(value);
-        self.get_or_init(|| async { value.take().unwrap() }).await;
-
-        match value {
-            Some(value) => Err(value),
-            None => {
-                // SAFETY: value was taken, so we are initialized
-                Ok(unsafe { self.get_unchecked() })
-            }
+    #[inline]
+    pub fn set(&self, value: T) -> Set<'_, T> {
+        Set {
+            inner: Initialize::new(self, SetValue(value)),
+        }
+    }
+
+    /// Try to set the value of the cell through a shared reference.
+    ///
+    /// This method is similar to [`set()`][`OnceCell::set()`], but the returned future is
+    /// `'static` and resolves to a [`OnceCellGuardArc`] holding on to the cell once the value is
+    /// set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use async_lock::OnceCell;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let cell = Arc::new(OnceCell::new());
+    ///
+    /// assert_eq!(*cell.set_arc(1).await.unwrap(), 1);
+    /// assert_eq!(cell.set_arc(2).await.unwrap_err(), 2);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn set_arc(self: &Arc<Self>, value: T) -> SetArc<T> {
+        SetArc {
+            inner: Initialize::new(self.clone(), SetValue(value)),
         }
     }
 
@@ -570,117 +725,7 @@ impl<T> OnceCell<T> {
     /// ```
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
     pub fn set_blocking(&self, value: T) -> Result<&T, T> {
-        let mut value = Some(value);
-        self.get_or_init_blocking(|| value.take().unwrap());
-
-        match value {
-            Some(value) => Err(value),
-            None => {
-                // SAFETY: value was taken, so we are initialized
-                Ok(unsafe { self.get_unchecked() })
-            }
-        }
-    }
-
-    /// Wait for the cell to be initialized, optionally using a closure
-    /// to initialize the cell if it is not initialized yet.
-    #[cold]
-    async fn initialize_or_wait<E, Fut: Future<Output = Result<T, E>>, F: FnOnce() -> Fut>(
-        &self,
-        closure: F,
-        strategy: &mut impl for<'a> Strategy<'a>,
-    ) -> Result<(), E> {
-        // The event listener we're currently waiting on.
-        let mut event_listener = None;
-
-        let mut closure = Some(closure);
-
-        loop {
-            // Check the current state of the cell.
-            let state = self.state.load(Ordering::Acquire);
-
-            // Determine what we should do based on our state.
-            match state.into() {
-                State::Initialized => {
-                    // The cell is initialized now, so we can return.
-                    return Ok(());
-                }
-                State::Initializing => {
-                    // The cell is currently initializing, or the cell is uninitialized
-                    // but we do not have the ability to initialize it.
-                    //
-                    // We need to wait the initialization to complete.
-                    if let Some(listener) = event_listener.take() {
-                        strategy.wait(listener).await;
-                    } else {
-                        event_listener = Some(self.active_initializers.listen());
-                    }
-                }
-                State::Uninitialized => {
-                    // Try to move the cell into the initializing state.
-                    if self
-                        .state
-                        .compare_exchange(
-                            State::Uninitialized.into(),
-                            State::Initializing.into(),
-                            Ordering::AcqRel,
-                            Ordering::Acquire,
-                        )
-                        .is_err()
-                    {
-                        // The cell was initialized while we were trying to
-                        // initialize it.
-                        continue;
-                    }
-
-                    // Now that we have an exclusive lock on the cell's value,
-                    // we can try to initialize it.
-                    let _guard = Guard(self);
-                    let initializer = closure.take().unwrap();
-                    match (initializer)().await {
-                        Ok(value) => {
-                            // Write the value into the cell and update the state.
-                            unsafe {
-                                ptr::write(self.value.get().cast(), value);
-                            }
-                            forget(_guard);
-                            self.state
-                                .store(State::Initialized.into(), Ordering::Release);
-
-                            // Notify the listeners that the value is initialized.
-                            self.active_initializers.notify_additional(core::usize::MAX);
-                            self.passive_waiters.notify_additional(core::usize::MAX);
-
-                            return Ok(());
-                        }
-                        Err(err) => {
-                            // Update the state to indicate that the value is
-                            // uninitialized.
-                            drop(_guard);
-
-                            return Err(err);
-                        }
-                    }
-                }
-            }
-        }
-
-        /// Set the cell's state back to `UNINITIALIZED on drop.
-        ///
-        /// If the closure panics, this ensures that the cell's state is set back to
-        /// `UNINITIALIZED` and that the next listener is notified.
-        struct Guard<'a, T>(&'a OnceCell<T>);
-
-        impl<'a, T> Drop for Guard<'a, T> {
-            fn drop(&mut self) {
-                self.0
-                    .state
-                    .store(State::Uninitialized.into(), Ordering::Release);
-
-                // Notify the next initializer that it's their turn.
-                self.0.active_initializers.notify(1);
-            }
-        }
+        block_on_future(self.set(value))
     }
 
     /// Get a reference to the inner value.
@@ -730,6 +775,60 @@ impl<T> From<T> for OnceCell<T> {
     }
 }
 
+/// A reference to the value of an initialized [`OnceCell`], holding on to the cell.
+///
+/// This is returned by the `_arc` methods of [`OnceCell`], like
+/// [`get_or_init_arc()`][`OnceCell::get_or_init_arc()`].
+pub struct OnceCellGuardArc<T>(Arc<OnceCell<T>>);
+
+impl<T> OnceCellGuardArc<T> {
+    /// Returns a reference to the cell a guard came from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{OnceCell, OnceCellGuardArc};
+    /// use std::sync::Arc;
+    ///
+    /// let cell = Arc::new(OnceCell::new());
+    /// let guard = cell.set_arc(1).await.unwrap();
+    /// assert!(Arc::ptr_eq(OnceCellGuardArc::source(&guard), &cell));
+    /// # })
+    /// ```
+    pub fn source(guard: &Self) -> &Arc<OnceCell<T>> {
+        &guard.0
+    }
+}
+
+impl<T> Clone for OnceCellGuardArc<T> {
+    fn clone(&self) -> Self {
+        OnceCellGuardArc(self.0.clone())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCellGuardArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for OnceCellGuardArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T> Deref for OnceCellGuardArc<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: Guards are only created for initialized cells, which stay initialized while
+        // shared.
+        unsafe { self.0.get_unchecked() }
+    }
+}
+
 impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         struct Inner<'a, T>(&'a OnceCell<T>);
@@ -770,27 +869,518 @@ impl<T> Default for OnceCell<T> {
     }
 }
 
-/// Either return the result of a future now, or panic.
-#[cfg(all(feature = "std", not(target_family = "wasm")))]
-fn now_or_never<T>(f: impl Future<Output = T>) -> T {
-    const NOOP_WAKER: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+impl<T> OnceCell<T> {
+    /// Polls for the cell to be initialized by someone else.
+    fn poll_wait<'a, S: Strategy<'a>>(
+        &self,
+        listener: &mut Option<EventListener>,
+        strategy: &mut S,
+        context: &mut S::Context,
+    ) -> Poll<()> {
+        loop {
+            if self.is_initialized() {
+                return Poll::Ready(());
+            }
+
+            if listener.is_none() {
+                *listener = Some(self.passive_waiters.listen());
+            } else {
+                ready!(strategy.poll(listener, context));
+            }
+        }
+    }
+
+    /// Marks the cell as initialized with `value` and wakes everyone waiting on it.
+    fn finish_initialization(&self, value: T) {
+        // SAFETY: The caller holds the cell in the initializing state, so nobody else touches
+        // the value.
+        unsafe {
+            ptr::write(self.value.get().cast(), value);
+        }
+        self.state
+            .store(State::Initialized.into(), Ordering::Release);
+
+        // Notify the listeners that the value is initialized.
+        self.active_initializers.notify_additional(usize::MAX);
+        self.passive_waiters.notify_additional(usize::MAX);
+    }
+
+    /// Moves the cell from the initializing state back to the uninitialized state.
+    fn abandon_initialization(&self) {
+        self.state
+            .store(State::Uninitialized.into(), Ordering::Release);
+
+        // Notify the next initializer that it's their turn.
+        self.active_initializers.notify(1);
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`OnceCell::wait`].
+    pub struct Wait<'a, T>(WaitInner<'a, T> => &'a T);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+pin_project_lite::pin_project! {
+    /// Inner future for waiting on the cell.
+    struct WaitInner<'a, T> {
+        // The cell to wait on.
+        cell: &'a OnceCell<T>,
+
+        // An event listener for the `cell.passive_waiters` event.
+        listener: Option<EventListener>,
+    }
+}
+
+impl<T> fmt::Debug for Wait<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Wait { .. }")
+    }
+}
+
+impl<'a, T> EventListenerFuture for WaitInner<'a, T> {
+    type Output = &'a T;
+
+    #[inline]
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        context: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        ready!(this.cell.poll_wait(this.listener, strategy, context));
+
+        // SAFETY: We know that the value is initialized, so it is safe to
+        // read it.
+        Poll::Ready(unsafe { this.cell.get_unchecked() })
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`OnceCell::wait_arc`].
+    pub struct WaitArc<T>(WaitArcInner<T> => OnceCellGuardArc<T>);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+pin_project_lite::pin_project! {
+    /// Inner future for waiting on the cell through an `Arc`.
+    struct WaitArcInner<T> {
+        // The cell to wait on.
+        cell: Arc<OnceCell<T>>,
+
+        // An event listener for the `cell.passive_waiters` event.
+        listener: Option<EventListener>,
+    }
+}
+
+impl<T> fmt::Debug for WaitArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WaitArc { .. }")
+    }
+}
+
+impl<T> EventListenerFuture for WaitArcInner<T> {
+    type Output = OnceCellGuardArc<T>;
+
+    #[inline]
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        context: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        ready!(this.cell.poll_wait(this.listener, strategy, context));
+        Poll::Ready(OnceCellGuardArc(this.cell.clone()))
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::get_or_try_init`].
+    pub struct GetOrTryInit<'a, T, F, Fut> {
+        #[pin]
+        inner: Initialize<&'a OnceCell<T>, T, F, Fut>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for GetOrTryInit<'_, T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrTryInit { .. }")
+    }
+}
+
+impl<'a, T, E, F, Fut> Future for GetOrTryInit<'a, T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<&'a T, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        ready!(inner.as_mut().poll(cx))?;
+
+        // SAFETY: We know that the value is initialized, so it is safe to
+        // read it.
+        Poll::Ready(Ok(unsafe { inner.cell.get_unchecked() }))
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::get_or_try_init_arc`].
+    pub struct GetOrTryInitArc<T, F, Fut> {
+        #[pin]
+        inner: Initialize<Arc<OnceCell<T>>, T, F, Fut>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for GetOrTryInitArc<T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrTryInitArc { .. }")
+    }
+}
+
+impl<T, E, F, Fut> Future for GetOrTryInitArc<T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<OnceCellGuardArc<T>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        ready!(inner.as_mut().poll(cx))?;
+        Poll::Ready(Ok(OnceCellGuardArc(inner.cell.clone())))
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::get_or_init`].
+    pub struct GetOrInit<'a, T, F, Fut> {
+        #[pin]
+        inner: Initialize<&'a OnceCell<T>, T, Infallibly<F>, InfallibleInit<Fut>>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for GetOrInit<'_, T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrInit { .. }")
+    }
+}
+
+impl<'a, T, F, Fut> Future for GetOrInit<'a, T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = &'a T;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        match ready!(inner.as_mut().poll(cx)) {
+            // SAFETY: We know that the value is initialized, so it is safe to
+            // read it.
+            Ok(()) => Poll::Ready(unsafe { inner.cell.get_unchecked() }),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::get_or_init_arc`].
+    pub struct GetOrInitArc<T, F, Fut> {
+        #[pin]
+        inner: Initialize<Arc<OnceCell<T>>, T, Infallibly<F>, InfallibleInit<Fut>>,
+    }
+}
+
+impl<T, F, Fut> fmt::Debug for GetOrInitArc<T, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("GetOrInitArc { .. }")
+    }
+}
+
+impl<T, F, Fut> Future for GetOrInitArc<T, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    type Output = OnceCellGuardArc<T>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        match ready!(inner.as_mut().poll(cx)) {
+            Ok(()) => Poll::Ready(OnceCellGuardArc(inner.cell.clone())),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
 
-    unsafe fn wake(_: *const ()) {}
-    unsafe fn wake_by_ref(_: *const ()) {}
-    unsafe fn clone(_: *const ()) -> RawWaker {
-        RawWaker::new(ptr::null(), &NOOP_WAKER)
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::set`].
+    pub struct Set<'a, T> {
+        #[pin]
+        inner: Initialize<&'a OnceCell<T>, T, SetValue<T>, Ready<Result<T, Infallible>>>,
     }
-    unsafe fn drop(_: *const ()) {}
+}
+
+impl<T> fmt::Debug for Set<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Set { .. }")
+    }
+}
+
+impl<'a, T> Future for Set<'a, T> {
+    type Output = Result<&'a T, T>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        match ready!(inner.as_mut().poll(cx)) {
+            Ok(()) => {}
+            Err(infallible) => match infallible {},
+        }
+
+        // The value is handed back if somebody else initialized the cell.
+        match inner.as_mut().take_initializer() {
+            Some(SetValue(value)) => Poll::Ready(Err(value)),
+            // SAFETY: We know that the value is initialized, so it is safe to
+            // read it.
+            None => Poll::Ready(Ok(unsafe { inner.cell.get_unchecked() })),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceCell::set_arc`].
+    pub struct SetArc<T> {
+        #[pin]
+        inner: Initialize<Arc<OnceCell<T>>, T, SetValue<T>, Ready<Result<T, Infallible>>>,
+    }
+}
+
+impl<T> fmt::Debug for SetArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SetArc { .. }")
+    }
+}
+
+impl<T> Future for SetArc<T> {
+    type Output = Result<OnceCellGuardArc<T>, T>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut inner = self.project().inner;
+        match ready!(inner.as_mut().poll(cx)) {
+            Ok(()) => {}
+            Err(infallible) => match infallible {},
+        }
 
-    pin!(f);
+        // The value is handed back if somebody else initialized the cell.
+        match inner.as_mut().take_initializer() {
+            Some(SetValue(value)) => Poll::Ready(Err(value)),
+            None => Poll::Ready(Ok(OnceCellGuardArc(inner.cell.clone()))),
+        }
+    }
+}
+
+/// Starts the future that initializes a cell.
+pub(crate) trait Initializer<Fut> {
+    /// Starts initializing the cell.
+    fn start(self) -> Fut;
+}
+
+impl<F: FnOnce() -> Fut, Fut> Initializer<Fut> for F {
+    #[inline]
+    fn start(self) -> Fut {
+        self()
+    }
+}
+
+/// Initializes a cell with an initializer that can't fail.
+pub(crate) struct Infallibly<I>(pub(crate) I);
+
+impl<I: Initializer<Fut>, Fut> Initializer<InfallibleInit<Fut>> for Infallibly<I> {
+    #[inline]
+    fn start(self) -> InfallibleInit<Fut> {
+        InfallibleInit {
+            future: self.0.start(),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Wraps the output of an initializer that can't fail in `Ok`.
+    pub(crate) struct InfallibleInit<Fut> {
+        #[pin]
+        future: Fut,
+    }
+}
+
+impl<Fut: Future> Future for InfallibleInit<Fut> {
+    type Output = Result<Fut::Output, Infallible>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().future.poll(cx).map(Ok)
+    }
+}
+
+/// Initializes a cell with a value that's already there.
+struct SetValue<T>(T);
+
+impl<T> Initializer<Ready<Result<T, Infallible>>> for SetValue<T> {
+    #[inline]
+    fn start(self) -> Ready<Result<T, Infallible>> {
+        ready(Ok(self.0))
+    }
+}
 
-    let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &NOOP_WAKER)) };
+pin_project_lite::pin_project! {
+    /// Future that either initializes the cell or waits for someone else to.
+    ///
+    /// Unlike `Wait`, this isn't an `EventListenerFuture`: the initializer it drives needs a real
+    /// task context, which a blocking `Strategy` doesn't provide. The blocking methods run it
+    /// with `block_on_future()` instead, so waiting on the listener here is always non-blocking.
+    pub(crate) struct Initialize<B: Borrow<OnceCell<T>>, T, I, Fut> {
+        // The cell to initialize.
+        cell: B,
 
-    // Poll the future exactly once.
-    let mut cx = Context::from_waker(&waker);
+        // The initializer, until it is started.
+        initializer: Option<I>,
 
-    match f.poll(&mut cx) {
-        Poll::Ready(value) => value,
-        Poll::Pending => unreachable!("future not ready"),
+        // The running initialization.
+        #[pin]
+        future: Option<Fut>,
+
+        // Whether this future holds the cell in the initializing state.
+        initializing: bool,
+
+        // An event listener for the `cell.active_initializers` event.
+        listener: Option<EventListener>,
+
+        // Capture the `T` type.
+        _marker: PhantomData<T>,
+    }
+
+    impl<B: Borrow<OnceCell<T>>, T, I, Fut> PinnedDrop for Initialize<B, T, I, Fut> {
+        fn drop(this: Pin<&mut Self>) {
+            let mut this = this.project();
+
+            if *this.initializing {
+                // The initializer was cancelled or panicked, so let the next one try.
+                this.future.set(None);
+                Borrow::<OnceCell<T>>::borrow(&*this.cell).abandon_initialization();
+            }
+        }
+    }
+}
+
+impl<B: Borrow<OnceCell<T>>, T, I, Fut> Initialize<B, T, I, Fut> {
+    /// Creates a future initializing `cell` with `initializer`.
+    #[inline]
+    pub(crate) fn new(cell: B, initializer: I) -> Self {
+        Initialize {
+            cell,
+            initializer: Some(initializer),
+            future: None,
+            initializing: false,
+            listener: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the cell being initialized.
+    #[inline]
+    pub(crate) fn cell(&self) -> &B {
+        &self.cell
+    }
+
+    /// Takes back the initializer if it was never started.
+    #[inline]
+    fn take_initializer(self: Pin<&mut Self>) -> Option<I> {
+        self.project().initializer.take()
+    }
+}
+
+impl<B, T, E, I, Fut> Future for Initialize<B, T, I, Fut>
+where
+    B: Borrow<OnceCell<T>>,
+    I: Initializer<Fut>,
+    Fut: Future<Output = Result<T, E>>,
+{
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        let cell: &OnceCell<T> = (*this.cell).borrow();
+
+        loop {
+            // Drive the initializer if we are running it.
+            if let Some(future) = this.future.as_mut().as_pin_mut() {
+                let result = ready!(future.poll(cx));
+                this.future.set(None);
+                *this.initializing = false;
+
+                return Poll::Ready(match result {
+                    Ok(value) => {
+                        cell.finish_initialization(value);
+                        Ok(())
+                    }
+                    Err(err) => {
+                        // Leave the cell uninitialized for the next initializer.
+                        cell.abandon_initialization();
+                        Err(err)
+                    }
+                });
+            }
+
+            // Check the current state of the cell.
+            match cell.state.load(Ordering::Acquire).into() {
+                State::Initialized => {
+                    // The cell is initialized now, so we can return.
+                    return Poll::Ready(Ok(()));
+                }
+                State::Initializing => {
+                    // The cell is currently initializing, so wait for it to either complete or
+                    // fail. Blocking callers park the thread when this returns `Pending`.
+                    if this.listener.is_none() {
+                        *this.listener = Some(cell.active_initializers.listen());
+                    } else {
+                        ready!(NonBlocking::default().poll(this.listener, cx));
+                    }
+                }
+                State::Uninitialized => {
+                    // Try to move the cell into the initializing state.
+                    if cell
+                        .state
+                        .compare_exchange(
+                            State::Uninitialized.into(),
+                            State::Initializing.into(),
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_err()
+                    {
+                        // Somebody else got there first.
+                        continue;
+                    }
+
+                    // Now that we have an exclusive lock on the cell's value, we can start
+                    // initializing it. If starting panics, dropping this future resets the cell.
+                    *this.initializing = true;
+                    let initializer = this
+                        .initializer
+                        .take()
+                        .expect("future polled after completion");
+                    this.future.set(Some(initializer.start()));
+                }
+            }
+        }
     }
 }
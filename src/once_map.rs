@@ -0,0 +1,421 @@
+use alloc::sync::Arc;
+
+use core::borrow::Borrow;
+use core::fmt;
+use core::future::Future;
+use core::hash::Hash;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::once_cell::{InfallibleInit, Infallibly, Initialize, Initializer};
+use crate::sync_mutex::SyncMutex;
+use crate::OnceCell;
+
+/// The map holding keyed cells.
+#[cfg(feature = "std")]
+pub(crate) type Map<K, V> = std::collections::HashMap<K, V>;
+
+/// The map holding keyed cells.
+#[cfg(not(feature = "std"))]
+pub(crate) type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+
+/// A map whose values are initialized at most once per key.
+///
+/// Each key gets its own [`OnceCell`]: the first task asking for a key runs its initializer,
+/// while concurrent tasks asking for the same key wait for it. Tasks asking for other keys are
+/// never held up.
+///
+/// Values are handed out as [`Arc`]s, so that they stay alive after being removed from the map.
+///
+/// Keys must implement both [`Hash`] and [`Ord`]. A `HashMap` stores them when the `std`
+/// feature is enabled and a `BTreeMap` otherwise, and requiring both keeps the accepted keys the
+/// same either way. Initializing a value also requires [`Clone`] keys, so that an abandoned
+/// initialization can remove its key again.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::OnceMap;
+///
+/// # futures_lite::future::block_on(async {
+/// let connections = OnceMap::new();
+///
+/// let a = connections.get_or_init("tenant-a", || async { 1 }).await;
+/// let b = connections.get_or_init("tenant-b", || async { 2 }).await;
+/// let a2 = connections.get_or_init("tenant-a", || async { 3 }).await;
+/// assert_eq!((*a, *b, *a2), (1, 2, 1));
+/// # });
+/// ```
+pub struct OnceMap<K, V> {
+    /// The cell of each key.
+    ///
+    /// This lock is only held for as long as it takes to look up, insert or remove a cell.
+    cells: SyncMutex<Map<K, Arc<OnceCell<Arc<V>>>>>,
+}
+
+impl<K: Eq + Hash + Ord, V> OnceMap<K, V> {
+    /// Creates a new, empty map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::OnceMap;
+    ///
+    /// let map = OnceMap::<u32, String>::new();
+    /// assert_eq!(map.get(&1), None);
+    /// ```
+    pub fn new() -> OnceMap<K, V> {
+        OnceMap {
+            cells: SyncMutex::new(Map::new()),
+        }
+    }
+
+    /// Returns the value of `key` if it has been initialized.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::OnceMap;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let map = OnceMap::new();
+    /// assert_eq!(map.get(&1), None);
+    ///
+    /// map.get_or_init(1, || async { "one" }).await;
+    /// assert_eq!(map.get(&1).as_deref(), Some(&"one"));
+    /// # });
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + Ord + ?Sized,
+    {
+        self.cells.lock().get(key)?.get().cloned()
+    }
+
+    /// Removes `key` from the map, returning its value if it was initialized.
+    ///
+    /// An initialization of `key` that is already running still completes for the tasks waiting
+    /// on it, but its value isn't stored in the map. The next task asking for `key` initializes
+    /// it again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::OnceMap;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let map = OnceMap::new();
+    /// map.get_or_init(1, || async { "one" }).await;
+    ///
+    /// assert_eq!(map.remove(&1).as_deref(), Some(&"one"));
+    /// assert_eq!(map.get(&1), None);
+    /// assert_eq!(*map.get_or_init(1, || async { "uno" }).await, "uno");
+    /// # });
+    /// ```
+    pub fn remove<Q>(&self, key: &Q) -> Option<Arc<V>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + Ord + ?Sized,
+    {
+        let cell = self.cells.lock().remove(key)?;
+        cell.get().cloned()
+    }
+
+    /// Returns the value of `key`, initializing it with the given closure if needed.
+    ///
+    /// Many tasks may call this method for the same key, but only one closure per key will be
+    /// invoked and the others wait for it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::OnceMap;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let map = OnceMap::new();
+    /// assert_eq!(*map.get_or_init(1, || async { "one" }).await, "one");
+    /// assert_eq!(*map.get_or_init(1, || async { "uno" }).await, "one");
+    /// # });
+    /// ```
+    pub fn get_or_init<F, Fut>(&self, key: K, closure: F) -> OnceMapGetOrInit<'_, K, V, F, Fut>
+    where
+        K: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        OnceMapGetOrInit {
+            inner: Initializing::new(self, key, Infallibly(closure)),
+        }
+    }
+
+    /// Returns the value of `key`, initializing it with the given closure if needed.
+    ///
+    /// Many tasks may call this method for the same key, but only one closure per key runs at a
+    /// time. If it fails, its error is returned, `key` stays uninitialized and the next waiting
+    /// task runs its own closure.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::OnceMap;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let map = OnceMap::new();
+    ///
+    /// assert!(map.get_or_try_init(1, || async { "x".parse::<i32>() }).await.is_err());
+    /// assert_eq!(map.get(&1), None);
+    ///
+    /// let value = map.get_or_try_init(1, || async { "1".parse::<i32>() }).await;
+    /// assert_eq!(value.as_deref(), Ok(&1));
+    /// # });
+    /// ```
+    pub fn get_or_try_init<E, F, Fut>(
+        &self,
+        key: K,
+        closure: F,
+    ) -> OnceMapGetOrTryInit<'_, K, V, F, Fut>
+    where
+        K: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        OnceMapGetOrTryInit {
+            inner: Initializing::new(self, key, closure),
+        }
+    }
+
+    /// Returns the cell of `key`, inserting an empty one if needed.
+    fn cell(&self, key: &K) -> Arc<OnceCell<Arc<V>>>
+    where
+        K: Clone,
+    {
+        let mut cells = self.cells.lock();
+        if let Some(cell) = cells.get(key) {
+            return cell.clone();
+        }
+
+        cells
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone()
+    }
+
+    /// Removes an uninitialized cell from the map if nobody else is using it.
+    fn discard(&self, key: &K, cell: &Arc<OnceCell<Arc<V>>>) {
+        let mut cells = self.cells.lock();
+
+        match cells.get(key) {
+            Some(current) if Arc::ptr_eq(current, cell) => {}
+            _ => return,
+        }
+
+        // Other references can only be made through the map, which is now locked. With only the
+        // map's and ours left, nobody is waiting to retry the initialization.
+        if Arc::strong_count(cell) == 2 && !cell.is_initialized() {
+            cells.remove(key);
+        }
+    }
+}
+
+impl<K: Eq + Hash + Ord, V> Default for OnceMap<K, V> {
+    #[inline]
+    fn default() -> OnceMap<K, V> {
+        OnceMap::new()
+    }
+}
+
+impl<K, V> fmt::Debug for OnceMap<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnceMap { .. }")
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceMap::get_or_init`].
+    pub struct OnceMapGetOrInit<'a, K, V, F, Fut>
+    where
+        K: Eq,
+        K: Hash,
+        K: Ord,
+    {
+        #[pin]
+        inner: Initializing<'a, K, V, Infallibly<F>, InfallibleInit<Fut>>,
+    }
+}
+
+impl<K: Eq + Hash + Ord, V, F, Fut> fmt::Debug for OnceMapGetOrInit<'_, K, V, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnceMapGetOrInit { .. }")
+    }
+}
+
+impl<K, V, F, Fut> Future for OnceMapGetOrInit<'_, K, V, F, Fut>
+where
+    K: Eq + Hash + Ord + Clone,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = V>,
+{
+    type Output = Arc<V>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match ready!(self.project().inner.poll(cx)) {
+            Ok(value) => Poll::Ready(value),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`OnceMap::get_or_try_init`].
+    pub struct OnceMapGetOrTryInit<'a, K, V, F, Fut>
+    where
+        K: Eq,
+        K: Hash,
+        K: Ord,
+    {
+        #[pin]
+        inner: Initializing<'a, K, V, F, Fut>,
+    }
+}
+
+impl<K: Eq + Hash + Ord, V, F, Fut> fmt::Debug for OnceMapGetOrTryInit<'_, K, V, F, Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OnceMapGetOrTryInit { .. }")
+    }
+}
+
+impl<K, V, E, F, Fut> Future for OnceMapGetOrTryInit<'_, K, V, F, Fut>
+where
+    K: Eq + Hash + Ord + Clone,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<V, E>>,
+{
+    type Output = Result<Arc<V>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future that either initializes the cell of a key or waits for someone else to.
+    ///
+    /// When dropped, it removes the cell again if the initialization was abandoned and nobody
+    /// is left to retry it.
+    struct Initializing<'a, K, V, I, Fut>
+    where
+        K: Eq,
+        K: Hash,
+        K: Ord,
+    {
+        // The map holding the cell.
+        map: &'a OnceMap<K, V>,
+
+        // The key of the cell.
+        key: K,
+
+        // The initializer, until the cell is looked up.
+        initializer: Option<I>,
+
+        // The initialization of the cell, or the wait for it.
+        #[pin]
+        init: Option<Initialize<Arc<OnceCell<Arc<V>>>, Arc<V>, Shared<I>, SharedInit<Fut>>>,
+    }
+
+    impl<'a, K, V, I, Fut> PinnedDrop for Initializing<'a, K, V, I, Fut>
+    where
+        K: Eq,
+        K: Hash,
+        K: Ord,
+    {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+
+            if let Some(init) = this.init.as_pin_mut() {
+                this.map.discard(this.key, init.cell());
+            }
+        }
+    }
+}
+
+impl<'a, K: Eq + Hash + Ord, V, I, Fut> Initializing<'a, K, V, I, Fut> {
+    /// Creates a future initializing the cell of `key` with `initializer` if needed.
+    #[inline]
+    fn new(map: &'a OnceMap<K, V>, key: K, initializer: I) -> Self {
+        Initializing {
+            map,
+            key,
+            initializer: Some(initializer),
+            init: None,
+        }
+    }
+}
+
+impl<K, V, E, I, Fut> Future for Initializing<'_, K, V, I, Fut>
+where
+    K: Eq + Hash + Ord + Clone,
+    I: Initializer<Fut>,
+    Fut: Future<Output = Result<V, E>>,
+{
+    type Output = Result<Arc<V>, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        if this.init.is_none() {
+            let cell = this.map.cell(this.key);
+            let initializer = this
+                .initializer
+                .take()
+                .expect("future polled after completion");
+            this.init
+                .set(Some(Initialize::new(cell, Shared(initializer))));
+        }
+
+        let mut init = this.init.as_mut().as_pin_mut().unwrap();
+        ready!(init.as_mut().poll(cx))?;
+
+        // SAFETY: We know that the cell is initialized, so it is safe to
+        // read it.
+        let value = unsafe { init.cell().get_unchecked() }.clone();
+
+        // The cell is initialized, so there's nothing left to discard.
+        this.init.set(None);
+        Poll::Ready(Ok(value))
+    }
+}
+
+/// Initializes a cell with an initializer whose value is shared through an `Arc`.
+struct Shared<I>(I);
+
+impl<I: Initializer<Fut>, Fut> Initializer<SharedInit<Fut>> for Shared<I> {
+    #[inline]
+    fn start(self) -> SharedInit<Fut> {
+        SharedInit {
+            future: self.0.start(),
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Wraps the value of an initializer in an `Arc`.
+    struct SharedInit<Fut> {
+        #[pin]
+        future: Fut,
+    }
+}
+
+impl<T, E, Fut: Future<Output = Result<T, E>>> Future for SharedInit<Fut> {
+    type Output = Result<Arc<T>, E>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project()
+            .future
+            .poll(cx)
+            .map(|result| result.map(Arc::new))
+    }
+}
@@ -0,0 +1,412 @@
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::Poll;
+
+use event_listener::{Event, EventListener, IntoNotification};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+
+/// An event that lets all tasks through while it is set.
+///
+/// [`wait()`][ManualResetEvent::wait] completes as long as the event is set. The event stays set
+/// until [`reset()`][ManualResetEvent::reset] is called, however many tasks pass.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::ManualResetEvent;
+/// use futures_lite::future;
+///
+/// # futures_lite::future::block_on(async {
+/// let event = ManualResetEvent::new(false);
+/// assert!(future::poll_once(event.wait()).await.is_none());
+///
+/// event.set();
+/// event.wait().await;
+/// event.wait().await;
+///
+/// event.reset();
+/// assert!(future::poll_once(event.wait()).await.is_none());
+/// # });
+/// ```
+pub struct ManualResetEvent {
+    /// Whether the event is set.
+    set: AtomicBool,
+
+    /// Tasks waiting for the event to be set.
+    event: Event,
+}
+
+impl ManualResetEvent {
+    /// Creates a new event, initially set if `set` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// let event = ManualResetEvent::new(true);
+    /// assert!(event.is_set());
+    /// ```
+    pub const fn new(set: bool) -> ManualResetEvent {
+        ManualResetEvent {
+            set: AtomicBool::new(set),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns `true` if the event is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// let event = ManualResetEvent::new(false);
+    /// assert!(!event.is_set());
+    /// ```
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.set.load(Ordering::Acquire)
+    }
+
+    /// Sets the event, waking up all waiting tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// let event = ManualResetEvent::new(false);
+    /// event.set();
+    /// assert!(event.is_set());
+    /// ```
+    pub fn set(&self) {
+        self.set.store(true, Ordering::Release);
+        self.event.notify(usize::MAX);
+    }
+
+    /// Resets the event, so that tasks wait for it to be set again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// let event = ManualResetEvent::new(true);
+    /// event.reset();
+    /// assert!(!event.is_set());
+    /// ```
+    #[inline]
+    pub fn reset(&self) {
+        self.set.store(false, Ordering::Release);
+    }
+
+    /// Waits until the event is set.
+    ///
+    /// Completes right away if it already is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let event = ManualResetEvent::new(true);
+    /// event.wait().await;
+    /// # });
+    /// ```
+    pub fn wait(&self) -> ManualResetEventWait<'_> {
+        ManualResetEventWait::_new(ResetEventWaitInner {
+            set: &self.set,
+            event: &self.event,
+            consume: false,
+            listener: None,
+        })
+    }
+
+    /// Waits until the event is set.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][ManualResetEvent::wait] method,
+    /// this method will block the current thread until the event is set.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `ManualResetEvent` can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::ManualResetEvent;
+    ///
+    /// let event = ManualResetEvent::new(true);
+    /// event.wait_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking(&self) {
+        self.wait().wait();
+    }
+}
+
+impl Default for ManualResetEvent {
+    #[inline]
+    fn default() -> ManualResetEvent {
+        ManualResetEvent::new(false)
+    }
+}
+
+impl fmt::Debug for ManualResetEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ManualResetEvent")
+            .field("set", &self.is_set())
+            .finish()
+    }
+}
+
+/// An event that lets a single task through each time it is set.
+///
+/// The first task to [`wait()`][AutoResetEvent::wait] on a set event resets it as it passes,
+/// so the others keep waiting for the next [`set()`][AutoResetEvent::set]. Setting an event
+/// that is already set does nothing.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::AutoResetEvent;
+/// use futures_lite::future;
+///
+/// # futures_lite::future::block_on(async {
+/// let event = AutoResetEvent::new(false);
+///
+/// event.set();
+/// event.set();
+/// event.wait().await;
+///
+/// // Only one task was let through.
+/// assert!(!event.is_set());
+/// assert!(future::poll_once(event.wait()).await.is_none());
+/// # });
+/// ```
+pub struct AutoResetEvent {
+    /// Whether the event is set.
+    set: AtomicBool,
+
+    /// Tasks waiting for the event to be set.
+    event: Event,
+}
+
+impl AutoResetEvent {
+    /// Creates a new event, initially set if `set` is `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// let event = AutoResetEvent::new(true);
+    /// assert!(event.is_set());
+    /// ```
+    pub const fn new(set: bool) -> AutoResetEvent {
+        AutoResetEvent {
+            set: AtomicBool::new(set),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns `true` if the event is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// let event = AutoResetEvent::new(false);
+    /// assert!(!event.is_set());
+    /// ```
+    #[inline]
+    pub fn is_set(&self) -> bool {
+        self.set.load(Ordering::Acquire)
+    }
+
+    /// Sets the event, waking up one waiting task.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// let event = AutoResetEvent::new(false);
+    /// event.set();
+    /// assert!(event.is_set());
+    /// ```
+    pub fn set(&self) {
+        if !self.set.swap(true, Ordering::AcqRel) {
+            self.event.notify(1.additional());
+        }
+    }
+
+    /// Resets the event without letting a task through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// let event = AutoResetEvent::new(true);
+    /// event.reset();
+    /// assert!(!event.is_set());
+    /// ```
+    #[inline]
+    pub fn reset(&self) {
+        self.set.store(false, Ordering::Release);
+    }
+
+    /// Waits until the event is set, and resets it.
+    ///
+    /// Completes right away if it already is set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let event = AutoResetEvent::new(true);
+    /// event.wait().await;
+    /// assert!(!event.is_set());
+    /// # });
+    /// ```
+    pub fn wait(&self) -> AutoResetEventWait<'_> {
+        AutoResetEventWait::_new(ResetEventWaitInner {
+            set: &self.set,
+            event: &self.event,
+            consume: true,
+            listener: None,
+        })
+    }
+
+    /// Waits until the event is set, and resets it.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][AutoResetEvent::wait] method,
+    /// this method will block the current thread until the event is set.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that an `AutoResetEvent` can be used in both asynchronous and synchronous
+    /// contexts. Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::AutoResetEvent;
+    ///
+    /// let event = AutoResetEvent::new(true);
+    /// event.wait_blocking();
+    /// assert!(!event.is_set());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking(&self) {
+        self.wait().wait();
+    }
+}
+
+impl Default for AutoResetEvent {
+    #[inline]
+    fn default() -> AutoResetEvent {
+        AutoResetEvent::new(false)
+    }
+}
+
+impl fmt::Debug for AutoResetEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AutoResetEvent")
+            .field("set", &self.is_set())
+            .finish()
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`ManualResetEvent::wait`].
+    pub struct ManualResetEventWait<'a>(ResetEventWaitInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl fmt::Debug for ManualResetEventWait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ManualResetEventWait { .. }")
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`AutoResetEvent::wait`].
+    pub struct AutoResetEventWait<'a>(ResetEventWaitInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl fmt::Debug for AutoResetEventWait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AutoResetEventWait { .. }")
+    }
+}
+
+/// Inner future for waiting on either kind of event.
+struct ResetEventWaitInner<'a> {
+    /// Whether the event is set.
+    set: &'a AtomicBool,
+
+    /// Tasks waiting for the event to be set.
+    event: &'a Event,
+
+    /// Whether passing resets the event.
+    consume: bool,
+
+    /// An event listener for the `event` event.
+    listener: Option<EventListener>,
+}
+
+impl ResetEventWaitInner<'_> {
+    /// Attempts to pass the event.
+    fn try_pass(&self) -> bool {
+        if self.consume {
+            self.set
+                .compare_exchange(true, false, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        } else {
+            self.set.load(Ordering::Acquire)
+        }
+    }
+}
+
+impl EventListenerFuture for ResetEventWaitInner<'_> {
+    type Output = ();
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<()> {
+        let this = self.get_mut();
+
+        loop {
+            if this.try_pass() {
+                // A notification this listener received is passed on to another one as it is
+                // dropped.
+                this.listener = None;
+                return Poll::Ready(());
+            }
+
+            if this.listener.is_some() {
+                ready!(strategy.poll(&mut this.listener, cx));
+            } else {
+                this.listener = Some(this.event.listen());
+            }
+        }
+    }
+}
@@ -3,14 +3,25 @@ use core::fmt;
 use core::mem::{self, ManuallyDrop};
 use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use core::task::Poll;
 
 use alloc::sync::Arc;
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{Duration, Instant};
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::deadline::BlockingDeadline;
+use crate::timer::Timer;
+
 pub(crate) mod futures;
 mod raw;
 
 use self::futures::{
-    Read, ReadArc, UpgradableRead, UpgradableReadArc, Upgrade, UpgradeArc, Write, WriteArc,
+    Read, ReadArc, ReadArcTimeout, ReadTimeout, UpgradableRead, UpgradableReadArc,
+    UpgradableReadArcTimeout, UpgradableReadTimeout, Upgrade, UpgradeArc, Write, WriteArc,
+    WriteArcTimeout, WriteTimeout,
 };
 use self::raw::{RawRwLock, RawUpgrade};
 
@@ -18,8 +29,9 @@ use self::raw::{RawRwLock, RawUpgrade};
 ///
 /// This type of lock allows multiple readers or one writer at any point in time.
 ///
-/// The locking strategy is write-preferring, which means writers are never starved.
-/// Releasing a write lock wakes the next blocked reader and the next blocked writer.
+/// The locking strategy is write-preferring by default, which means writers are never starved.
+/// Releasing a write lock wakes the next blocked reader and the next blocked writer. Other
+/// strategies can be picked with [`RwLock::with_policy()`].
 ///
 /// # Examples
 ///
@@ -67,8 +79,25 @@ impl<T> RwLock<T> {
     #[must_use]
     #[inline]
     pub const fn new(t: T) -> RwLock<T> {
+        RwLock::with_policy(t, RwLockPolicy::write_preferring())
+    }
+
+    /// Creates a new reader-writer lock with the given policy.
+    ///
+    /// See [`RwLockPolicy`] for the available policies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{RwLock, RwLockPolicy};
+    ///
+    /// let lock = RwLock::with_policy(0, RwLockPolicy::phase_fair());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn with_policy(t: T, policy: RwLockPolicy) -> RwLock<T> {
         RwLock {
-            raw: RawRwLock::new(),
+            raw: RawRwLock::new(policy),
             value: UnsafeCell::new(t),
         }
     }
@@ -148,6 +177,38 @@ impl<T> RwLock<T> {
         ReadArc::new(self.raw.read(), self)
     }
 
+    /// Acquires an owned, reference-counted read lock, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let lock = Arc::new(RwLock::new(1));
+    /// let writer = lock.write().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.read_arc_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn read_arc_timeout<'a, Tm: Timer>(
+        self: &'a Arc<Self>,
+        timer: Tm,
+    ) -> ReadArcTimeout<'a, T, Tm> {
+        ReadArcTimeout::new(self.read_arc(), timer)
+    }
+
     /// Acquires an owned, reference-counted read lock.
     ///
     /// Returns a guard that releases the lock when dropped.
@@ -276,6 +337,34 @@ impl<T: ?Sized> RwLock<T> {
         self.read().wait()
     }
 
+    /// Acquires a read lock, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    /// let writer = lock.write().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.read_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn read_timeout<Tm: Timer>(&self, timer: Tm) -> ReadTimeout<'_, T, Tm> {
+        ReadTimeout::new(self.read(), timer)
+    }
+
     /// Attempts to acquire a read lock with the possiblity to upgrade to a write lock.
     ///
     /// If a read lock could not be acquired at this time, then [`None`] is returned. Otherwise, a
@@ -343,6 +432,38 @@ impl<T: ?Sized> RwLock<T> {
         UpgradableRead::new(self.raw.upgradable_read(), self.value.get())
     }
 
+    /// Acquires a read lock with the possiblity to upgrade to a write lock, giving up once `timer`
+    /// expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    /// let reader = lock.upgradable_read().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.upgradable_read_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn upgradable_read_timeout<Tm: Timer>(
+        &self,
+        timer: Tm,
+    ) -> UpgradableReadTimeout<'_, T, Tm> {
+        UpgradableReadTimeout::new(self.upgradable_read(), timer)
+    }
+
     /// Attempts to acquire a read lock with the possiblity to upgrade to a write lock.
     ///
     /// Returns a guard that releases the lock when dropped.
@@ -491,6 +612,39 @@ impl<T: ?Sized> RwLock<T> {
         UpgradableReadArc::new(self.raw.upgradable_read(), self)
     }
 
+    /// Acquires an owned, reference-counted read lock with the possiblity to upgrade to a write
+    /// lock, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let lock = Arc::new(RwLock::new(1));
+    /// let reader = lock.upgradable_read().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.upgradable_read_arc_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn upgradable_read_arc_timeout<'a, Tm: Timer>(
+        self: &'a Arc<Self>,
+        timer: Tm,
+    ) -> UpgradableReadArcTimeout<'a, T, Tm> {
+        UpgradableReadArcTimeout::new(self.upgradable_read_arc(), timer)
+    }
+
     /// Attempts to acquire a write lock.
     ///
     /// If a write lock could not be acquired at this time, then [`None`] is returned. Otherwise, a
@@ -571,6 +725,106 @@ impl<T: ?Sized> RwLock<T> {
         self.write().wait()
     }
 
+    /// Acquires a write lock, giving up after `timeout`.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`None`] if the write lock could
+    /// not be acquired in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`write`] method, this method will
+    /// block the current thread until the write lock is acquired or the timeout expires.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a lock can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let reader = lock.read_blocking();
+    /// assert!(lock.write_blocking_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn write_blocking_timeout(&self, timeout: Duration) -> Option<RwLockWriteGuard<'_, T>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.write_blocking_deadline(deadline),
+            None => Some(self.write_blocking()),
+        }
+    }
+
+    /// Acquires a write lock, giving up at `deadline`.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`None`] if the write lock could
+    /// not be acquired in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`write`] method, this method will
+    /// block the current thread until the write lock is acquired or the deadline is reached.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a lock can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::RwLock;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let reader = lock.read_blocking();
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// assert!(lock.write_blocking_deadline(deadline).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn write_blocking_deadline(&self, deadline: Instant) -> Option<RwLockWriteGuard<'_, T>> {
+        let write = self.write();
+        pin!(write);
+
+        // Dropping the future on expiry releases the partially acquired write lock.
+        match write.poll_with_strategy(&mut BlockingDeadline::new(deadline), &mut ()) {
+            Poll::Ready(guard) => Some(guard),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Acquires a write lock, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::time::Duration;
+    ///
+    /// let lock = RwLock::new(1);
+    /// let reader = lock.read().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.write_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn write_timeout<Tm: Timer>(&self, timer: Tm) -> WriteTimeout<'_, T, Tm> {
+        WriteTimeout::new(self.write(), timer)
+    }
+
     /// Attempts to acquire an owned, reference-counted write lock.
     ///
     /// If a write lock could not be acquired at this time, then [`None`] is returned. Otherwise, a
@@ -621,6 +875,38 @@ impl<T: ?Sized> RwLock<T> {
         WriteArc::new(self.raw.write(), self)
     }
 
+    /// Acquires an owned, reference-counted write lock, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the lock when dropped, or [`TimedOut`] if the timer expired
+    /// first. See [`Timer`] for the timers that can be used.
+    ///
+    /// [`TimedOut`]: crate::TimedOut
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::RwLock;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let lock = Arc::new(RwLock::new(1));
+    /// let reader = lock.read().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(lock.write_arc_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn write_arc_timeout<'a, Tm: Timer>(
+        self: &'a Arc<Self>,
+        timer: Tm,
+    ) -> WriteArcTimeout<'a, T, Tm> {
+        WriteArcTimeout::new(self.write_arc(), timer)
+    }
+
     /// Acquires an owned, reference-counted write lock.
     ///
     /// Returns a guard that releases the lock when dropped.
@@ -691,6 +977,93 @@ impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLock<T> {
     }
 }
 
+/// The policy deciding whether readers or writers of a [`RwLock`] go first.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::{RwLock, RwLockPolicy};
+///
+/// // A read-heavy cache that can tolerate delayed updates.
+/// let lock = RwLock::with_policy(0, RwLockPolicy::read_preferring());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RwLockPolicy(PolicyKind);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PolicyKind {
+    /// Waiting writers keep new readers out.
+    WritePreferring,
+
+    /// New readers don't wait for waiting writers.
+    ReadPreferring,
+
+    /// Readers and writers take turns.
+    PhaseFair,
+}
+
+impl RwLockPolicy {
+    /// Writer preference, where a writer waiting for the lock keeps new readers out.
+    ///
+    /// Writers are never starved, but a steady stream of writers can starve readers. This is the
+    /// policy used by [`RwLock::new()`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{RwLock, RwLockPolicy};
+    ///
+    /// let lock = RwLock::with_policy(0, RwLockPolicy::write_preferring());
+    /// ```
+    pub const fn write_preferring() -> RwLockPolicy {
+        RwLockPolicy(PolicyKind::WritePreferring)
+    }
+
+    /// Reader preference, where new readers get the lock as long as no writer is holding it.
+    ///
+    /// A writer only gets the lock once there are no readers left, so readers are never starved,
+    /// but a steady stream of readers can starve writers. Upgrading an upgradable read lock
+    /// also waits for all other readers to be gone.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{RwLock, RwLockPolicy};
+    ///
+    /// let lock = RwLock::with_policy(0, RwLockPolicy::read_preferring());
+    /// ```
+    pub const fn read_preferring() -> RwLockPolicy {
+        RwLockPolicy(PolicyKind::ReadPreferring)
+    }
+
+    /// Phase fairness, where readers and writers take turns.
+    ///
+    /// A writer waiting for the lock keeps new readers out, like with
+    /// [`write_preferring()`][RwLockPolicy::write_preferring]. Once it is done, the readers it
+    /// blocked get the lock before the next writer does. Neither side can be starved.
+    ///
+    /// The writer hands the lock over to those readers as it unlocks, so a blocked read operation
+    /// that isn't polled again holds the read lock until it is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{RwLock, RwLockPolicy};
+    ///
+    /// let lock = RwLock::with_policy(0, RwLockPolicy::phase_fair());
+    /// ```
+    pub const fn phase_fair() -> RwLockPolicy {
+        RwLockPolicy(PolicyKind::PhaseFair)
+    }
+}
+
+impl Default for RwLockPolicy {
+    #[inline]
+    fn default() -> RwLockPolicy {
+        RwLockPolicy::write_preferring()
+    }
+}
+
 impl<T> From<T> for RwLock<T> {
     #[inline]
     fn from(val: T) -> RwLock<T> {
@@ -698,7 +1071,7 @@ impl<T> From<T> for RwLock<T> {
     }
 }
 
-impl<T: Default + ?Sized> Default for RwLock<T> {
+impl<T: Default> Default for RwLock<T> {
     #[inline]
     fn default() -> RwLock<T> {
         RwLock::new(Default::default())
@@ -719,6 +1092,83 @@ pub struct RwLockReadGuard<'a, T: ?Sized> {
 unsafe impl<T: Sync + ?Sized> Send for RwLockReadGuard<'_, T> {}
 unsafe impl<T: Sync + ?Sized> Sync for RwLockReadGuard<'_, T> {}
 
+impl<'a, T: ?Sized> RwLockReadGuard<'a, T> {
+    /// Makes a new guard for a component of the locked data.
+    ///
+    /// The read lock stays held until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `RwLockReadGuard::map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{RwLock, RwLockReadGuard};
+    ///
+    /// let lock = RwLock::new((1, 2));
+    ///
+    /// let second = RwLockReadGuard::map(lock.read().await, |pair| &pair.1);
+    /// assert_eq!(*second, 2);
+    /// assert!(lock.try_write().is_none());
+    ///
+    /// drop(second);
+    /// assert!(lock.try_write().is_some());
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> RwLockReadGuard<'a, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        // If `f` panics, `guard` is dropped and the read lock is released.
+        let value: *const U = f(unsafe { &*guard.value });
+        let guard = ManuallyDrop::new(guard);
+
+        RwLockReadGuard {
+            lock: guard.lock,
+            value,
+        }
+    }
+
+    /// Attempts to make a new guard for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as `RwLockReadGuard::try_map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{RwLock, RwLockReadGuard};
+    ///
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    ///
+    /// let reader = RwLockReadGuard::try_map(lock.read().await, |v| v.get(5)).unwrap_err();
+    /// let first = RwLockReadGuard::try_map(reader, |v| v.first()).unwrap();
+    /// assert_eq!(*first, 1);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<RwLockReadGuard<'a, U>, Self>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        let value: *const U = match f(unsafe { &*guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+        let guard = ManuallyDrop::new(guard);
+
+        Ok(RwLockReadGuard {
+            lock: guard.lock,
+            value,
+        })
+    }
+}
+
 impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
     #[inline]
     fn drop(&mut self) {
@@ -789,23 +1239,108 @@ impl<T> RwLockReadGuardArc<T> {
             lock: NonNull::new(ptr as *mut RwLock<T> as *mut T).unwrap(),
         }
     }
-}
 
-impl<T> Drop for RwLockReadGuardArc<T> {
+    /// Consumes the guard (without unlocking) and returns the underlying `Arc`.
     #[inline]
-    fn drop(&mut self) {
-        // SAFETY: we are in `drop`, decrementing the reference count
-        // on purpose.
-        // We hold a read lock on the `RwLock`.
-        unsafe {
-            let arc = ManuallyDrop::into_inner(Self::inner_arc(self));
-            arc.raw.read_unlock();
-        }
+    fn into_arc(guard: Self) -> Arc<RwLock<T>> {
+        // SAFETY: `guard` is forgotten, so only the returned `Arc` decrements the reference count.
+        let arc = unsafe { ManuallyDrop::into_inner(Self::inner_arc(&guard)) };
+        mem::forget(guard);
+        arc
     }
-}
 
-impl<T: fmt::Debug> fmt::Debug for RwLockReadGuardArc<T> {
-    #[inline]
+    /// Makes a new [`MappedRwLockReadGuardArc`] for a component of the locked data.
+    ///
+    /// The read lock stays held until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `RwLockReadGuardArc::map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{RwLock, RwLockReadGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, 2)));
+    ///
+    /// let second = RwLockReadGuardArc::map(lock.read_arc().await, |pair| &pair.1);
+    /// assert_eq!(*second, 2);
+    /// assert!(lock.try_write().is_none());
+    ///
+    /// drop(second);
+    /// assert!(lock.try_write().is_some());
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> MappedRwLockReadGuardArc<T, U>
+    where
+        F: FnOnce(&T) -> &U,
+    {
+        // If `f` panics, `guard` is dropped and the read lock is released.
+        let value: *const U = f(&*guard);
+
+        MappedRwLockReadGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedRwLockReadGuardArc`] for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockReadGuardArc::try_map(...)`. A method would interfere with methods of the same name
+    /// on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{RwLock, RwLockReadGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new(vec![1, 2, 3]));
+    ///
+    /// let reader = RwLockReadGuardArc::try_map(lock.read_arc().await, |v| v.get(5)).unwrap_err();
+    /// let first = RwLockReadGuardArc::try_map(reader, |v| v.first()).unwrap();
+    /// assert_eq!(*first, 1);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<MappedRwLockReadGuardArc<T, U>, Self>
+    where
+        F: FnOnce(&T) -> Option<&U>,
+    {
+        let value: *const U = match f(&*guard) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedRwLockReadGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        })
+    }
+}
+
+impl<T> Drop for RwLockReadGuardArc<T> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: we are in `drop`, decrementing the reference count
+        // on purpose.
+        // We hold a read lock on the `RwLock`.
+        unsafe {
+            let arc = ManuallyDrop::into_inner(Self::inner_arc(self));
+            arc.raw.read_unlock();
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for RwLockReadGuardArc<T> {
+    #[inline]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&**self, f)
     }
@@ -1301,6 +1836,84 @@ impl<'a, T: ?Sized> RwLockWriteGuard<'a, T> {
             value: write.value,
         }
     }
+
+    /// Makes a new guard for a component of the locked data.
+    ///
+    /// The write lock stays held until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `RwLockWriteGuard::map(...)`. A
+    /// method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new((1, 2));
+    ///
+    /// let mut second = RwLockWriteGuard::map(lock.write().await, |pair| &mut pair.1);
+    /// *second = 3;
+    /// assert!(lock.try_read().is_none());
+    ///
+    /// drop(second);
+    /// assert_eq!(*lock.read().await, (1, 3));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> RwLockWriteGuard<'a, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // If `f` panics, `guard` is dropped and the write lock is released.
+        let value: *mut U = f(unsafe { &mut *guard.value });
+        let guard = ManuallyDrop::new(guard);
+
+        RwLockWriteGuard {
+            lock: guard.lock,
+            value,
+        }
+    }
+
+    /// Attempts to make a new guard for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as `RwLockWriteGuard::try_map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{RwLock, RwLockWriteGuard};
+    ///
+    /// let lock = RwLock::new(vec![1, 2, 3]);
+    ///
+    /// let writer = RwLockWriteGuard::try_map(lock.write().await, |v| v.get_mut(5)).unwrap_err();
+    /// let mut first = RwLockWriteGuard::try_map(writer, |v| v.first_mut()).unwrap();
+    /// *first = 10;
+    ///
+    /// drop(first);
+    /// assert_eq!(*lock.read().await, [10, 2, 3]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<RwLockWriteGuard<'a, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let value: *mut U = match f(unsafe { &mut *guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+        let guard = ManuallyDrop::new(guard);
+
+        Ok(RwLockWriteGuard {
+            lock: guard.lock,
+            value,
+        })
+    }
 }
 
 impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLockWriteGuard<'_, T> {
@@ -1432,6 +2045,86 @@ impl<T: ?Sized> RwLockWriteGuardArc<T> {
             lock: Self::into_arc(guard),
         }
     }
+
+    /// Makes a new [`MappedRwLockWriteGuardArc`] for a component of the locked data.
+    ///
+    /// The write lock stays held until the returned guard is dropped.
+    ///
+    /// This is an associated function that needs to be used as `RwLockWriteGuardArc::map(...)`.
+    /// A method would interfere with methods of the same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{RwLock, RwLockWriteGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, 2)));
+    ///
+    /// let mut second = RwLockWriteGuardArc::map(lock.write_arc().await, |pair| &mut pair.1);
+    /// *second = 3;
+    /// assert!(lock.try_read().is_none());
+    ///
+    /// drop(second);
+    /// assert_eq!(*lock.read().await, (1, 3));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<U: ?Sized, F>(guard: Self, f: F) -> MappedRwLockWriteGuardArc<T, U>
+    where
+        F: FnOnce(&mut T) -> &mut U,
+    {
+        // If `f` panics, `guard` is dropped and the write lock is released.
+        let value: *mut U = f(unsafe { &mut *guard.lock.value.get() });
+
+        MappedRwLockWriteGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedRwLockWriteGuardArc`] for a component of the locked data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `RwLockWriteGuardArc::try_map(...)`. A method would interfere with methods of the same
+    /// name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{RwLock, RwLockWriteGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new(vec![1, 2, 3]));
+    ///
+    /// let writer = RwLockWriteGuardArc::try_map(lock.write_arc().await, |v| v.get_mut(5))
+    ///     .unwrap_err();
+    /// let mut first = RwLockWriteGuardArc::try_map(writer, |v| v.first_mut()).unwrap();
+    /// *first = 10;
+    ///
+    /// drop(first);
+    /// assert_eq!(*lock.read().await, [10, 2, 3]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<U: ?Sized, F>(guard: Self, f: F) -> Result<MappedRwLockWriteGuardArc<T, U>, Self>
+    where
+        F: FnOnce(&mut T) -> Option<&mut U>,
+    {
+        let value: *mut U = match f(unsafe { &mut *guard.lock.value.get() }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedRwLockWriteGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        })
+    }
 }
 
 impl<T: fmt::Debug + ?Sized> fmt::Debug for RwLockWriteGuardArc<T> {
@@ -1463,3 +2156,277 @@ impl<T: ?Sized> DerefMut for RwLockWriteGuardArc<T> {
         unsafe { &mut *self.lock.value.get() }
     }
 }
+
+/// An owned, reference-counted guard that releases the read lock when dropped, pointing to a
+/// component of the locked data.
+///
+/// Created by [`RwLockReadGuardArc::map`] and [`RwLockReadGuardArc::try_map`].
+#[clippy::has_significant_drop]
+pub struct MappedRwLockReadGuardArc<T: ?Sized, U: ?Sized> {
+    /// The lock being held. Keeps the original value alive.
+    lock: Arc<RwLock<T>>,
+
+    /// Pointer to the component of the locked data. Covariant in `U`.
+    value: *const U,
+}
+
+unsafe impl<T: Send + Sync + ?Sized, U: Sync + ?Sized> Send for MappedRwLockReadGuardArc<T, U> {}
+unsafe impl<T: Send + Sync + ?Sized, U: Sync + ?Sized> Sync for MappedRwLockReadGuardArc<T, U> {}
+
+impl<T: ?Sized, U: ?Sized> MappedRwLockReadGuardArc<T, U> {
+    /// Consumes the guard (without unlocking) and returns the underlying `Arc`.
+    #[inline]
+    fn into_arc(guard: Self) -> Arc<RwLock<T>> {
+        let guard = ManuallyDrop::new(guard);
+        // SAFETY: `guard` is not used after this
+        unsafe { ptr::read(&guard.lock) }
+    }
+
+    /// Makes a new [`MappedRwLockReadGuardArc`] for a component of the already mapped data.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `MappedRwLockReadGuardArc::map(...)`. A method would interfere with methods of the same
+    /// name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{MappedRwLockReadGuardArc, RwLock, RwLockReadGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, (2, 3))));
+    ///
+    /// let inner = RwLockReadGuardArc::map(lock.read_arc().await, |pair| &pair.1);
+    /// let third = MappedRwLockReadGuardArc::map(inner, |pair| &pair.1);
+    /// assert_eq!(*third, 3);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<V: ?Sized, F>(guard: Self, f: F) -> MappedRwLockReadGuardArc<T, V>
+    where
+        F: FnOnce(&U) -> &V,
+    {
+        // If `f` panics, `guard` is dropped and the read lock is released.
+        let value: *const V = f(unsafe { &*guard.value });
+
+        MappedRwLockReadGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedRwLockReadGuardArc`] for a component of the already mapped
+    /// data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `MappedRwLockReadGuardArc::try_map(...)`. A method would interfere with methods of the
+    /// same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{MappedRwLockReadGuardArc, RwLock, RwLockReadGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, vec![2])));
+    ///
+    /// let inner = RwLockReadGuardArc::map(lock.read_arc().await, |pair| &pair.1);
+    /// let inner = MappedRwLockReadGuardArc::try_map(inner, |v| v.get(1)).unwrap_err();
+    /// assert_eq!(*inner, [2]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<V: ?Sized, F>(guard: Self, f: F) -> Result<MappedRwLockReadGuardArc<T, V>, Self>
+    where
+        F: FnOnce(&U) -> Option<&V>,
+    {
+        let value: *const V = match f(unsafe { &*guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedRwLockReadGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        })
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedRwLockReadGuardArc<T, U> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: we are dropping a read guard.
+        unsafe {
+            self.lock.raw.read_unlock();
+        }
+    }
+}
+
+impl<T: ?Sized, U: fmt::Debug + ?Sized> fmt::Debug for MappedRwLockReadGuardArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: fmt::Display + ?Sized> fmt::Display for MappedRwLockReadGuardArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedRwLockReadGuardArc<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+/// An owned, reference-counted guard that releases the write lock when dropped, pointing to a
+/// component of the locked data.
+///
+/// Created by [`RwLockWriteGuardArc::map`] and [`RwLockWriteGuardArc::try_map`].
+#[clippy::has_significant_drop]
+pub struct MappedRwLockWriteGuardArc<T: ?Sized, U: ?Sized> {
+    /// The lock being held. Keeps the original value alive.
+    lock: Arc<RwLock<T>>,
+
+    /// Pointer to the component of the locked data. Invariant in `U`.
+    value: *mut U,
+}
+
+unsafe impl<T: Send + Sync + ?Sized, U: Send + ?Sized> Send for MappedRwLockWriteGuardArc<T, U> {}
+unsafe impl<T: Send + Sync + ?Sized, U: Sync + ?Sized> Sync for MappedRwLockWriteGuardArc<T, U> {}
+
+impl<T: ?Sized, U: ?Sized> MappedRwLockWriteGuardArc<T, U> {
+    /// Consumes the guard (without unlocking) and returns the underlying `Arc`.
+    #[inline]
+    fn into_arc(guard: Self) -> Arc<RwLock<T>> {
+        let guard = ManuallyDrop::new(guard);
+        // SAFETY: `guard` is not used after this
+        unsafe { ptr::read(&guard.lock) }
+    }
+
+    /// Makes a new [`MappedRwLockWriteGuardArc`] for a component of the already mapped data.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `MappedRwLockWriteGuardArc::map(...)`. A method would interfere with methods of the same
+    /// name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{MappedRwLockWriteGuardArc, RwLock, RwLockWriteGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, (2, 3))));
+    ///
+    /// let inner = RwLockWriteGuardArc::map(lock.write_arc().await, |pair| &mut pair.1);
+    /// let mut third = MappedRwLockWriteGuardArc::map(inner, |pair| &mut pair.1);
+    /// *third = 4;
+    ///
+    /// drop(third);
+    /// assert_eq!(*lock.read().await, (1, (2, 4)));
+    /// # })
+    /// ```
+    #[inline]
+    pub fn map<V: ?Sized, F>(guard: Self, f: F) -> MappedRwLockWriteGuardArc<T, V>
+    where
+        F: FnOnce(&mut U) -> &mut V,
+    {
+        // If `f` panics, `guard` is dropped and the write lock is released.
+        let value: *mut V = f(unsafe { &mut *guard.value });
+
+        MappedRwLockWriteGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        }
+    }
+
+    /// Attempts to make a new [`MappedRwLockWriteGuardArc`] for a component of the already
+    /// mapped data.
+    ///
+    /// If the closure returns [`None`], the original guard is returned back.
+    ///
+    /// This is an associated function that needs to be used as
+    /// `MappedRwLockWriteGuardArc::try_map(...)`. A method would interfere with methods of the
+    /// same name on the contents of the locked data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use std::sync::Arc;
+    /// use async_lock::{MappedRwLockWriteGuardArc, RwLock, RwLockWriteGuardArc};
+    ///
+    /// let lock = Arc::new(RwLock::new((1, vec![2])));
+    ///
+    /// let inner = RwLockWriteGuardArc::map(lock.write_arc().await, |pair| &mut pair.1);
+    /// let inner = MappedRwLockWriteGuardArc::try_map(inner, |v| v.get_mut(1)).unwrap_err();
+    /// assert_eq!(*inner, [2]);
+    /// # })
+    /// ```
+    #[inline]
+    pub fn try_map<V: ?Sized, F>(guard: Self, f: F) -> Result<MappedRwLockWriteGuardArc<T, V>, Self>
+    where
+        F: FnOnce(&mut U) -> Option<&mut V>,
+    {
+        let value: *mut V = match f(unsafe { &mut *guard.value }) {
+            Some(value) => value,
+            None => return Err(guard),
+        };
+
+        Ok(MappedRwLockWriteGuardArc {
+            lock: Self::into_arc(guard),
+            value,
+        })
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedRwLockWriteGuardArc<T, U> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: we are dropping a write lock.
+        unsafe {
+            self.lock.raw.write_unlock();
+        }
+    }
+}
+
+impl<T: ?Sized, U: fmt::Debug + ?Sized> fmt::Debug for MappedRwLockWriteGuardArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: ?Sized, U: fmt::Display + ?Sized> fmt::Display for MappedRwLockWriteGuardArc<T, U> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedRwLockWriteGuardArc<T, U> {
+    type Target = U;
+
+    #[inline]
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedRwLockWriteGuardArc<T, U> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
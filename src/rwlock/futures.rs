@@ -1,7 +1,8 @@
 use core::fmt;
+use core::future::Future;
 use core::mem::ManuallyDrop;
 use core::pin::Pin;
-use core::task::Poll;
+use core::task::{Context, Poll};
 
 use alloc::sync::Arc;
 
@@ -10,6 +11,7 @@ use super::{
     RwLock, RwLockReadGuard, RwLockReadGuardArc, RwLockUpgradableReadGuard,
     RwLockUpgradableReadGuardArc, RwLockWriteGuard, RwLockWriteGuardArc,
 };
+use crate::timer::{TimedOut, Timeout, Timer};
 
 use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
 
@@ -68,6 +70,39 @@ impl<'a, T: ?Sized> EventListenerFuture for ReadInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::read_timeout`].
+    pub struct ReadTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<Read<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T: ?Sized, Tm> ReadTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: Read<'x, T>, timer: Tm) -> Self {
+        ReadTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for ReadTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ReadTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for ReadTimeout<'a, T, Tm> {
+    type Output = Result<RwLockReadGuard<'a, T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLock::read_arc`].
     pub struct ReadArc<'a, T>(ReadArcInner<'a, T> => RwLockReadGuardArc<T>);
@@ -121,6 +156,39 @@ impl<'a, T> EventListenerFuture for ReadArcInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::read_arc_timeout`].
+    pub struct ReadArcTimeout<'a, T, Tm> {
+        #[pin]
+        inner: Timeout<ReadArc<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T, Tm> ReadArcTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: ReadArc<'x, T>, timer: Tm) -> Self {
+        ReadArcTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T, Tm> fmt::Debug for ReadArcTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ReadArcTimeout { .. }")
+    }
+}
+
+impl<'a, T, Tm: Timer> Future for ReadArcTimeout<'a, T, Tm> {
+    type Output = Result<RwLockReadGuardArc<T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLock::upgradable_read`].
     pub struct UpgradableRead<'a, T: ?Sized>(
@@ -179,6 +247,39 @@ impl<'a, T: ?Sized> EventListenerFuture for UpgradableReadInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::upgradable_read_timeout`].
+    pub struct UpgradableReadTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<UpgradableRead<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T: ?Sized, Tm> UpgradableReadTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: UpgradableRead<'x, T>, timer: Tm) -> Self {
+        UpgradableReadTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for UpgradableReadTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UpgradableReadTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for UpgradableReadTimeout<'a, T, Tm> {
+    type Output = Result<RwLockUpgradableReadGuard<'a, T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLock::upgradable_read_arc`].
     pub struct UpgradableReadArc<'a, T: ?Sized>(
@@ -233,6 +334,39 @@ impl<'a, T: ?Sized> EventListenerFuture for UpgradableReadArcInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::upgradable_read_arc_timeout`].
+    pub struct UpgradableReadArcTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<UpgradableReadArc<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T: ?Sized, Tm> UpgradableReadArcTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: UpgradableReadArc<'x, T>, timer: Tm) -> Self {
+        UpgradableReadArcTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for UpgradableReadArcTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UpgradableReadArcTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for UpgradableReadArcTimeout<'a, T, Tm> {
+    type Output = Result<RwLockUpgradableReadGuardArc<T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLock::write`].
     pub struct Write<'a, T: ?Sized>(WriteInner<'a, T> => RwLockWriteGuard<'a, T>);
@@ -288,6 +422,39 @@ impl<'a, T: ?Sized> EventListenerFuture for WriteInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::write_timeout`].
+    pub struct WriteTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<Write<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T: ?Sized, Tm> WriteTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: Write<'x, T>, timer: Tm) -> Self {
+        WriteTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for WriteTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WriteTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for WriteTimeout<'a, T, Tm> {
+    type Output = Result<RwLockWriteGuard<'a, T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLock::write_arc`].
     pub struct WriteArc<'a, T: ?Sized>(WriteArcInner<'a, T> => RwLockWriteGuardArc<T>);
@@ -341,6 +508,39 @@ impl<'a, T: ?Sized> EventListenerFuture for WriteArcInner<'a, T> {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// The future returned by [`RwLock::write_arc_timeout`].
+    pub struct WriteArcTimeout<'a, T: ?Sized, Tm> {
+        #[pin]
+        inner: Timeout<WriteArc<'a, T>, Tm>,
+    }
+}
+
+impl<'x, T: ?Sized, Tm> WriteArcTimeout<'x, T, Tm> {
+    #[inline]
+    pub(super) fn new(inner: WriteArc<'x, T>, timer: Tm) -> Self {
+        WriteArcTimeout {
+            inner: Timeout::new(inner, timer),
+        }
+    }
+}
+
+impl<T: ?Sized, Tm> fmt::Debug for WriteArcTimeout<'_, T, Tm> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WriteArcTimeout { .. }")
+    }
+}
+
+impl<'a, T: ?Sized, Tm: Timer> Future for WriteArcTimeout<'a, T, Tm> {
+    type Output = Result<RwLockWriteGuardArc<T>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
 easy_wrapper! {
     /// The future returned by [`RwLockUpgradableReadGuard::upgrade`].
     pub struct Upgrade<'a, T: ?Sized>(UpgradeInner<'a, T> => RwLockWriteGuard<'a, T>);
@@ -15,12 +15,16 @@ use core::task::Poll;
 use event_listener::{Event, EventListener};
 use event_listener_strategy::{EventListenerFuture, Strategy};
 
+use super::{PolicyKind, RwLockPolicy};
 use crate::futures::Lock;
 use crate::Mutex;
 
 const WRITER_BIT: usize = 1;
 const ONE_READER: usize = 2;
 
+/// Flipped in `blocked_readers` every time a phase-fair writer lets the blocked readers in.
+const PHASE_BIT: usize = !(usize::MAX >> 1);
+
 /// A "raw" RwLock that doesn't hold any data.
 pub(super) struct RawRwLock {
     /// Acquired by the writer.
@@ -35,21 +39,34 @@ pub(super) struct RawRwLock {
     /// Current state of the lock.
     ///
     /// The least significant bit (`WRITER_BIT`) is set to 1 when a writer is holding the lock or
-    /// trying to acquire it.
+    /// trying to acquire it. With the reader-preferring policy, it is only set once the writer
+    /// holds the lock.
     ///
     /// The upper bits contain the number of currently active readers. Each active reader
     /// increments the state by `ONE_READER`.
     state: AtomicUsize,
+
+    /// The readers blocked by a writer.
+    ///
+    /// Only tracked with the phase-fair policy, where the writer lets them in as it unlocks. The
+    /// lower bits contain the number of blocked readers, and `PHASE_BIT` tells them apart from
+    /// the readers blocked by the next writer.
+    blocked_readers: AtomicUsize,
+
+    /// Which of readers and writers go first.
+    policy: RwLockPolicy,
 }
 
 impl RawRwLock {
     #[inline]
-    pub(super) const fn new() -> Self {
+    pub(super) const fn new(policy: RwLockPolicy) -> Self {
         RawRwLock {
             mutex: Mutex::new(()),
             no_readers: Event::new(),
             no_writer: Event::new(),
             state: AtomicUsize::new(0),
+            blocked_readers: AtomicUsize::new(0),
+            policy,
         }
     }
 
@@ -65,7 +82,7 @@ impl RawRwLock {
             }
 
             // Make sure the number of readers doesn't overflow.
-            if state > core::isize::MAX as usize {
+            if state > isize::MAX as usize {
                 crate::abort();
             }
 
@@ -88,12 +105,12 @@ impl RawRwLock {
             lock: self,
             state: self.state.load(Ordering::Acquire),
             listener: None,
+            phase: None,
             _pin: PhantomPinned,
         }
     }
 
     /// Returns `true` iff an upgradable read lock was successfully acquired.
-
     pub(super) fn try_upgradable_read(&self) -> bool {
         // First try grabbing the mutex.
         let lock = if let Some(lock) = self.mutex.try_lock() {
@@ -107,7 +124,7 @@ impl RawRwLock {
         let mut state = self.state.load(Ordering::Acquire);
 
         // Make sure the number of readers doesn't overflow.
-        if state > core::isize::MAX as usize {
+        if state > isize::MAX as usize {
             crate::abort();
         }
 
@@ -126,7 +143,6 @@ impl RawRwLock {
     }
 
     #[inline]
-
     pub(super) fn upgradable_read(&self) -> RawUpgradableRead<'_> {
         RawUpgradableRead {
             lock: self,
@@ -135,7 +151,6 @@ impl RawRwLock {
     }
 
     /// Returs `true` iff a write lock was successfully acquired.
-
     pub(super) fn try_write(&self) -> bool {
         // First try grabbing the mutex.
         let lock = if let Some(lock) = self.mutex.try_lock() {
@@ -158,8 +173,67 @@ impl RawRwLock {
         }
     }
 
-    #[inline]
+    /// Counts a reader as blocked by the current writer, returning its phase.
+    fn block_reader(&self) -> usize {
+        let mut blocked = self.blocked_readers.load(Ordering::SeqCst);
+
+        loop {
+            // Make sure the number of blocked readers doesn't overflow, as they may all be let in.
+            if blocked & !PHASE_BIT > isize::MAX as usize / ONE_READER {
+                crate::abort();
+            }
+
+            match self.blocked_readers.compare_exchange(
+                blocked,
+                blocked + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return blocked & PHASE_BIT,
+                Err(b) => blocked = b,
+            }
+        }
+    }
+
+    /// Stops counting a blocked reader, returning `true` if the writer has let it in already.
+    ///
+    /// In that case the reader holds a read lock.
+    fn unblock_reader(&self, phase: usize) -> bool {
+        let mut blocked = self.blocked_readers.load(Ordering::SeqCst);
+
+        loop {
+            // The phase can't flip twice while this reader holds the read lock it was given.
+            if blocked & PHASE_BIT != phase {
+                return true;
+            }
 
+            match self.blocked_readers.compare_exchange(
+                blocked,
+                blocked - 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => return false,
+                Err(b) => blocked = b,
+            }
+        }
+    }
+
+    /// Returns `true` iff the write lock was acquired, while holding the writer mutex.
+    fn try_write_locked(&self) -> bool {
+        match self.policy.0 {
+            // Only enter once there are no readers, without keeping new ones out.
+            PolicyKind::ReadPreferring => self
+                .state
+                .compare_exchange(0, WRITER_BIT, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok(),
+
+            // Set `WRITER_BIT` to keep new readers out, and wait for the current ones to finish.
+            _ => self.state.fetch_or(WRITER_BIT, Ordering::SeqCst) & !WRITER_BIT == 0,
+        }
+    }
+
+    #[inline]
     pub(super) fn write(&self) -> RawWrite<'_> {
         RawWrite {
             lock: self,
@@ -176,7 +250,6 @@ impl RawRwLock {
     ///
     /// Caller must hold an upgradable read lock.
     /// This will attempt to upgrade it to a write lock.
-
     pub(super) unsafe fn try_upgrade(&self) -> bool {
         self.state
             .compare_exchange(ONE_READER, WRITER_BIT, Ordering::AcqRel, Ordering::Acquire)
@@ -187,11 +260,13 @@ impl RawRwLock {
     ///
     /// Caller must hold an upgradable read lock.
     /// This will upgrade it to a write lock.
-
     pub(super) unsafe fn upgrade(&self) -> RawUpgrade<'_> {
-        // Set `WRITER_BIT` and decrement the number of readers at the same time.
-        self.state
-            .fetch_sub(ONE_READER - WRITER_BIT, Ordering::SeqCst);
+        // Set `WRITER_BIT` and decrement the number of readers at the same time. Reader-preferring
+        // upgrades do this once they are the only reader left instead.
+        if self.policy.0 != PolicyKind::ReadPreferring {
+            self.state
+                .fetch_sub(ONE_READER - WRITER_BIT, Ordering::SeqCst);
+        }
 
         RawUpgrade {
             lock: Some(self),
@@ -205,7 +280,6 @@ impl RawRwLock {
     /// Caller must hold an upgradable read lock.
     /// This will downgrade it to a stadard read lock.
     #[inline]
-
     pub(super) unsafe fn downgrade_upgradable_read(&self) {
         self.mutex.unlock_unchecked();
     }
@@ -214,7 +288,6 @@ impl RawRwLock {
     ///
     /// Caller must hold a write lock.
     /// This will downgrade it to a read lock.
-
     pub(super) unsafe fn downgrade_write(&self) {
         // Atomically downgrade state.
         self.state
@@ -231,7 +304,6 @@ impl RawRwLock {
     ///
     /// Caller must hold a write lock.
     /// This will downgrade it to an upgradable read lock.
-
     pub(super) unsafe fn downgrade_to_upgradable(&self) {
         // Atomically downgrade state.
         self.state
@@ -242,11 +314,15 @@ impl RawRwLock {
     ///
     /// Caller must hold a read lock .
     /// This will unlock that lock.
-
     pub(super) unsafe fn read_unlock(&self) {
         // Decrement the number of readers.
-        if self.state.fetch_sub(ONE_READER, Ordering::SeqCst) & !WRITER_BIT == ONE_READER {
-            // If this was the last reader, trigger the "no readers" event.
+        let readers = self.state.fetch_sub(ONE_READER, Ordering::SeqCst) & !WRITER_BIT;
+
+        // If this was the last reader, trigger the "no readers" event. Reader-preferring upgrades
+        // keep counting as a reader, so they need it when a single reader is left.
+        if readers == ONE_READER
+            || (readers == 2 * ONE_READER && self.policy.0 == PolicyKind::ReadPreferring)
+        {
             self.no_readers.notify(1);
         }
     }
@@ -255,7 +331,6 @@ impl RawRwLock {
     ///
     /// Caller must hold an upgradable read lock.
     /// This will unlock that lock.
-
     pub(super) unsafe fn upgradable_read_unlock(&self) {
         // Decrement the number of readers.
         if self.state.fetch_sub(ONE_READER, Ordering::SeqCst) & !WRITER_BIT == ONE_READER {
@@ -271,10 +346,24 @@ impl RawRwLock {
     ///
     /// Caller must hold a write lock.
     /// This will unlock that lock.
-
     pub(super) unsafe fn write_unlock(&self) {
-        // Unset `WRITER_BIT`.
-        self.state.fetch_and(!WRITER_BIT, Ordering::SeqCst);
+        // Phase-fair writers let in the readers they blocked, and only those, before the next
+        // writer can get the lock. Flipping the phase tells them they hold a read lock.
+        let readers = if self.policy.0 == PolicyKind::PhaseFair {
+            let blocked = self.blocked_readers.fetch_xor(PHASE_BIT, Ordering::SeqCst);
+            self.blocked_readers
+                .fetch_sub(blocked & !PHASE_BIT, Ordering::SeqCst);
+            blocked & !PHASE_BIT
+        } else {
+            0
+        };
+
+        // Unset `WRITER_BIT` and count the readers let in. They may release their read locks
+        // before this, so the state can wrap around in the meantime, while it still looks locked.
+        self.state.fetch_add(
+            (readers * ONE_READER).wrapping_sub(WRITER_BIT),
+            Ordering::SeqCst,
+        );
         // Trigger the "no writer" event.
         self.no_writer.notify(1);
 
@@ -282,11 +371,25 @@ impl RawRwLock {
         // SAFETY: `RwLockWriteGuard` always holds a lock on writer mutex.
         self.mutex.unlock_unchecked();
     }
+
+    /// # Safety
+    ///
+    /// Caller must hold the writer mutex while waiting for the readers to finish.
+    /// This will give up on acquiring the write lock.
+    unsafe fn write_cancel(&self) {
+        if self.policy.0 == PolicyKind::ReadPreferring {
+            // `WRITER_BIT` is only set once the write lock is acquired, so only the writer mutex
+            // is held.
+            self.no_writer.notify(1);
+            self.mutex.unlock_unchecked();
+        } else {
+            self.write_unlock();
+        }
+    }
 }
 
 pin_project_lite::pin_project! {
     /// The future returned by [`RawRwLock::read`].
-
     pub(super) struct RawRead<'a> {
         // The lock that is being acquired.
         pub(super) lock: &'a RawRwLock,
@@ -297,10 +400,26 @@ pin_project_lite::pin_project! {
         // The listener for the "no writers" event.
         listener: Option<EventListener>,
 
+        // The phase this reader is counted in `blocked_readers` for, if any.
+        phase: Option<usize>,
+
         // Making this type `!Unpin` enables future optimizations.
         #[pin]
         _pin: PhantomPinned
     }
+
+    impl PinnedDrop for RawRead<'_> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+
+            if let Some(phase) = *this.phase {
+                if this.lock.unblock_reader(phase) {
+                    // SAFETY: the writer has let this reader in.
+                    unsafe { this.lock.read_unlock() };
+                }
+            }
+        }
+    }
 }
 
 impl<'a> EventListenerFuture for RawRead<'a> {
@@ -314,9 +433,17 @@ impl<'a> EventListenerFuture for RawRead<'a> {
         let this = self.project();
 
         loop {
+            // A phase-fair writer may have let this reader in as it unlocked.
+            if let Some(phase) = *this.phase {
+                if this.lock.blocked_readers.load(Ordering::SeqCst) & PHASE_BIT != phase {
+                    *this.phase = None;
+                    return Poll::Ready(());
+                }
+            }
+
             if *this.state & WRITER_BIT == 0 {
                 // Make sure the number of readers doesn't overflow.
-                if *this.state > core::isize::MAX as usize {
+                if *this.state > isize::MAX as usize {
                     crate::abort();
                 }
 
@@ -328,7 +455,17 @@ impl<'a> EventListenerFuture for RawRead<'a> {
                     Ordering::AcqRel,
                     Ordering::Acquire,
                 ) {
-                    Ok(_) => return Poll::Ready(()),
+                    Ok(_) => {
+                        if let Some(phase) = this.phase.take() {
+                            if this.lock.unblock_reader(phase) {
+                                // The writer has let this reader in already, so it now holds two
+                                // read locks.
+                                this.lock.state.fetch_sub(ONE_READER, Ordering::SeqCst);
+                            }
+                        }
+
+                        return Poll::Ready(());
+                    }
                     Err(s) => *this.state = s,
                 }
             } else {
@@ -336,6 +473,11 @@ impl<'a> EventListenerFuture for RawRead<'a> {
                 let load_ordering = if this.listener.is_none() {
                     *this.listener = Some(this.lock.no_writer.listen());
 
+                    // Let phase-fair writers know that this reader is waiting for its turn.
+                    if this.phase.is_none() && this.lock.policy.0 == PolicyKind::PhaseFair {
+                        *this.phase = Some(this.lock.block_reader());
+                    }
+
                     // Make sure there really is no writer.
                     Ordering::SeqCst
                 } else {
@@ -386,7 +528,7 @@ impl<'a> EventListenerFuture for RawUpgradableRead<'a> {
         let mut state = this.lock.state.load(Ordering::Acquire);
 
         // Make sure the number of readers doesn't overflow.
-        if state > core::isize::MAX as usize {
+        if state > isize::MAX as usize {
             crate::abort();
         }
 
@@ -409,7 +551,6 @@ impl<'a> EventListenerFuture for RawUpgradableRead<'a> {
 
 pin_project_lite::pin_project! {
     /// The future returned by [`RawRwLock::write`].
-
     pub(super) struct RawWrite<'a> {
         // The lock that is being acquired.
         pub(super) lock: &'a RawRwLock,
@@ -427,9 +568,9 @@ pin_project_lite::pin_project! {
             let this = this.project();
 
             if matches!(this.state.project(), WriteStateProj::WaitingReaders) {
-                // Safety: we hold a write lock, more or less.
+                // Safety: we hold the writer mutex and are waiting for the readers.
                 unsafe {
-                    this.lock.write_unlock();
+                    this.lock.write_cancel();
                 }
             }
         }
@@ -468,29 +609,13 @@ impl<'a> EventListenerFuture for RawWrite<'a> {
                     let mutex_guard = ready!(lock.poll_with_strategy(strategy, cx));
                     forget(mutex_guard);
 
-                    // Set `WRITER_BIT` and create a guard that unsets it in case this future is canceled.
-                    let new_state = this.lock.state.fetch_or(WRITER_BIT, Ordering::SeqCst);
-
-                    // If we just acquired the lock, return.
-                    if new_state == WRITER_BIT {
-                        this.state.as_mut().set(WriteState::Acquired);
-                        return Poll::Ready(());
-                    }
-
-                    // Start waiting for the readers to finish.
-                    *this.no_readers = Some(this.lock.no_readers.listen());
+                    // From now on, dropping this future unsets `WRITER_BIT` and releases the mutex.
                     this.state.as_mut().set(WriteState::WaitingReaders);
                 }
 
                 WriteStateProj::WaitingReaders => {
-                    let load_ordering = if this.no_readers.is_some() {
-                        Ordering::Acquire
-                    } else {
-                        Ordering::SeqCst
-                    };
-
                     // Check the state again.
-                    if this.lock.state.load(load_ordering) == WRITER_BIT {
+                    if this.lock.try_write_locked() {
                         // We are the only ones holding the lock, return `Ready`.
                         this.state.as_mut().set(WriteState::Acquired);
                         return Poll::Ready(());
@@ -513,7 +638,6 @@ impl<'a> EventListenerFuture for RawWrite<'a> {
 
 pin_project_lite::pin_project! {
     /// The future returned by [`RawRwLock::upgrade`].
-
     pub(super) struct RawUpgrade<'a> {
         lock: Option<&'a RawRwLock>,
 
@@ -532,7 +656,12 @@ pin_project_lite::pin_project! {
                 // SAFETY: we are dropping the future that would give us a write lock,
                 // so we don't need said lock anymore.
                 unsafe {
-                    lock.write_unlock();
+                    if lock.policy.0 == PolicyKind::ReadPreferring {
+                        // We are still holding the upgradable read lock.
+                        lock.upgradable_read_unlock();
+                    } else {
+                        lock.write_unlock();
+                    }
                 }
             }
         }
@@ -559,8 +688,14 @@ impl<'a> EventListenerFuture for RawUpgrade<'a> {
             };
 
             // See if the number of readers is zero.
-            let state = lock.state.load(load_ordering);
-            if state == WRITER_BIT {
+            let upgraded = if lock.policy.0 == PolicyKind::ReadPreferring {
+                lock.state
+                    .compare_exchange(ONE_READER, WRITER_BIT, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+            } else {
+                lock.state.load(load_ordering) == WRITER_BIT
+            };
+            if upgraded {
                 break;
             }
 
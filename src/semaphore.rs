@@ -1,20 +1,59 @@
 use core::fmt;
+use core::future::Future;
 use core::marker::PhantomPinned;
 use core::mem;
 use core::pin::Pin;
-use core::sync::atomic::{AtomicUsize, Ordering};
-use core::task::Poll;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::task::{Context, Poll};
 
 use alloc::sync::Arc;
 
-use event_listener::{Event, EventListener};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::time::{Duration, Instant};
+
+use event_listener::{Event, EventListener, Listener};
 use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use crate::deadline::BlockingDeadline;
+use crate::futures::LockArc;
+use crate::timer::{TimedOut, Timeout, Timer};
+use crate::{Fairness, Mutex, MutexGuardArc};
+
 /// A counter for limiting the number of concurrent operations.
 #[derive(Debug)]
 pub struct Semaphore {
+    /// The number of available permits.
     count: AtomicUsize,
+
+    /// Acquire operations waiting for a single permit.
     event: Event,
+
+    /// Acquire operations waiting for more than one permit.
+    ///
+    /// One of them is woken up when permits are released. If it can't use them, it passes the
+    /// wakeup on to the next one, until it has gone around all of them.
+    event_many: Event,
+
+    /// The number of times permits were released, wrapping around.
+    releases: AtomicUsize,
+
+    /// Set once the semaphore is closed and stops handing out permits.
+    closed: AtomicBool,
+
+    /// The wait queue of a fair semaphore.
+    queue: Option<FairQueue>,
+}
+
+/// The wait queue of a fair semaphore.
+#[derive(Debug)]
+struct FairQueue {
+    /// The acquire operation holding this FIFO lock is at the head of the queue and takes the
+    /// next released permits.
+    lock: Arc<Mutex<()>>,
+
+    /// The number of acquire operations in the queue, including the one at its head.
+    len: Arc<AtomicUsize>,
 }
 
 impl Semaphore {
@@ -31,6 +70,51 @@ impl Semaphore {
         Semaphore {
             count: AtomicUsize::new(n),
             event: Event::new(),
+            event_many: Event::new(),
+            releases: AtomicUsize::new(0),
+            closed: AtomicBool::new(false),
+            queue: None,
+        }
+    }
+
+    /// Creates a new fair semaphore with a limit of `n` concurrent operations.
+    ///
+    /// Acquire operations that have to wait are served in strict FIFO order: released permits
+    /// go to the operation at the head of the queue, and nobody can take permits ahead of it,
+    /// even if there are enough for a smaller request. While anyone is waiting,
+    /// [`try_acquire`][Semaphore::try_acquire] always fails.
+    ///
+    /// This prevents operations waiting for many permits from being starved by a stream of
+    /// smaller ones, at the cost of some throughput.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new_fair(2);
+    /// let guard = s.acquire().await;
+    ///
+    /// // Waits in line for both permits.
+    /// let many = s.acquire_many(2);
+    /// futures_lite::pin!(many);
+    /// assert!(futures_lite::future::poll_once(many.as_mut()).await.is_none());
+    ///
+    /// // The free permit is kept for the queued operation.
+    /// assert!(s.try_acquire().is_none());
+    ///
+    /// drop(guard);
+    /// many.await;
+    /// # });
+    /// ```
+    pub fn new_fair(n: usize) -> Semaphore {
+        Semaphore {
+            queue: Some(FairQueue {
+                lock: Arc::new(Mutex::with_fairness((), Fairness::fifo())),
+                len: Arc::new(AtomicUsize::new(0)),
+            }),
+            ..Semaphore::new(n)
         }
     }
 
@@ -53,22 +137,39 @@ impl Semaphore {
     /// drop(g2);
     /// assert!(s.try_acquire().is_some());
     /// ```
+    #[inline]
     pub fn try_acquire(&self) -> Option<SemaphoreGuard<'_>> {
-        let mut count = self.count.load(Ordering::Acquire);
-        loop {
-            if count == 0 {
-                return None;
-            }
+        self.try_acquire_many(1)
+    }
 
-            match self.count.compare_exchange_weak(
-                count,
-                count - 1,
-                Ordering::AcqRel,
-                Ordering::Acquire,
-            ) {
-                Ok(_) => return Some(SemaphoreGuard(self)),
-                Err(c) => count = c,
-            }
+    /// Attempts to get `n` permits at once.
+    ///
+    /// If the permits could not be acquired at this time, then [`None`] is returned. Otherwise, a
+    /// guard is returned that releases all `n` permits when dropped.
+    ///
+    /// Asking for zero permits is allowed and returns a guard holding none, unless the semaphore
+    /// is closed or, if it is fair, other operations are waiting in line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(3);
+    ///
+    /// let g1 = s.try_acquire_many(2).unwrap();
+    /// assert!(s.try_acquire_many(2).is_none());
+    /// drop(g1);
+    /// assert!(s.try_acquire_many(3).is_some());
+    /// ```
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphoreGuard<'_>> {
+        if self.try_take_queued(n, false) {
+            Some(SemaphoreGuard {
+                semaphore: self,
+                permits: n,
+            })
+        } else {
+            None
         }
     }
 
@@ -76,6 +177,11 @@ impl Semaphore {
     ///
     /// Returns a guard that releases the permit when dropped.
     ///
+    /// # Closing
+    ///
+    /// This method never completes once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_checked`][Semaphore::acquire_checked] instead to find out about it.
+    ///
     /// # Examples
     ///
     /// ```
@@ -86,10 +192,44 @@ impl Semaphore {
     /// let guard = s.acquire().await;
     /// # });
     /// ```
+    #[inline]
     pub fn acquire(&self) -> Acquire<'_> {
+        self.acquire_many(1)
+    }
+
+    /// Waits for `n` permits at once.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped. The permits are acquired all at
+    /// once, so two tasks each waiting for more permits than remain can't deadlock each other
+    /// while holding half of them.
+    ///
+    /// Waiting for more permits than the semaphore will ever have never completes.
+    ///
+    /// Waiting for zero permits is allowed and completes with a guard holding none, as soon as the
+    /// operations waiting in line ahead of it on a fair semaphore are served.
+    ///
+    /// # Closing
+    ///
+    /// This method never completes once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_many_checked`][Semaphore::acquire_many_checked] instead to find out about it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(5);
+    /// let guard = s.acquire_many(3).await;
+    /// assert!(s.try_acquire_many(3).is_none());
+    /// # });
+    /// ```
+    pub fn acquire_many(&self, n: usize) -> Acquire<'_> {
         Acquire::_new(AcquireInner {
             semaphore: self,
-            listener: None,
+            permits: n,
+            queue: Queue::Idle,
+            waiter: Waiter::default(),
             _pin: PhantomPinned,
         })
     }
@@ -98,6 +238,11 @@ impl Semaphore {
     ///
     /// Returns a guard that releases the permit when dropped.
     ///
+    /// # Closing
+    ///
+    /// This method blocks forever once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_checked_blocking`][Semaphore::acquire_checked_blocking] instead to find out about it.
+    ///
     /// # Blocking
     ///
     /// Rather than using asynchronous waiting, like the [`acquire`][Semaphore::acquire] method,
@@ -121,6 +266,137 @@ impl Semaphore {
         self.acquire().wait()
     }
 
+    /// Waits for `n` permits at once.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped.
+    ///
+    /// # Closing
+    ///
+    /// This method blocks forever once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_many_checked_blocking`][Semaphore::acquire_many_checked_blocking] instead to find out about it.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`acquire_many`][Semaphore::acquire_many]
+    /// method, this method will block the current thread until the permits are acquired.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(5);
+    /// let guard = s.acquire_many_blocking(3);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_many_blocking(&self, n: usize) -> SemaphoreGuard<'_> {
+        self.acquire_many(n).wait()
+    }
+
+    /// Waits for a permit for a concurrent operation, giving up after `timeout`.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`None`] if no permit became
+    /// available in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`acquire`][Semaphore::acquire] method,
+    /// this method will block the current thread until the permit is acquired or the timeout
+    /// expires.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    /// use std::time::Duration;
+    ///
+    /// let s = Semaphore::new(1);
+    /// let guard = s.acquire_blocking_timeout(Duration::from_millis(10)).unwrap();
+    /// assert!(s.acquire_blocking_timeout(Duration::from_millis(10)).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_blocking_timeout(&self, timeout: Duration) -> Option<SemaphoreGuard<'_>> {
+        match Instant::now().checked_add(timeout) {
+            Some(deadline) => self.acquire_blocking_deadline(deadline),
+            None => Some(self.acquire_blocking()),
+        }
+    }
+
+    /// Waits for a permit for a concurrent operation, giving up at `deadline`.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`None`] if no permit became
+    /// available in time.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`acquire`][Semaphore::acquire] method,
+    /// this method will block the current thread until the permit is acquired or the deadline
+    /// is reached.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    /// use std::time::{Duration, Instant};
+    ///
+    /// let s = Semaphore::new(1);
+    /// let deadline = Instant::now() + Duration::from_millis(10);
+    /// let guard = s.acquire_blocking_deadline(deadline).unwrap();
+    /// assert!(s.acquire_blocking_deadline(deadline).is_none());
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn acquire_blocking_deadline(&self, deadline: Instant) -> Option<SemaphoreGuard<'_>> {
+        let acquire = self.acquire();
+        pin!(acquire);
+
+        match acquire.poll_with_strategy(&mut BlockingDeadline::new(deadline), &mut ()) {
+            Poll::Ready(guard) => Some(guard),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Waits for a permit for a concurrent operation, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`TimedOut`] if the timer
+    /// expired first. See [`Timer`] for the timers that can be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::Semaphore;
+    /// use std::time::Duration;
+    ///
+    /// let s = Semaphore::new(1);
+    /// let guard = s.acquire().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(s.acquire_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn acquire_timeout<Tm: Timer>(&self, timer: Tm) -> AcquireTimeout<'_, Tm> {
+        AcquireTimeout {
+            inner: Timeout::new(self.acquire(), timer),
+        }
+    }
+
     /// Attempts to get an owned permit for a concurrent operation.
     ///
     /// If the permit could not be acquired at this time, then [`None`] is returned. Otherwise, an
@@ -141,22 +417,37 @@ impl Semaphore {
     /// drop(g2);
     /// assert!(s.try_acquire_arc().is_some());
     /// ```
+    #[inline]
     pub fn try_acquire_arc(self: &Arc<Self>) -> Option<SemaphoreGuardArc> {
-        let mut count = self.count.load(Ordering::Acquire);
-        loop {
-            if count == 0 {
-                return None;
-            }
+        self.try_acquire_many_arc(1)
+    }
 
-            match self.count.compare_exchange_weak(
-                count,
-                count - 1,
-                Ordering::AcqRel,
-                Ordering::Acquire,
-            ) {
-                Ok(_) => return Some(SemaphoreGuardArc(Some(self.clone()))),
-                Err(c) => count = c,
-            }
+    /// Attempts to get `n` owned permits at once.
+    ///
+    /// If the permits could not be acquired at this time, then [`None`] is returned. Otherwise,
+    /// an owned guard is returned that releases all `n` permits when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(3));
+    ///
+    /// let g1 = s.try_acquire_many_arc(2).unwrap();
+    /// assert!(s.try_acquire_many_arc(2).is_none());
+    /// drop(g1);
+    /// assert!(s.try_acquire_many_arc(3).is_some());
+    /// ```
+    pub fn try_acquire_many_arc(self: &Arc<Self>, n: usize) -> Option<SemaphoreGuardArc> {
+        if self.try_take_queued(n, false) {
+            Some(SemaphoreGuardArc {
+                semaphore: Some(self.clone()),
+                permits: n,
+            })
+        } else {
+            None
         }
     }
 
@@ -164,6 +455,11 @@ impl Semaphore {
     ///
     /// Returns a guard that releases the permit when dropped.
     ///
+    /// # Closing
+    ///
+    /// This method never completes once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_arc_checked`][Semaphore::acquire_arc_checked] instead to find out about it.
+    ///
     /// # Examples
     ///
     /// ```
@@ -175,10 +471,72 @@ impl Semaphore {
     /// let guard = s.acquire_arc().await;
     /// # });
     /// ```
+    #[inline]
     pub fn acquire_arc(self: &Arc<Self>) -> AcquireArc {
+        self.acquire_many_arc(1)
+    }
+
+    /// Waits for an owned permit for a concurrent operation, giving up once `timer` expires.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`TimedOut`] if the timer
+    /// expired first. See [`Timer`] for the timers that can be used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+    /// # futures_lite::future::block_on(async {
+    /// use async_io::Timer;
+    /// use async_lock::Semaphore;
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    ///
+    /// let s = Arc::new(Semaphore::new(1));
+    /// let guard = s.acquire().await;
+    ///
+    /// let timer = Timer::after(Duration::from_millis(10));
+    /// assert!(s.acquire_arc_timeout(timer).await.is_err());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn acquire_arc_timeout<Tm: Timer>(self: &Arc<Self>, timer: Tm) -> AcquireArcTimeout<Tm> {
+        AcquireArcTimeout {
+            inner: Timeout::new(self.acquire_arc(), timer),
+        }
+    }
+
+    /// Waits for `n` owned permits at once.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped.
+    ///
+    /// Waiting for more permits than the semaphore will ever have never completes.
+    ///
+    /// Waiting for zero permits is allowed and completes with a guard holding none, as soon as the
+    /// operations waiting in line ahead of it on a fair semaphore are served.
+    ///
+    /// # Closing
+    ///
+    /// This method never completes once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_many_arc_checked`][Semaphore::acquire_many_arc_checked] instead to find out about it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Semaphore;
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(5));
+    /// let guard = s.acquire_many_arc(3).await;
+    /// assert!(s.try_acquire_many_arc(3).is_none());
+    /// # });
+    /// ```
+    pub fn acquire_many_arc(self: &Arc<Self>, n: usize) -> AcquireArc {
         AcquireArc::_new(AcquireArcInner {
             semaphore: self.clone(),
-            listener: None,
+            permits: n,
+            queue: Queue::Idle,
+            waiter: Waiter::default(),
             _pin: PhantomPinned,
         })
     }
@@ -187,6 +545,11 @@ impl Semaphore {
     ///
     /// Returns a guard that releases the permit when dropped.
     ///
+    /// # Closing
+    ///
+    /// This method blocks forever once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_arc_checked_blocking`][Semaphore::acquire_arc_checked_blocking] instead to find out about it.
+    ///
     /// # Blocking
     ///
     /// Rather than using asynchronous waiting, like the [`acquire_arc`][Semaphore::acquire_arc] method,
@@ -211,33 +574,715 @@ impl Semaphore {
         self.acquire_arc().wait()
     }
 
-    /// Adds `n` additional permits to the semaphore.
+    /// Waits for `n` owned permits at once.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped.
+    ///
+    /// # Closing
+    ///
+    /// This method blocks forever once the semaphore is [closed][Semaphore::close]. Use
+    /// [`acquire_many_arc_checked_blocking`][Semaphore::acquire_many_arc_checked_blocking] instead to find out about it.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`acquire_many_arc`][Semaphore::acquire_many_arc] method, this method will block the
+    /// current thread until the permits are acquired.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::sync::Arc;
     /// use async_lock::Semaphore;
     ///
-    /// # futures_lite::future::block_on(async {
-    /// let s = Semaphore::new(1);
+    /// let s = Arc::new(Semaphore::new(5));
+    /// let guard = s.acquire_many_arc_blocking(3);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_many_arc_blocking(self: &Arc<Self>, n: usize) -> SemaphoreGuardArc {
+        self.acquire_many_arc(n).wait()
+    }
+
+    /// Closes the semaphore.
     ///
-    /// let _guard = s.acquire().await;
-    /// assert!(s.try_acquire().is_none());
+    /// A closed semaphore doesn't hand out permits anymore. Every pending
+    /// [`acquire_checked`][Semaphore::acquire_checked] operation is woken up and fails with
+    /// [`AcquireError::Closed`], and so do later ones. Other acquire operations never complete,
+    /// and [`try_acquire`][Semaphore::try_acquire] returns [`None`].
     ///
-    /// s.add_permits(2);
+    /// Permits that are already held can still be used, and are returned to the closed
+    /// semaphore when their guards are dropped.
     ///
-    /// let _guard = s.acquire().await;
-    /// let _guard = s.acquire().await;
+    /// Returns `true` if this call closed the semaphore and it was not closed already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{AcquireError, Semaphore};
+    ///
+    /// let s = Semaphore::new(2);
+    /// let guard = s.acquire().await;
+    ///
+    /// assert!(s.close());
+    /// assert!(!s.close());
+    /// assert!(s.try_acquire().is_none());
+    /// assert_eq!(s.acquire_checked().await.unwrap_err(), AcquireError::Closed);
+    /// # });
+    /// ```
+    pub fn close(&self) -> bool {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+
+        // Wake everyone up so that checked acquire operations can fail.
+        self.event.notify(usize::MAX);
+        self.event_many.notify(usize::MAX);
+        true
+    }
+
+    /// Returns `true` if the semaphore is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(2);
+    /// assert!(!s.is_closed());
+    ///
+    /// s.close();
+    /// assert!(s.is_closed());
+    /// ```
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    /// Waits for a permit for a concurrent operation, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`AcquireError::Closed`] if the
+    /// semaphore is closed before a permit is acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{AcquireError, Semaphore};
+    ///
+    /// let s = Semaphore::new(1);
+    /// let guard = s.acquire_checked().await.unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_checked().await.unwrap_err(), AcquireError::Closed);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn acquire_checked(&self) -> AcquireChecked<'_> {
+        self.acquire_many_checked(1)
+    }
+
+    /// Waits for `n` permits at once, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped, or [`AcquireError::Closed`] if
+    /// the semaphore is closed before the permits are acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{AcquireError, Semaphore};
+    ///
+    /// let s = Semaphore::new(5);
+    /// let guard = s.acquire_many_checked(3).await.unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_many_checked(2).await.unwrap_err(), AcquireError::Closed);
+    /// # });
+    /// ```
+    pub fn acquire_many_checked(&self, n: usize) -> AcquireChecked<'_> {
+        AcquireChecked::_new(AcquireCheckedInner {
+            semaphore: self,
+            permits: n,
+            queue: Queue::Idle,
+            waiter: Waiter::default(),
+            _pin: PhantomPinned,
+        })
+    }
+
+    /// Waits for a permit for a concurrent operation, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`AcquireError::Closed`] if the
+    /// semaphore is closed before a permit is acquired.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`acquire_checked`][Semaphore::acquire_checked] method, this method will block the
+    /// current thread until the permit is acquired or the semaphore is closed.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{AcquireError, Semaphore};
+    ///
+    /// let s = Semaphore::new(1);
+    /// let guard = s.acquire_checked_blocking().unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_checked_blocking().unwrap_err(), AcquireError::Closed);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_checked_blocking(&self) -> Result<SemaphoreGuard<'_>, AcquireError> {
+        self.acquire_checked().wait()
+    }
+
+    /// Waits for `n` permits at once, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped, or [`AcquireError::Closed`] if
+    /// the semaphore is closed before the permits are acquired.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`acquire_many_checked`][Semaphore::acquire_many_checked] method, this method will block
+    /// the current thread until the permits are acquired or the semaphore is closed.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{AcquireError, Semaphore};
+    ///
+    /// let s = Semaphore::new(5);
+    /// let guard = s.acquire_many_checked_blocking(3).unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_many_checked_blocking(2).unwrap_err(), AcquireError::Closed);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_many_checked_blocking(
+        &self,
+        n: usize,
+    ) -> Result<SemaphoreGuard<'_>, AcquireError> {
+        self.acquire_many_checked(n).wait()
+    }
+
+    /// Waits for an owned permit for a concurrent operation, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`AcquireError::Closed`] if the
+    /// semaphore is closed before a permit is acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{AcquireError, Semaphore};
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(1));
+    /// let guard = s.acquire_arc_checked().await.unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_arc_checked().await.unwrap_err(), AcquireError::Closed);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn acquire_arc_checked(self: &Arc<Self>) -> AcquireArcChecked {
+        self.acquire_many_arc_checked(1)
+    }
+
+    /// Waits for `n` owned permits at once, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped, or [`AcquireError::Closed`] if
+    /// the semaphore is closed before the permits are acquired.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::{AcquireError, Semaphore};
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(5));
+    /// let guard = s.acquire_many_arc_checked(3).await.unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_many_arc_checked(2).await.unwrap_err(), AcquireError::Closed);
+    /// # });
+    /// ```
+    pub fn acquire_many_arc_checked(self: &Arc<Self>, n: usize) -> AcquireArcChecked {
+        AcquireArcChecked::_new(AcquireArcCheckedInner {
+            semaphore: self.clone(),
+            permits: n,
+            queue: Queue::Idle,
+            waiter: Waiter::default(),
+            _pin: PhantomPinned,
+        })
+    }
+
+    /// Waits for an owned permit for a concurrent operation, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases the permit when dropped, or [`AcquireError::Closed`] if the
+    /// semaphore is closed before a permit is acquired.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`acquire_arc_checked`][Semaphore::acquire_arc_checked] method, this method will block the
+    /// current thread until the permit is acquired or the semaphore is closed.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{AcquireError, Semaphore};
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(1));
+    /// let guard = s.acquire_arc_checked_blocking().unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_arc_checked_blocking().unwrap_err(), AcquireError::Closed);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_arc_checked_blocking(
+        self: &Arc<Self>,
+    ) -> Result<SemaphoreGuardArc, AcquireError> {
+        self.acquire_arc_checked().wait()
+    }
+
+    /// Waits for `n` owned permits at once, failing if the semaphore is closed.
+    ///
+    /// Returns a guard that releases all `n` permits when dropped, or [`AcquireError::Closed`] if
+    /// the semaphore is closed before the permits are acquired.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`acquire_many_arc_checked`][Semaphore::acquire_many_arc_checked] method, this method will
+    /// block the current thread until the permits are acquired or the semaphore is closed.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{AcquireError, Semaphore};
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(5));
+    /// let guard = s.acquire_many_arc_checked_blocking(3).unwrap();
+    ///
+    /// s.close();
+    /// assert_eq!(s.acquire_many_arc_checked_blocking(2).unwrap_err(), AcquireError::Closed);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn acquire_many_arc_checked_blocking(
+        self: &Arc<Self>,
+        n: usize,
+    ) -> Result<SemaphoreGuardArc, AcquireError> {
+        self.acquire_many_arc_checked(n).wait()
+    }
+
+    /// Returns the number of permits that are currently available.
+    ///
+    /// Permits held by guards are not counted. The value may be outdated by the time it is used,
+    /// since other tasks can acquire and release permits concurrently.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(3);
+    /// assert_eq!(s.available_permits(), 3);
+    ///
+    /// let guard = s.try_acquire_many(2).unwrap();
+    /// assert_eq!(s.available_permits(), 1);
+    ///
+    /// drop(guard);
+    /// assert_eq!(s.available_permits(), 3);
+    /// ```
+    #[inline]
+    pub fn available_permits(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Adds `n` additional permits to the semaphore.
+    ///
+    /// The number of permits is not checked for overflow. Use
+    /// [`try_add_permits`][Semaphore::try_add_permits] if it could exceed a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let s = Semaphore::new(1);
+    ///
+    /// let _guard = s.acquire().await;
+    /// assert!(s.try_acquire().is_none());
+    ///
+    /// s.add_permits(2);
+    ///
+    /// let _guard = s.acquire().await;
+    /// let _guard = s.acquire().await;
     /// # });
     /// ```
     pub fn add_permits(&self, n: usize) {
         self.count.fetch_add(n, Ordering::AcqRel);
+        self.notify(n);
+    }
+
+    /// Attempts to add `n` additional permits to the semaphore.
+    ///
+    /// If the number of available permits would overflow a `usize`, nothing is added and an
+    /// error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(1);
+    ///
+    /// assert!(s.try_add_permits(2).is_ok());
+    /// assert_eq!(s.available_permits(), 3);
+    ///
+    /// assert!(s.try_add_permits(usize::MAX).is_err());
+    /// assert_eq!(s.available_permits(), 3);
+    /// ```
+    pub fn try_add_permits(&self, n: usize) -> Result<(), TooManyPermits> {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            let new = count
+                .checked_add(n)
+                .ok_or(TooManyPermits { _private: () })?;
+            match self
+                .count
+                .compare_exchange_weak(count, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => break,
+                Err(c) => count = c,
+            }
+        }
+
+        self.notify(n);
+        Ok(())
+    }
+
+    /// Permanently removes `n` permits from the semaphore.
+    ///
+    /// Available permits are removed right away. The returned future then waits for the rest to
+    /// be released by their guards, so that the total number of permits is reduced by `n` when
+    /// it completes.
+    ///
+    /// If the future is dropped before completing, the permits it already removed are given
+    /// back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # futures_lite::future::block_on(async {
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(3);
+    /// let guard = s.acquire().await;
+    ///
+    /// let reduce = s.reduce_permits(3);
+    /// futures_lite::pin!(reduce);
+    /// assert!(futures_lite::future::poll_once(reduce.as_mut()).await.is_none());
+    ///
+    /// drop(guard);
+    /// reduce.await;
+    /// assert_eq!(s.available_permits(), 0);
+    /// # });
+    /// ```
+    pub fn reduce_permits(&self, n: usize) -> ReducePermits<'_> {
+        ReducePermits::_new(ReducePermitsInner {
+            semaphore: self,
+            remaining: n,
+            removed: 0,
+            listener: None,
+            _pin: PhantomPinned,
+        })
+    }
+
+    /// Permanently removes `n` permits from the semaphore.
+    ///
+    /// Available permits are removed right away, then this method waits for the rest to be
+    /// released by their guards.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the
+    /// [`reduce_permits`][Semaphore::reduce_permits] method, this method will block the current
+    /// thread until all `n` permits are removed.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a semaphore can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(3);
+    /// s.reduce_permits_blocking(2);
+    /// assert_eq!(s.available_permits(), 1);
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn reduce_permits_blocking(&self, n: usize) {
+        self.reduce_permits(n).wait()
+    }
+
+    /// Takes `n` permits if they are available.
+    fn try_take(&self, n: usize) -> bool {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            if count < n || self.is_closed() {
+                return false;
+            }
+
+            match self.count.compare_exchange_weak(
+                count,
+                count - n,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return true,
+                Err(c) => count = c,
+            }
+        }
+    }
+
+    /// Takes `n` permits if they are available and no queued operation is waiting for them.
+    ///
+    /// Operations at the head of the queue pass `at_head` to take permits in their turn.
+    fn try_take_queued(&self, n: usize, at_head: bool) -> bool {
+        match &self.queue {
+            Some(queue) if !at_head && queue.len.load(Ordering::Acquire) > 0 => false,
+            _ => self.try_take(n),
+        }
+    }
+
+    /// Takes `n` permits, waiting with `waiter` until they are available.
+    ///
+    /// If the semaphore is fair, `queue` tracks the position of the operation in the wait queue.
+    /// If `checked` is set, fails once the semaphore is closed.
+    fn poll_take<'x, S: Strategy<'x>>(
+        &self,
+        n: usize,
+        checked: bool,
+        mut queue: Pin<&mut Queue>,
+        waiter: &mut Waiter,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Result<(), AcquireError>> {
+        let mut woken = false;
+
+        loop {
+            if self.is_closed() {
+                // Leave the queue so that the operations behind this one find out too.
+                queue.set(Queue::Idle);
+
+                if checked {
+                    return Poll::Ready(Err(AcquireError::Closed));
+                }
+            }
+
+            // Wait to get to the head of the queue.
+            if let QueueProj::Waiting { lock, ticket } = queue.as_mut().project() {
+                let head = ready!(lock.poll_with_strategy(strategy, cx));
+                let ticket = ticket.take();
+                queue.set(Queue::Head { head, ticket });
+                continue;
+            }
+
+            let at_head = matches!(*queue, Queue::Head { .. });
+            if self.try_take_queued(n, at_head) {
+                // Take back a wakeup this operation got, since it doesn't need it anymore.
+                if let Some(listener) = waiter.listener.take() {
+                    listener.discard();
+                }
+                if n > 1 {
+                    self.pass_on_many();
+                }
+
+                // Let the next operation in line through.
+                queue.set(Queue::Idle);
+                return Poll::Ready(Ok(()));
+            }
+
+            if woken && n > 1 {
+                // Let another operation try the released permits, unless this one already did so
+                // for this release: then the wakeup went around all of them.
+                let release = self.releases.load(Ordering::Acquire);
+                if waiter.passed_on != Some(release) {
+                    waiter.passed_on = Some(release);
+                    self.event_many.notify(1);
+                }
+                woken = false;
+            }
+
+            // Wait in line if the semaphore is fair.
+            if let Some(fair) = &self.queue {
+                if !at_head && !self.is_closed() {
+                    queue.set(Queue::Waiting {
+                        ticket: Ticket::new(&fair.len),
+                        lock: fair.lock.lock_arc(),
+                    });
+                    continue;
+                }
+            }
+
+            // Wait on the listener.
+            if waiter.listener.is_none() {
+                waiter.listener = Some(self.event(n).listen());
+            } else {
+                ready!(strategy.poll(&mut waiter.listener, cx));
+                woken = true;
+            }
+        }
+    }
+
+    /// Makes `n` permits available and wakes up the acquire operations that might use them.
+    fn release(&self, n: usize) {
+        self.count.fetch_add(n, Ordering::AcqRel);
+        self.notify(n);
+    }
+
+    /// Takes up to `n` of the available permits, returning how many were taken.
+    ///
+    /// Unlike [`try_take`][Semaphore::try_take], this works on a closed semaphore.
+    fn take_up_to(&self, n: usize) -> usize {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            let taken = count.min(n);
+            if taken == 0 {
+                return 0;
+            }
+
+            match self.count.compare_exchange_weak(
+                count,
+                count - taken,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return taken,
+                Err(c) => count = c,
+            }
+        }
+    }
+
+    /// Wakes up the acquire operations that might use `n` newly available permits.
+    fn notify(&self, n: usize) {
+        self.releases.fetch_add(1, Ordering::AcqRel);
         self.event.notify(n);
+        self.event_many.notify(1);
+    }
+
+    /// Wakes up an operation waiting for more than one permit if any permits are left.
+    fn pass_on_many(&self) {
+        if self.count.load(Ordering::Acquire) > 0 {
+            self.event_many.notify(1);
+        }
+    }
+
+    /// Returns the event to wait on for `n` permits.
+    #[inline]
+    fn event(&self, n: usize) -> &Event {
+        if n > 1 {
+            &self.event_many
+        } else {
+            &self.event
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The position of an acquire operation in the wait queue of a fair semaphore.
+    #[project = QueueProj]
+    enum Queue {
+        /// Not waiting in line.
+        Idle,
+
+        /// Waiting to get to the head of the queue.
+        Waiting {
+            #[pin]
+            lock: LockArc<()>,
+            ticket: Ticket,
+        },
+
+        /// At the head of the queue.
+        Head {
+            head: MutexGuardArc<()>,
+            ticket: Ticket,
+        },
+    }
+}
+
+/// Counts an acquire operation as being in the wait queue of a fair semaphore until dropped.
+struct Ticket(Option<Arc<AtomicUsize>>);
+
+impl Ticket {
+    /// Counts a new operation in the queue of length `len`.
+    fn new(len: &Arc<AtomicUsize>) -> Ticket {
+        len.fetch_add(1, Ordering::AcqRel);
+        Ticket(Some(len.clone()))
     }
+
+    /// Moves the count out of this ticket.
+    fn take(&mut self) -> Ticket {
+        Ticket(self.0.take())
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if let Some(len) = self.0.take() {
+            len.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+/// The registration of an acquire operation waiting for permits.
+#[derive(Default)]
+struct Waiter {
+    /// The listener waiting on the semaphore.
+    listener: Option<EventListener>,
+
+    /// The last release whose wakeup this operation passed on without using it.
+    passed_on: Option<usize>,
 }
 
 easy_wrapper! {
-    /// The future returned by [`Semaphore::acquire`].
+    /// The future returned by [`Semaphore::acquire`] and [`Semaphore::acquire_many`].
     pub struct Acquire<'a>(AcquireInner<'a> => SemaphoreGuard<'a>);
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
     pub(crate) wait();
@@ -248,8 +1293,15 @@ pin_project_lite::pin_project! {
         // The semaphore being acquired.
         semaphore: &'a Semaphore,
 
-        // The listener waiting on the semaphore.
-        listener: Option<EventListener>,
+        // The number of permits to acquire.
+        permits: usize,
+
+        // The position of this operation in the wait queue.
+        #[pin]
+        queue: Queue,
+
+        // The registration waiting on the semaphore.
+        waiter: Waiter,
 
         // Keeping this future `!Unpin` enables future optimizations.
         #[pin]
@@ -272,25 +1324,49 @@ impl<'a> EventListenerFuture for AcquireInner<'a> {
         cx: &mut S::Context,
     ) -> Poll<Self::Output> {
         let this = self.project();
+        let semaphore = *this.semaphore;
 
-        loop {
-            match this.semaphore.try_acquire() {
-                Some(guard) => return Poll::Ready(guard),
-                None => {
-                    // Wait on the listener.
-                    if this.listener.is_none() {
-                        *this.listener = Some(this.semaphore.event.listen());
-                    } else {
-                        ready!(strategy.poll(this.listener, cx));
-                    }
-                }
-            }
-        }
+        // Without the error, this waits forever for a closed semaphore.
+        let _ = ready!(semaphore.poll_take(
+            *this.permits,
+            false,
+            this.queue,
+            this.waiter,
+            strategy,
+            cx
+        ));
+        Poll::Ready(SemaphoreGuard {
+            semaphore,
+            permits: *this.permits,
+        })
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Semaphore::acquire_timeout`].
+    pub struct AcquireTimeout<'a, Tm> {
+        #[pin]
+        inner: Timeout<Acquire<'a>, Tm>,
+    }
+}
+
+impl<Tm> fmt::Debug for AcquireTimeout<'_, Tm> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AcquireTimeout { .. }")
+    }
+}
+
+impl<'a, Tm: Timer> Future for AcquireTimeout<'a, Tm> {
+    type Output = Result<SemaphoreGuard<'a>, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
     }
 }
 
 easy_wrapper! {
-    /// The future returned by [`Semaphore::acquire_arc`].
+    /// The future returned by [`Semaphore::acquire_arc`] and [`Semaphore::acquire_many_arc`].
     pub struct AcquireArc(AcquireArcInner => SemaphoreGuardArc);
     #[cfg(all(feature = "std", not(target_family = "wasm")))]
     pub(crate) wait();
@@ -301,8 +1377,15 @@ pin_project_lite::pin_project! {
         // The semaphore being acquired.
         semaphore: Arc<Semaphore>,
 
-        // The listener waiting on the semaphore.
-        listener: Option<EventListener>,
+        // The number of permits to acquire.
+        permits: usize,
+
+        // The position of this operation in the wait queue.
+        #[pin]
+        queue: Queue,
+
+        // The registration waiting on the semaphore.
+        waiter: Waiter,
 
         // Keeping this future `!Unpin` enables future optimizations.
         #[pin]
@@ -319,6 +1402,206 @@ impl fmt::Debug for AcquireArc {
 impl EventListenerFuture for AcquireArcInner {
     type Output = SemaphoreGuardArc;
 
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        let semaphore = this.semaphore;
+
+        // Without the error, this waits forever for a closed semaphore.
+        let _ = ready!(semaphore.poll_take(
+            *this.permits,
+            false,
+            this.queue,
+            this.waiter,
+            strategy,
+            cx
+        ));
+        Poll::Ready(SemaphoreGuardArc {
+            semaphore: Some(semaphore.clone()),
+            permits: *this.permits,
+        })
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// The future returned by [`Semaphore::acquire_arc_timeout`].
+    pub struct AcquireArcTimeout<Tm> {
+        #[pin]
+        inner: Timeout<AcquireArc, Tm>,
+    }
+}
+
+impl<Tm> fmt::Debug for AcquireArcTimeout<Tm> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AcquireArcTimeout { .. }")
+    }
+}
+
+impl<Tm: Timer> Future for AcquireArcTimeout<Tm> {
+    type Output = Result<SemaphoreGuardArc, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.project().inner.poll(cx)
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Semaphore::acquire_checked`] and
+    /// [`Semaphore::acquire_many_checked`].
+    pub struct AcquireChecked<'a>(AcquireCheckedInner<'a> => Result<SemaphoreGuard<'a>, AcquireError>);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+pin_project_lite::pin_project! {
+    struct AcquireCheckedInner<'a> {
+        // The semaphore being acquired.
+        semaphore: &'a Semaphore,
+
+        // The number of permits to acquire.
+        permits: usize,
+
+        // The position of this operation in the wait queue.
+        #[pin]
+        queue: Queue,
+
+        // The registration waiting on the semaphore.
+        waiter: Waiter,
+
+        // Keeping this future `!Unpin` enables future optimizations.
+        #[pin]
+        _pin: PhantomPinned
+    }
+}
+
+impl fmt::Debug for AcquireChecked<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AcquireChecked { .. }")
+    }
+}
+
+impl<'a> EventListenerFuture for AcquireCheckedInner<'a> {
+    type Output = Result<SemaphoreGuard<'a>, AcquireError>;
+
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        let semaphore = *this.semaphore;
+
+        ready!(semaphore.poll_take(*this.permits, true, this.queue, this.waiter, strategy, cx))?;
+        Poll::Ready(Ok(SemaphoreGuard {
+            semaphore,
+            permits: *this.permits,
+        }))
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Semaphore::acquire_arc_checked`] and
+    /// [`Semaphore::acquire_many_arc_checked`].
+    pub struct AcquireArcChecked(AcquireArcCheckedInner => Result<SemaphoreGuardArc, AcquireError>);
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+pin_project_lite::pin_project! {
+    struct AcquireArcCheckedInner {
+        // The semaphore being acquired.
+        semaphore: Arc<Semaphore>,
+
+        // The number of permits to acquire.
+        permits: usize,
+
+        // The position of this operation in the wait queue.
+        #[pin]
+        queue: Queue,
+
+        // The registration waiting on the semaphore.
+        waiter: Waiter,
+
+        // Keeping this future `!Unpin` enables future optimizations.
+        #[pin]
+        _pin: PhantomPinned
+    }
+}
+
+impl fmt::Debug for AcquireArcChecked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AcquireArcChecked { .. }")
+    }
+}
+
+impl EventListenerFuture for AcquireArcCheckedInner {
+    type Output = Result<SemaphoreGuardArc, AcquireError>;
+
+    fn poll_with_strategy<'x, S: Strategy<'x>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<Self::Output> {
+        let this = self.project();
+        let semaphore = this.semaphore;
+
+        ready!(semaphore.poll_take(*this.permits, true, this.queue, this.waiter, strategy, cx))?;
+        Poll::Ready(Ok(SemaphoreGuardArc {
+            semaphore: Some(semaphore.clone()),
+            permits: *this.permits,
+        }))
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`Semaphore::reduce_permits`].
+    pub struct ReducePermits<'a>(ReducePermitsInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+pin_project_lite::pin_project! {
+    struct ReducePermitsInner<'a> {
+        // The semaphore being reduced.
+        semaphore: &'a Semaphore,
+
+        // The number of permits left to remove.
+        remaining: usize,
+
+        // The number of permits removed so far, given back if the future is dropped.
+        removed: usize,
+
+        // The listener waiting for permits to be released.
+        listener: Option<EventListener>,
+
+        // Keeping this future `!Unpin` enables future optimizations.
+        #[pin]
+        _pin: PhantomPinned
+    }
+
+    impl<'a> PinnedDrop for ReducePermitsInner<'a> {
+        fn drop(this: Pin<&mut Self>) {
+            let this = this.project();
+            if *this.remaining > 0 && *this.removed > 0 {
+                this.semaphore.release(*this.removed);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ReducePermits<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ReducePermits { .. }")
+    }
+}
+
+impl EventListenerFuture for ReducePermitsInner<'_> {
+    type Output = ();
+
     fn poll_with_strategy<'x, S: Strategy<'x>>(
         self: Pin<&mut Self>,
         strategy: &mut S,
@@ -327,62 +1610,137 @@ impl EventListenerFuture for AcquireArcInner {
         let this = self.project();
 
         loop {
-            match this.semaphore.try_acquire_arc() {
-                Some(guard) => return Poll::Ready(guard),
-                None => {
-                    // Wait on the listener.
-                    if this.listener.is_none() {
-                        *this.listener = Some(this.semaphore.event.listen());
-                    } else {
-                        ready!(strategy.poll(this.listener, cx));
-                    }
-                }
+            let taken = this.semaphore.take_up_to(*this.remaining);
+            *this.remaining -= taken;
+            *this.removed += taken;
+
+            if *this.remaining == 0 {
+                this.semaphore.pass_on_many();
+                return Poll::Ready(());
+            }
+
+            // Wait for more permits to be released.
+            if this.listener.is_none() {
+                *this.listener = Some(this.semaphore.event_many.listen());
+            } else {
+                ready!(strategy.poll(this.listener, cx));
             }
         }
     }
 }
 
-/// A guard that releases the acquired permit.
+/// The error returned by [`Semaphore::try_add_permits`] when the permits would overflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooManyPermits {
+    _private: (),
+}
+
+impl fmt::Display for TooManyPermits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("too many permits")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TooManyPermits {}
+
+/// The error returned by the checked acquire operations of a [`Semaphore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum AcquireError {
+    /// The semaphore was closed.
+    Closed,
+}
+
+impl fmt::Display for AcquireError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcquireError::Closed => f.write_str("semaphore closed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AcquireError {}
+
+/// A guard that releases the acquired permits.
 #[clippy::has_significant_drop]
 #[derive(Debug)]
-pub struct SemaphoreGuard<'a>(&'a Semaphore);
+pub struct SemaphoreGuard<'a> {
+    semaphore: &'a Semaphore,
+    permits: usize,
+}
 
 impl SemaphoreGuard<'_> {
-    /// Drops the guard _without_ releasing the acquired permit.
+    /// Drops the guard _without_ releasing the acquired permits.
     #[inline]
     pub fn forget(self) {
         mem::forget(self);
     }
+
+    /// Returns the number of permits held by this guard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    ///
+    /// let s = Semaphore::new(5);
+    /// let guard = s.try_acquire_many(3).unwrap();
+    /// assert_eq!(guard.permits(), 3);
+    /// ```
+    #[inline]
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
 }
 
 impl Drop for SemaphoreGuard<'_> {
     fn drop(&mut self) {
-        self.0.count.fetch_add(1, Ordering::AcqRel);
-        self.0.event.notify(1);
+        self.semaphore.release(self.permits);
     }
 }
 
-/// An owned guard that releases the acquired permit.
+/// An owned guard that releases the acquired permits.
 #[clippy::has_significant_drop]
 #[derive(Debug)]
-pub struct SemaphoreGuardArc(Option<Arc<Semaphore>>);
+pub struct SemaphoreGuardArc {
+    semaphore: Option<Arc<Semaphore>>,
+    permits: usize,
+}
 
 impl SemaphoreGuardArc {
-    /// Drops the guard _without_ releasing the acquired permit.
+    /// Drops the guard _without_ releasing the acquired permits.
     /// (Will still decrement the `Arc` reference count.)
     #[inline]
     pub fn forget(mut self) {
         // Drop the inner `Arc` in order to decrement the reference count.
         // FIXME: get rid of the `Option` once RFC 3466 or equivalent becomes available.
-        drop(self.0.take());
+        drop(self.semaphore.take());
         mem::forget(self);
     }
+
+    /// Returns the number of permits held by this guard.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Semaphore;
+    /// use std::sync::Arc;
+    ///
+    /// let s = Arc::new(Semaphore::new(5));
+    /// let guard = s.try_acquire_many_arc(3).unwrap();
+    /// assert_eq!(guard.permits(), 3);
+    /// ```
+    #[inline]
+    pub fn permits(&self) -> usize {
+        self.permits
+    }
 }
 
 impl Drop for SemaphoreGuardArc {
     fn drop(&mut self) {
-        let opt = self.0.take().unwrap();
-        opt.count.fetch_add(1, Ordering::AcqRel);
-        opt.event.notify(1);
+        let opt = self.semaphore.take().unwrap();
+        opt.release(self.permits);
     }
 }
@@ -0,0 +1,164 @@
+use alloc::sync::Arc;
+
+use core::fmt;
+use core::future::Future;
+use core::hash::Hash;
+
+use crate::once_map::Map;
+use crate::sync_mutex::SyncMutex;
+use crate::OnceCell;
+
+/// Deduplicates concurrent calls with the same key.
+///
+/// Concurrent [`call()`][Singleflight::call]s with the same key share one execution: the first
+/// caller runs its future, while the others wait for its output and get a clone of it. Nothing
+/// is kept once the call completes, so a later call with the same key runs again.
+///
+/// If the running caller is cancelled or its future panics, one of the waiting callers takes
+/// over and runs its own future instead.
+///
+/// Keys must implement both [`Hash`] and [`Ord`], like the keys of a [`OnceMap`][crate::OnceMap].
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Singleflight;
+/// use futures_lite::future;
+///
+/// # futures_lite::future::block_on(async {
+/// let group = Singleflight::new();
+///
+/// // Both calls share the first future's execution.
+/// let (a, b) = future::zip(
+///     group.call("key", async {
+///         future::yield_now().await;
+///         1
+///     }),
+///     group.call("key", async { 2 }),
+/// )
+/// .await;
+/// assert_eq!((a, b), (1, 1));
+///
+/// // The call is forgotten once it completes.
+/// assert_eq!(group.call("key", async { 3 }).await, 3);
+/// # });
+/// ```
+pub struct Singleflight<K, V> {
+    /// The calls in flight.
+    ///
+    /// This lock is only held for as long as it takes to look up, insert or remove a call.
+    flights: SyncMutex<Map<K, Arc<OnceCell<V>>>>,
+}
+
+impl<K: Clone + Eq + Hash + Ord, V: Clone> Singleflight<K, V> {
+    /// Creates a new group with no calls in flight.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Singleflight;
+    ///
+    /// let group = Singleflight::<String, u32>::new();
+    /// ```
+    pub fn new() -> Singleflight<K, V> {
+        Singleflight {
+            flights: SyncMutex::new(Map::new()),
+        }
+    }
+
+    /// Runs `future`, unless a call with the same key is already running, in which case its
+    /// output is shared instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Singleflight;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let group = Singleflight::new();
+    /// assert_eq!(group.call(1, async { "one" }).await, "one");
+    /// # });
+    /// ```
+    pub async fn call<Fut: Future<Output = V>>(&self, key: K, future: Fut) -> V {
+        let flight = self.join(&key);
+
+        // Forget the call if it is abandoned and nobody is left to take over.
+        let _guard = Leave {
+            group: self,
+            key: &key,
+            flight: &flight,
+            landed: false,
+        };
+
+        flight
+            .get_or_init(|| async {
+                let output = future.await;
+
+                // Calls made from now on run again.
+                drop(Leave {
+                    group: self,
+                    key: &key,
+                    flight: &flight,
+                    landed: true,
+                });
+
+                output
+            })
+            .await
+            .clone()
+    }
+
+    /// Returns the call in flight for `key`, starting a new one if needed.
+    fn join(&self, key: &K) -> Arc<OnceCell<V>> {
+        self.flights
+            .lock()
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(OnceCell::new()))
+            .clone()
+    }
+}
+
+impl<K: Clone + Eq + Hash + Ord, V: Clone> Default for Singleflight<K, V> {
+    #[inline]
+    fn default() -> Singleflight<K, V> {
+        Singleflight::new()
+    }
+}
+
+impl<K, V> fmt::Debug for Singleflight<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Singleflight { .. }")
+    }
+}
+
+/// Removes a call from its group when dropped.
+struct Leave<'a, K: Clone + Eq + Hash + Ord, V: Clone> {
+    /// The group making the call.
+    group: &'a Singleflight<K, V>,
+
+    /// The key of the call.
+    key: &'a K,
+
+    /// The call.
+    flight: &'a Arc<OnceCell<V>>,
+
+    /// Whether the call has completed.
+    landed: bool,
+}
+
+impl<K: Clone + Eq + Hash + Ord, V: Clone> Drop for Leave<'_, K, V> {
+    fn drop(&mut self) {
+        let mut flights = self.group.flights.lock();
+
+        let current = match flights.get(self.key) {
+            Some(current) if Arc::ptr_eq(current, self.flight) => current,
+            _ => return,
+        };
+
+        // Other references to an abandoned call can only be made through the map, which is now
+        // locked. With only the map's and ours left, nobody is waiting to take over.
+        if self.landed || (Arc::strong_count(current) == 2 && !current.is_initialized()) {
+            flights.remove(self.key);
+        }
+    }
+}
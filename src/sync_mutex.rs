@@ -0,0 +1,56 @@
+//! A mutex for the short critical sections of primitives that also need to lock from
+//! synchronous code, like `Drop` impls and non-async getters.
+
+/// A mutex held only for as long as it takes to read or swap a few fields.
+///
+/// With `std`, this is a [`std::sync::Mutex`] that ignores poisoning. Without it, there is no
+/// way to block the thread, so this spins on a [`Mutex`][crate::Mutex] instead.
+pub(crate) struct SyncMutex<T> {
+    #[cfg(feature = "std")]
+    inner: std::sync::Mutex<T>,
+
+    #[cfg(not(feature = "std"))]
+    inner: crate::Mutex<T>,
+}
+
+/// A guard releasing a [`SyncMutex`] when dropped.
+#[cfg(feature = "std")]
+pub(crate) type SyncMutexGuard<'a, T> = std::sync::MutexGuard<'a, T>;
+
+/// A guard releasing a [`SyncMutex`] when dropped.
+#[cfg(not(feature = "std"))]
+pub(crate) type SyncMutexGuard<'a, T> = crate::MutexGuard<'a, T>;
+
+impl<T> SyncMutex<T> {
+    /// Creates a new mutex.
+    pub(crate) fn new(value: T) -> SyncMutex<T> {
+        SyncMutex {
+            #[cfg(feature = "std")]
+            inner: std::sync::Mutex::new(value),
+
+            #[cfg(not(feature = "std"))]
+            inner: crate::Mutex::new(value),
+        }
+    }
+
+    /// Locks the mutex, blocking the current thread until it is available.
+    #[cfg(feature = "std")]
+    pub(crate) fn lock(&self) -> SyncMutexGuard<'_, T> {
+        // The critical sections leave the data consistent even if they panic.
+        self.inner
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Locks the mutex, spinning until it is available.
+    #[cfg(not(feature = "std"))]
+    pub(crate) fn lock(&self) -> SyncMutexGuard<'_, T> {
+        loop {
+            if let Some(guard) = self.inner.try_lock() {
+                return guard;
+            }
+
+            core::hint::spin_loop();
+        }
+    }
+}
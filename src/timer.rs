@@ -0,0 +1,137 @@
+use core::fmt;
+use core::future::Future;
+use core::ops::DerefMut;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A timer bounding how long an operation may wait.
+///
+/// Timers are passed to methods like [`Mutex::lock_timeout()`][crate::Mutex::lock_timeout],
+/// which give up with a [`TimedOut`] error once the timer expires. Giving up this way properly
+/// withdraws the operation, which isn't always the case when wrapping the plain future into a
+/// runtime's own timeout.
+///
+/// With the `async-io` feature enabled, this trait is implemented for [`async_io::Timer`]. Timers
+/// from other runtimes can be used by implementing this trait for a wrapper type.
+///
+/// [`async_io::Timer`]: https://docs.rs/async-io/latest/async_io/struct.Timer.html
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Timer;
+/// use std::future::Future;
+/// use std::pin::Pin;
+/// use std::task::{Context, Poll};
+///
+/// /// Turns any future into a timer that expires when the future completes.
+/// struct Sleep<F>(Pin<Box<F>>);
+///
+/// impl<F: Future> Timer for Sleep<F> {
+///     fn poll_expired(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+///         self.0.as_mut().poll(cx).map(|_| ())
+///     }
+/// }
+/// ```
+pub trait Timer {
+    /// Polls the timer, returning `Poll::Ready(())` once it has expired.
+    ///
+    /// Otherwise, the waker from `cx` is woken up when the timer expires.
+    fn poll_expired(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()>;
+}
+
+impl<T: Timer + Unpin + ?Sized> Timer for &mut T {
+    #[inline]
+    fn poll_expired(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        Pin::new(&mut **self).poll_expired(cx)
+    }
+}
+
+impl<P> Timer for Pin<P>
+where
+    P: DerefMut + Unpin,
+    P::Target: Timer,
+{
+    #[inline]
+    fn poll_expired(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.get_mut().as_mut().poll_expired(cx)
+    }
+}
+
+#[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+impl Timer for async_io::Timer {
+    #[inline]
+    fn poll_expired(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.poll(cx).map(|_| ())
+    }
+}
+
+/// The error returned when a [`Timer`] expires before an operation completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut {
+    _private: (),
+}
+
+impl TimedOut {
+    #[inline]
+    pub(crate) fn new() -> TimedOut {
+        TimedOut { _private: () }
+    }
+}
+
+impl fmt::Display for TimedOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("operation timed out")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimedOut {}
+
+pin_project_lite::pin_project! {
+    /// Races a future against a timer.
+    pub(crate) struct Timeout<F, Tm> {
+        // The operation, dropped as soon as the timer expires.
+        #[pin]
+        future: Option<F>,
+
+        // The timer bounding the operation.
+        #[pin]
+        timer: Tm,
+    }
+}
+
+impl<F, Tm> Timeout<F, Tm> {
+    #[inline]
+    pub(crate) fn new(future: F, timer: Tm) -> Timeout<F, Tm> {
+        Timeout {
+            future: Some(future),
+            timer,
+        }
+    }
+}
+
+impl<F: Future, Tm: Timer> Future for Timeout<F, Tm> {
+    type Output = Result<F::Output, TimedOut>;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+
+        let future = this
+            .future
+            .as_mut()
+            .as_pin_mut()
+            .expect("`Timeout` polled after completion");
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(Ok(output));
+        }
+
+        ready!(this.timer.poll_expired(cx));
+
+        // Withdraw the operation right away rather than when this future is dropped, so that it
+        // doesn't keep its place in line in the meantime.
+        this.future.set(None);
+        Poll::Ready(Err(TimedOut::new()))
+    }
+}
@@ -0,0 +1,386 @@
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::Poll;
+
+use alloc::sync::Arc;
+
+use event_listener::{Event, EventListener};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+
+/// Waits for a set of tasks to finish.
+///
+/// The group counts the tasks that haven't finished yet, and [`wait()`][WaitGroup::wait]
+/// completes once the count drops to zero. Unlike a [`Barrier`][crate::Barrier], the number of
+/// tasks doesn't need to be known up front, and waiting doesn't count as one of them.
+///
+/// Tasks are counted either with [`add()`][WaitGroup::add] and [`done()`][WaitGroup::done], or
+/// with tokens that count as one task until they are dropped. Cloning a token counts another
+/// task.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::WaitGroup;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let group = Arc::new(WaitGroup::new());
+///
+/// for _ in 0..4 {
+///     let token = group.token_arc();
+///     thread::spawn(move || {
+///         // Do some work, then let the group know by dropping the token.
+///         drop(token);
+///     });
+/// }
+///
+/// # futures_lite::future::block_on(async {
+/// group.wait().await;
+/// assert_eq!(group.count(), 0);
+/// # });
+/// ```
+pub struct WaitGroup {
+    /// The number of tasks that haven't finished yet.
+    count: AtomicUsize,
+
+    /// Tasks waiting for the count to drop to zero.
+    event: Event,
+}
+
+impl WaitGroup {
+    /// Creates a new group with no tasks in it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    ///
+    /// let group = WaitGroup::new();
+    /// ```
+    pub const fn new() -> WaitGroup {
+        WaitGroup {
+            count: AtomicUsize::new(0),
+            event: Event::new(),
+        }
+    }
+
+    /// Returns the number of tasks that haven't finished yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    ///
+    /// let group = WaitGroup::new();
+    /// group.add(2);
+    /// assert_eq!(group.count(), 2);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Adds `n` tasks to the group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of tasks would overflow a `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    ///
+    /// let group = WaitGroup::new();
+    /// group.add(3);
+    /// assert_eq!(group.count(), 3);
+    /// ```
+    pub fn add(&self, n: usize) {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            let new = match count.checked_add(n) {
+                Some(new) => new,
+                None => panic!("too many tasks added to the wait group"),
+            };
+            match self
+                .count
+                .compare_exchange_weak(count, new, Ordering::AcqRel, Ordering::Acquire)
+            {
+                Ok(_) => return,
+                Err(c) => count = c,
+            }
+        }
+    }
+
+    /// Marks one task of the group as finished.
+    ///
+    /// Tasks waiting on the group are woken up if this was the last one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the group has no tasks left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let group = WaitGroup::new();
+    /// group.add(1);
+    /// group.done();
+    /// group.wait().await;
+    /// # });
+    /// ```
+    pub fn done(&self) {
+        if !self.try_done() {
+            panic!("`WaitGroup::done()` called with no tasks left");
+        }
+    }
+
+    /// Marks one task of the group as finished, returning `false` if the group had none left.
+    ///
+    /// Tokens finish their task with this, so that dropping one after its task was already
+    /// finished by an extra call to [`done()`][WaitGroup::done] doesn't panic in `drop`.
+    fn try_done(&self) -> bool {
+        let mut count = self.count.load(Ordering::Acquire);
+        loop {
+            if count == 0 {
+                return false;
+            }
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(c) => count = c,
+            }
+        }
+
+        if count == 1 {
+            self.event.notify(usize::MAX);
+        }
+        true
+    }
+
+    /// Adds a task to the group, returning a token that marks it as finished when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    ///
+    /// let group = WaitGroup::new();
+    /// let token = group.token();
+    /// let token2 = token.clone();
+    /// assert_eq!(group.count(), 2);
+    ///
+    /// drop((token, token2));
+    /// assert_eq!(group.count(), 0);
+    /// ```
+    pub fn token(&self) -> WaitGroupToken<'_> {
+        self.add(1);
+        WaitGroupToken { group: self }
+    }
+
+    /// Adds a task to the group, returning an owned token that marks it as finished when
+    /// dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    /// use std::sync::Arc;
+    ///
+    /// let group = Arc::new(WaitGroup::new());
+    /// let token = group.token_arc();
+    /// assert_eq!(group.count(), 1);
+    ///
+    /// drop(token);
+    /// assert_eq!(group.count(), 0);
+    /// ```
+    pub fn token_arc(self: &Arc<Self>) -> WaitGroupTokenArc {
+        self.add(1);
+        WaitGroupTokenArc {
+            group: self.clone(),
+        }
+    }
+
+    /// Waits until all tasks of the group have finished.
+    ///
+    /// Completes right away if the group has no tasks left.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    /// use futures_lite::future;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let group = WaitGroup::new();
+    /// let token = group.token();
+    /// assert!(future::poll_once(group.wait()).await.is_none());
+    ///
+    /// drop(token);
+    /// group.wait().await;
+    /// # });
+    /// ```
+    pub fn wait(&self) -> WaitGroupWait<'_> {
+        WaitGroupWait::_new(CountWaitInner::new(&self.count, &self.event))
+    }
+
+    /// Waits until all tasks of the group have finished.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`wait`][WaitGroup::wait] method,
+    /// this method will block the current thread until the group has no tasks left.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `WaitGroup` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::WaitGroup;
+    /// use std::sync::Arc;
+    /// use std::thread;
+    ///
+    /// let group = Arc::new(WaitGroup::new());
+    /// let token = group.token_arc();
+    ///
+    /// thread::spawn(move || drop(token));
+    /// group.wait_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn wait_blocking(&self) {
+        self.wait().wait();
+    }
+}
+
+impl Default for WaitGroup {
+    #[inline]
+    fn default() -> WaitGroup {
+        WaitGroup::new()
+    }
+}
+
+impl fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+/// A token counting as one unfinished task of a [`WaitGroup`].
+///
+/// Created by [`WaitGroup::token()`].
+#[clippy::has_significant_drop]
+#[derive(Debug)]
+pub struct WaitGroupToken<'a> {
+    /// The group this token counts a task of.
+    group: &'a WaitGroup,
+}
+
+impl Clone for WaitGroupToken<'_> {
+    fn clone(&self) -> Self {
+        self.group.token()
+    }
+}
+
+impl Drop for WaitGroupToken<'_> {
+    fn drop(&mut self) {
+        self.group.try_done();
+    }
+}
+
+/// An owned token counting as one unfinished task of a [`WaitGroup`].
+///
+/// Created by [`WaitGroup::token_arc()`].
+#[clippy::has_significant_drop]
+#[derive(Debug)]
+pub struct WaitGroupTokenArc {
+    /// The group this token counts a task of.
+    group: Arc<WaitGroup>,
+}
+
+impl Clone for WaitGroupTokenArc {
+    fn clone(&self) -> Self {
+        self.group.token_arc()
+    }
+}
+
+impl Drop for WaitGroupTokenArc {
+    fn drop(&mut self) {
+        self.group.try_done();
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`WaitGroup::wait`].
+    pub struct WaitGroupWait<'a>(CountWaitInner<'a> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl fmt::Debug for WaitGroupWait<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WaitGroupWait { .. }")
+    }
+}
+
+/// Inner future for waiting until a count drops to zero.
+///
+/// Shared by [`WaitGroup`] and [`Latch`][crate::Latch], which both count down to zero and wake
+/// up everyone waiting once they get there.
+pub(crate) struct CountWaitInner<'a> {
+    /// The count to wait on.
+    count: &'a AtomicUsize,
+
+    /// Tasks waiting for the count to drop to zero.
+    event: &'a Event,
+
+    /// An event listener for the `event` event.
+    listener: Option<EventListener>,
+}
+
+impl<'a> CountWaitInner<'a> {
+    /// Creates a future waiting until `count` drops to zero, which is announced on `event`.
+    #[inline]
+    pub(crate) fn new(count: &'a AtomicUsize, event: &'a Event) -> Self {
+        CountWaitInner {
+            count,
+            event,
+            listener: None,
+        }
+    }
+}
+
+impl EventListenerFuture for CountWaitInner<'_> {
+    type Output = ();
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<()> {
+        let this = self.get_mut();
+
+        loop {
+            if this.count.load(Ordering::Acquire) == 0 {
+                return Poll::Ready(());
+            }
+
+            if this.listener.is_some() {
+                ready!(strategy.poll(&mut this.listener, cx));
+            } else {
+                this.listener = Some(this.event.listen());
+            }
+        }
+    }
+}
@@ -0,0 +1,601 @@
+use alloc::sync::Arc;
+
+use core::fmt;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::Poll;
+
+use event_listener::{Event, EventListener};
+use event_listener_strategy::{easy_wrapper, EventListenerFuture, Strategy};
+
+use crate::futures::Read;
+use crate::RwLock;
+
+/// A single value broadcast to tasks watching for changes.
+///
+/// Writers replace the value with [`send()`][Watch::send] or update it in place with
+/// [`send_modify()`][Watch::send_modify]. Each [`WatchReceiver`] remembers the version of the
+/// value it has seen, and [`changed()`][WatchReceiver::changed] waits until a newer one is sent.
+///
+/// Receivers only ever see the latest value: if several values are sent in a row, the
+/// intermediate ones are skipped.
+///
+/// # Examples
+///
+/// ```
+/// use async_lock::Watch;
+///
+/// # futures_lite::future::block_on(async {
+/// let config = Watch::new("v1");
+/// let mut receiver = config.subscribe();
+///
+/// config.send("v2").await;
+/// receiver.changed().await;
+/// assert_eq!(*receiver.borrow().await, "v2");
+/// # });
+/// ```
+pub struct Watch<T: ?Sized> {
+    /// The number of values sent so far.
+    version: AtomicUsize,
+
+    /// Receivers waiting for a new value.
+    event: Event,
+
+    /// The current value.
+    value: RwLock<T>,
+}
+
+impl<T> Watch<T> {
+    /// Creates a new `Watch` holding `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// let watch = Watch::new(0);
+    /// ```
+    pub const fn new(value: T) -> Watch<T> {
+        Watch {
+            version: AtomicUsize::new(0),
+            event: Event::new(),
+            value: RwLock::new(value),
+        }
+    }
+
+    /// Consumes the `Watch`, returning its current value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// let watch = Watch::new(5);
+    /// assert_eq!(watch.into_inner(), 5);
+    /// ```
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    /// Replaces the value and wakes up all receivers waiting for a change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// watch.send(2).await;
+    /// assert_eq!(*watch.borrow().await, 2);
+    /// # });
+    /// ```
+    pub async fn send(&self, value: T) {
+        self.send_modify(|current| *current = value).await;
+    }
+
+    /// Replaces the value and wakes up all receivers waiting for a change.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`send`][Watch::send] method, this method
+    /// will block the current thread until the value can be replaced.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Watch` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// let watch = Watch::new(1);
+    /// watch.send_blocking(2);
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(*watch.borrow().await, 2);
+    /// # });
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn send_blocking(&self, value: T) {
+        self.send_modify_blocking(|current| *current = value);
+    }
+}
+
+impl<T: ?Sized> Watch<T> {
+    /// Updates the value in place and wakes up all receivers waiting for a change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(vec![1]);
+    /// watch.send_modify(|list| list.push(2)).await;
+    /// assert_eq!(*watch.borrow().await, [1, 2]);
+    /// # });
+    /// ```
+    pub async fn send_modify(&self, modify: impl FnOnce(&mut T)) {
+        {
+            let mut value = self.value.write().await;
+            modify(&mut value);
+
+            // Bump the version before the new value can be read, so that receivers reading it
+            // don't see it as changed again afterwards.
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+
+        self.event.notify(usize::MAX);
+    }
+
+    /// Updates the value in place and wakes up all receivers waiting for a change.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`send_modify`][Watch::send_modify]
+    /// method, this method will block the current thread until the value can be updated.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Watch` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// let watch = Watch::new(vec![1]);
+    /// watch.send_modify_blocking(|list| list.push(2));
+    /// # futures_lite::future::block_on(async {
+    /// assert_eq!(*watch.borrow().await, [1, 2]);
+    /// # });
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub fn send_modify_blocking(&self, modify: impl FnOnce(&mut T)) {
+        {
+            let mut value = self.value.write_blocking();
+            modify(&mut value);
+
+            // Bump the version before the new value can be read, as in `send_modify()`.
+            self.version.fetch_add(1, Ordering::AcqRel);
+        }
+
+        self.event.notify(usize::MAX);
+    }
+
+    /// Acquires a read lock on the current value.
+    ///
+    /// Senders wait until the returned guard is dropped, so it shouldn't be held for long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// assert_eq!(*watch.borrow().await, 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn borrow(&self) -> Read<'_, T> {
+        self.value.read()
+    }
+
+    /// Creates a receiver that has seen the current value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// let receiver = watch.subscribe();
+    /// assert!(!receiver.has_changed());
+    ///
+    /// watch.send(2).await;
+    /// assert!(receiver.has_changed());
+    /// # });
+    /// ```
+    pub fn subscribe(&self) -> WatchReceiver<'_, T> {
+        WatchReceiver {
+            watch: self,
+            version: self.version.load(Ordering::Acquire),
+        }
+    }
+
+    /// Creates a receiver that has seen the current value.
+    ///
+    /// This method is similar to [`subscribe()`][Watch::subscribe], but the returned receiver
+    /// holds on to the `Watch` through an [`Arc`] instead of borrowing it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Arc::new(Watch::new(1));
+    /// let mut receiver = watch.subscribe_arc();
+    ///
+    /// watch.send(2).await;
+    /// receiver.changed().await;
+    /// assert_eq!(*receiver.borrow().await, 2);
+    /// # });
+    /// ```
+    pub fn subscribe_arc(self: &Arc<Self>) -> WatchReceiverArc<T> {
+        WatchReceiverArc {
+            watch: self.clone(),
+            version: self.version.load(Ordering::Acquire),
+        }
+    }
+}
+
+impl<T: Default> Default for Watch<T> {
+    #[inline]
+    fn default() -> Watch<T> {
+        Watch::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Watch<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Watch")
+            .field("version", &self.version.load(Ordering::Relaxed))
+            .field("value", &&self.value)
+            .finish()
+    }
+}
+
+/// A receiver of the values sent to a [`Watch`].
+///
+/// Created by [`Watch::subscribe()`]. Cloning a receiver creates another one that has seen the
+/// same version of the value.
+pub struct WatchReceiver<'a, T: ?Sized> {
+    /// The watched value.
+    watch: &'a Watch<T>,
+
+    /// The version of the value this receiver has seen.
+    version: usize,
+}
+
+impl<'a, T: ?Sized> WatchReceiver<'a, T> {
+    /// Returns `true` if a value was sent since this receiver last saw one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// let mut receiver = watch.subscribe();
+    ///
+    /// watch.send(2).await;
+    /// assert!(receiver.has_changed());
+    ///
+    /// receiver.changed().await;
+    /// assert!(!receiver.has_changed());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn has_changed(&self) -> bool {
+        self.watch.version.load(Ordering::Acquire) != self.version
+    }
+
+    /// Waits until a value is sent that this receiver hasn't seen yet, and marks it as seen.
+    ///
+    /// Completes right away if such a value was sent before this method was called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use futures_lite::future;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// let mut receiver = watch.subscribe();
+    /// assert!(future::poll_once(receiver.changed()).await.is_none());
+    ///
+    /// watch.send(2).await;
+    /// receiver.changed().await;
+    /// # });
+    /// ```
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed::_new(ChangedInner {
+            watch: self.watch,
+            version: &mut self.version,
+            listener: None,
+        })
+    }
+
+    /// Waits until a value is sent that this receiver hasn't seen yet, and marks it as seen.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`changed`][WatchReceiver::changed]
+    /// method, this method will block the current thread until a new value is sent.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Watch` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// let watch = Watch::new(1);
+    /// let mut receiver = watch.subscribe();
+    ///
+    /// watch.send_blocking(2);
+    /// receiver.changed_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn changed_blocking(&mut self) {
+        self.changed().wait();
+    }
+
+    /// Acquires a read lock on the current value, without marking it as seen.
+    ///
+    /// Senders wait until the returned guard is dropped, so it shouldn't be held for long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Watch::new(1);
+    /// let receiver = watch.subscribe();
+    /// assert_eq!(*receiver.borrow().await, 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn borrow(&self) -> Read<'a, T> {
+        self.watch.borrow()
+    }
+}
+
+impl<T: ?Sized> Clone for WatchReceiver<'_, T> {
+    fn clone(&self) -> Self {
+        WatchReceiver {
+            watch: self.watch,
+            version: self.version,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for WatchReceiver<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchReceiver")
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+/// A receiver of the values sent to a [`Watch`], holding on to it through an [`Arc`].
+///
+/// Created by [`Watch::subscribe_arc()`]. Cloning a receiver creates another one that has seen
+/// the same version of the value.
+pub struct WatchReceiverArc<T: ?Sized> {
+    /// The watched value.
+    watch: Arc<Watch<T>>,
+
+    /// The version of the value this receiver has seen.
+    version: usize,
+}
+
+impl<T: ?Sized> WatchReceiverArc<T> {
+    /// Returns `true` if a value was sent since this receiver last saw one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Arc::new(Watch::new(1));
+    /// let mut receiver = watch.subscribe_arc();
+    ///
+    /// watch.send(2).await;
+    /// assert!(receiver.has_changed());
+    ///
+    /// receiver.changed().await;
+    /// assert!(!receiver.has_changed());
+    /// # });
+    /// ```
+    #[inline]
+    pub fn has_changed(&self) -> bool {
+        self.watch.version.load(Ordering::Acquire) != self.version
+    }
+
+    /// Waits until a value is sent that this receiver hasn't seen yet, and marks it as seen.
+    ///
+    /// Completes right away if such a value was sent before this method was called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use futures_lite::future;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Arc::new(Watch::new(1));
+    /// let mut receiver = watch.subscribe_arc();
+    /// assert!(future::poll_once(receiver.changed()).await.is_none());
+    ///
+    /// watch.send(2).await;
+    /// receiver.changed().await;
+    /// # });
+    /// ```
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed::_new(ChangedInner {
+            watch: &self.watch,
+            version: &mut self.version,
+            listener: None,
+        })
+    }
+
+    /// Waits until a value is sent that this receiver hasn't seen yet, and marks it as seen.
+    ///
+    /// # Blocking
+    ///
+    /// Rather than using asynchronous waiting, like the [`changed`][WatchReceiverArc::changed]
+    /// method, this method will block the current thread until a new value is sent.
+    ///
+    /// This method should not be used in an asynchronous context. It is intended to be
+    /// used in a way that a `Watch` can be used in both asynchronous and synchronous contexts.
+    /// Calling this method in an asynchronous context may result in a deadlock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use std::sync::Arc;
+    ///
+    /// let watch = Arc::new(Watch::new(1));
+    /// let mut receiver = watch.subscribe_arc();
+    ///
+    /// watch.send_blocking(2);
+    /// receiver.changed_blocking();
+    /// ```
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    #[inline]
+    pub fn changed_blocking(&mut self) {
+        self.changed().wait();
+    }
+
+    /// Acquires a read lock on the current value, without marking it as seen.
+    ///
+    /// Senders wait until the returned guard is dropped, so it shouldn't be held for long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::Watch;
+    /// use std::sync::Arc;
+    ///
+    /// # futures_lite::future::block_on(async {
+    /// let watch = Arc::new(Watch::new(1));
+    /// let receiver = watch.subscribe_arc();
+    /// assert_eq!(*receiver.borrow().await, 1);
+    /// # });
+    /// ```
+    #[inline]
+    pub fn borrow(&self) -> Read<'_, T> {
+        self.watch.borrow()
+    }
+
+    /// Returns a reference to the `Watch` this receiver is subscribed to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_lock::{Watch, WatchReceiverArc};
+    /// use std::sync::Arc;
+    ///
+    /// let watch = Arc::new(Watch::new(1));
+    /// let receiver = watch.subscribe_arc();
+    /// assert!(Arc::ptr_eq(WatchReceiverArc::source(&receiver), &watch));
+    /// ```
+    pub fn source(receiver: &Self) -> &Arc<Watch<T>> {
+        &receiver.watch
+    }
+}
+
+impl<T: ?Sized> Clone for WatchReceiverArc<T> {
+    fn clone(&self) -> Self {
+        WatchReceiverArc {
+            watch: self.watch.clone(),
+            version: self.version,
+        }
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for WatchReceiverArc<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchReceiverArc")
+            .field("version", &self.version)
+            .finish()
+    }
+}
+
+easy_wrapper! {
+    /// The future returned by [`WatchReceiver::changed`] and [`WatchReceiverArc::changed`].
+    pub struct Changed<'a, T: ?Sized>(ChangedInner<'a, T> => ());
+    #[cfg(all(feature = "std", not(target_family = "wasm")))]
+    pub(crate) wait();
+}
+
+impl<T: ?Sized> fmt::Debug for Changed<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Changed { .. }")
+    }
+}
+
+/// Inner future for waiting on a new value.
+struct ChangedInner<'a, T: ?Sized> {
+    /// The watched value.
+    watch: &'a Watch<T>,
+
+    /// The version of the value the receiver has seen.
+    version: &'a mut usize,
+
+    /// An event listener for the `watch.event` event.
+    listener: Option<EventListener>,
+}
+
+impl<T: ?Sized> EventListenerFuture for ChangedInner<'_, T> {
+    type Output = ();
+
+    fn poll_with_strategy<'a, S: Strategy<'a>>(
+        self: Pin<&mut Self>,
+        strategy: &mut S,
+        cx: &mut S::Context,
+    ) -> Poll<()> {
+        let this = self.get_mut();
+
+        loop {
+            let version = this.watch.version.load(Ordering::Acquire);
+            if version != *this.version {
+                *this.version = version;
+                return Poll::Ready(());
+            }
+
+            if this.listener.is_some() {
+                ready!(strategy.poll(&mut this.listener, cx));
+            } else {
+                this.listener = Some(this.watch.event.listen());
+            }
+        }
+    }
+}
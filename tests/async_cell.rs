@@ -0,0 +1,181 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_lock::AsyncCell;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// A clock that only moves when told to.
+fn mock_clock() -> (Arc<AtomicU64>, impl Fn() -> Duration) {
+    let now = Arc::new(AtomicU64::new(0));
+    let clock = {
+        let now = now.clone();
+        move || Duration::from_secs(now.load(Ordering::SeqCst))
+    };
+    (now, clock)
+}
+
+#[test]
+fn smoke() {
+    future::block_on(async {
+        let cell = AsyncCell::new();
+        assert_eq!(cell.get(), None);
+        assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+        assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 1);
+
+        cell.invalidate();
+        assert_eq!(cell.get(), None);
+        assert_eq!(*cell.get_or_refresh(|| async { 3 }).await, 3);
+    });
+}
+
+#[test]
+fn coalesced_refresh() {
+    let calls = AtomicUsize::new(0);
+
+    future::block_on(async {
+        let cell = AsyncCell::new();
+        let refresh = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            future::yield_now().await;
+            calls.load(Ordering::SeqCst)
+        };
+
+        let (a, b) = future::zip(cell.get_or_refresh(refresh), cell.get_or_refresh(refresh)).await;
+        assert_eq!((*a, *b), (1, 1));
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn failed_refresh() {
+    future::block_on(async {
+        let cell = AsyncCell::new();
+        assert_eq!(cell.get_or_try_refresh(|| async { Err(()) }).await, Err(()));
+        assert_eq!(cell.get(), None);
+
+        let value = cell.get_or_try_refresh(|| async { Ok::<_, ()>(1) }).await;
+        assert_eq!(value.as_deref(), Ok(&1));
+    });
+}
+
+#[test]
+fn cancelled_refresh() {
+    future::block_on(async {
+        let cell = AsyncCell::new();
+        let refresh = cell.get_or_refresh(|| async {
+            future::yield_now().await;
+            1
+        });
+        assert!(future::poll_once(Box::pin(refresh)).await.is_none());
+
+        assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 2);
+    });
+}
+
+#[test]
+fn ttl() {
+    let (now, clock) = mock_clock();
+
+    future::block_on(async {
+        let cell = AsyncCell::with_clock(Duration::from_secs(10), clock);
+        assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+
+        now.store(9, Ordering::SeqCst);
+        assert_eq!(cell.get().as_deref(), Some(&1));
+        assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 1);
+
+        now.store(10, Ordering::SeqCst);
+        assert_eq!(cell.get(), None);
+        assert_eq!(*cell.get_or_refresh(|| async { 3 }).await, 3);
+
+        // The time-to-live counts from the refresh.
+        now.store(19, Ordering::SeqCst);
+        assert_eq!(cell.get().as_deref(), Some(&3));
+    });
+}
+
+#[test]
+fn stale_while_revalidate() {
+    let (now, clock) = mock_clock();
+
+    future::block_on(async {
+        let cell = AsyncCell::with_clock(Duration::from_secs(10), clock).stale_while_revalidate();
+        assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+        now.store(10, Ordering::SeqCst);
+
+        // The first task to notice refreshes the value...
+        let mut refresh = Box::pin(cell.get_or_refresh(|| async {
+            future::yield_now().await;
+            2
+        }));
+        assert!(future::poll_once(refresh.as_mut()).await.is_none());
+
+        // ...while the others get the stale one.
+        assert_eq!(*cell.get_or_refresh(|| async { 3 }).await, 1);
+        assert_eq!(*refresh.await, 2);
+        assert_eq!(*cell.get_or_refresh(|| async { 3 }).await, 2);
+
+        // Invalidated values are never served.
+        now.store(20, Ordering::SeqCst);
+        let mut refresh = Box::pin(cell.get_or_refresh(|| async {
+            future::yield_now().await;
+            4
+        }));
+        assert!(future::poll_once(refresh.as_mut()).await.is_none());
+        cell.invalidate();
+
+        let mut other = Box::pin(cell.get_or_refresh(|| async {
+            future::yield_now().await;
+            5
+        }));
+        assert!(future::poll_once(other.as_mut()).await.is_none());
+        assert_eq!(*refresh.await, 4);
+        assert_eq!(*other.await, 5);
+        assert_eq!(cell.get().as_deref(), Some(&5));
+    });
+}
+
+#[test]
+fn expired_value_dropped() {
+    let (now, clock) = mock_clock();
+
+    future::block_on(async {
+        let cell = AsyncCell::with_clock(Duration::from_secs(10), clock);
+        let first = cell.get_or_refresh(|| async { 1 }).await;
+        now.store(10, Ordering::SeqCst);
+        assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 2);
+        assert_eq!(Arc::strong_count(&first), 1);
+    });
+
+    // Stale values are only kept until the refresh completes.
+    let (now, clock) = mock_clock();
+
+    future::block_on(async {
+        let cell = AsyncCell::with_clock(Duration::from_secs(10), clock).stale_while_revalidate();
+        let first = cell.get_or_refresh(|| async { 1 }).await;
+        now.store(10, Ordering::SeqCst);
+        assert_eq!(*cell.get_or_refresh(|| async { 2 }).await, 2);
+        assert_eq!(Arc::strong_count(&first), 1);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn with_ttl() {
+    future::block_on(async {
+        let cell = AsyncCell::with_ttl(Duration::from_secs(60));
+        assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+        assert_eq!(cell.get().as_deref(), Some(&1));
+
+        let cell = AsyncCell::with_ttl(Duration::ZERO);
+        assert_eq!(*cell.get_or_refresh(|| async { 1 }).await, 1);
+        assert_eq!(cell.get(), None);
+    });
+}
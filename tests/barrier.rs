@@ -1,8 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 
 use async_lock::Barrier;
-use futures_lite::future;
+use futures_lite::{future, pin};
 
 #[test]
 #[cfg_attr(miri, ignore)]
@@ -86,3 +87,475 @@ fn smoke_blocking() {
         }
     });
 }
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn wait_blocking_timeout() {
+    use std::time::{Duration, Instant};
+
+    let barrier = Arc::new(Barrier::new(2));
+
+    // Expired waiters are no longer counted.
+    assert!(barrier
+        .wait_blocking_timeout(Duration::from_millis(10))
+        .is_none());
+    assert!(barrier
+        .wait_blocking_deadline(Instant::now() + Duration::from_millis(10))
+        .is_none());
+
+    let b = barrier.clone();
+    let handle = thread::spawn(move || b.wait_blocking_timeout(Duration::from_secs(60)));
+    let res = barrier
+        .wait_blocking_timeout(Duration::from_secs(60))
+        .unwrap();
+    let other = handle.join().unwrap().unwrap();
+    assert_ne!(res.is_leader(), other.is_leader());
+}
+
+#[test]
+fn cancellation() {
+    future::block_on(async {
+        let barrier = Barrier::new(2);
+
+        // Cancelled before arriving.
+        drop(barrier.wait());
+
+        // Cancelled while waiting for the others.
+        {
+            let wait = barrier.wait();
+            pin!(wait);
+            assert!(future::poll_once(wait.as_mut()).await.is_none());
+        }
+
+        // Neither arrival is counted.
+        let mut wait = Box::pin(barrier.wait());
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+
+        // Cancelled after the barrier was released, so the arrival still counts.
+        assert!(barrier.wait().await.is_leader());
+        drop(wait);
+
+        let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+        assert!(!a.is_leader());
+        assert!(b.is_leader());
+
+        // Dropped after completing.
+        let mut wait = Box::pin(barrier.wait());
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        assert!(barrier.wait().await.is_leader());
+        assert!(!wait.await.is_leader());
+
+        let wait = barrier.wait();
+        pin!(wait);
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        assert!(barrier.wait().await.is_leader());
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cancellation_stress() {
+    const N: usize = 4;
+    const ROUNDS: usize = 200;
+
+    let barrier = Arc::new(Barrier::new(N));
+    let sync = Arc::new(std::sync::Barrier::new(N));
+    let arrived = Arc::new(AtomicUsize::new(0));
+    let released = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..N)
+        .map(|i| {
+            let barrier = barrier.clone();
+            let sync = sync.clone();
+            let arrived = arrived.clone();
+            let released = released.clone();
+
+            thread::spawn(move || {
+                for round in 0..ROUNDS {
+                    // Everyone but one thread arrives and gives up, which must not count.
+                    if i != 0 {
+                        let wait = barrier.wait();
+                        pin!(wait);
+                        assert!(future::block_on(future::poll_once(wait.as_mut())).is_none());
+                        thread::yield_now();
+                    }
+                    sync.wait();
+
+                    arrived.fetch_add(1, Ordering::SeqCst);
+                    let wait = barrier.wait();
+                    pin!(wait);
+                    loop {
+                        match future::block_on(future::poll_once(wait.as_mut())) {
+                            Some(res) => {
+                                if res.is_leader() {
+                                    released.fetch_add(1, Ordering::SeqCst);
+                                }
+                                break;
+                            }
+
+                            // Give up once the barrier is released, while woken up or not.
+                            None if released.load(Ordering::SeqCst) > round => break,
+                            None => thread::yield_now(),
+                        }
+                    }
+
+                    // The barrier isn't released before everyone arrived.
+                    assert!(arrived.load(Ordering::SeqCst) >= N * (round + 1));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(released.load(Ordering::SeqCst), ROUNDS);
+}
+
+#[test]
+fn dynamic_parties() {
+    future::block_on(async {
+        let barrier = Barrier::new(1);
+        assert_eq!(barrier.register(), 0);
+
+        // Arriving without waiting.
+        let wait = barrier.wait();
+        pin!(wait);
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        assert_eq!(barrier.arrive(), 0);
+        assert!(!wait.await.is_leader());
+        assert_eq!(barrier.await_phase(0).await, 1);
+
+        // A new party is waited for in the current phase.
+        let wait = barrier.wait();
+        pin!(wait);
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        assert_eq!(barrier.register(), 1);
+        assert_eq!(barrier.arrive(), 1);
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        let await_phase = barrier.await_phase(1);
+        pin!(await_phase);
+        assert!(future::poll_once(await_phase.as_mut()).await.is_none());
+
+        // A leaving party releases the others if they all arrived.
+        assert_eq!(barrier.arrive_and_deregister(), 1);
+        assert!(!wait.await.is_leader());
+        assert_eq!(await_phase.await, 2);
+
+        // Two parties are left.
+        assert_eq!(barrier.arrive(), 2);
+        assert!(barrier.wait().await.is_leader());
+        assert_eq!(barrier.await_phase(0).await, 3);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn dynamic_parties_threads() {
+    const WORKERS: usize = 8;
+    const ROUNDS: usize = 100;
+
+    // Workers leave at different times without holding up the others.
+    let barrier = Arc::new(Barrier::new(1));
+    let handles = (0..WORKERS)
+        .map(|i| {
+            barrier.register();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                for _ in 0..ROUNDS * (i + 1) / WORKERS {
+                    barrier.wait_blocking();
+                }
+                barrier.arrive_and_deregister();
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let mut phase = 0;
+    for _ in 0..ROUNDS {
+        phase = barrier.await_phase_blocking(barrier.arrive());
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(phase, ROUNDS as u64);
+}
+
+#[test]
+fn completion() {
+    future::block_on(async {
+        let runs = AtomicUsize::new(0);
+        let barrier = Barrier::with_sync_completion(2, || runs.fetch_add(1, Ordering::SeqCst));
+
+        for phase in 0..3 {
+            let (a, b) = future::zip(barrier.wait(), barrier.wait()).await;
+            assert_ne!(a.is_leader(), b.is_leader());
+            assert_eq!(*a.output(), phase);
+            assert_eq!(b.into_output(), phase);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[test]
+fn completion_slow_reader() {
+    future::block_on(async {
+        let runs = AtomicUsize::new(0);
+        let barrier =
+            Barrier::with_completion(2, || future::ready(runs.fetch_add(1, Ordering::SeqCst)));
+
+        let a = barrier.wait();
+        pin!(a);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(barrier.wait().await.is_leader());
+
+        // Extra tasks go through the next phases before the released task looks at its output.
+        for phase in 1..4 {
+            let (b, c) = future::zip(barrier.wait(), barrier.wait()).await;
+            assert_eq!(*b.output(), phase);
+            assert_eq!(*c.output(), phase);
+        }
+        assert_eq!(*a.await.output(), 0);
+    });
+}
+
+#[test]
+fn completion_async() {
+    future::block_on(async {
+        let (tx, rx) = flume::unbounded::<&str>();
+        let barrier = Barrier::with_completion(2, || {
+            let rx = rx.clone();
+            async move { rx.recv_async().await.unwrap() }
+        });
+
+        let a = barrier.wait();
+        let b = barrier.wait();
+        let c = barrier.wait();
+        pin!(a, b, c);
+
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // The completion holds everyone, including tasks arriving for the next phase.
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(c.as_mut()).await.is_none());
+
+        tx.send("swapped").unwrap();
+        let b = b.await;
+        assert!(b.is_leader());
+        assert_eq!(*b.output(), "swapped");
+
+        let a = a.await;
+        assert!(!a.is_leader());
+        assert_eq!(*a.output(), "swapped");
+
+        // The held task arrived at the next phase.
+        assert!(future::poll_once(c.as_mut()).await.is_none());
+        tx.send("again").unwrap();
+        let (c, d) = future::zip(c, barrier.wait()).await;
+        assert_eq!(*c.output(), "again");
+        assert_eq!(*d.output(), "again");
+    });
+}
+
+#[test]
+fn completion_cancellation() {
+    future::block_on(async {
+        let (tx, rx) = flume::unbounded::<usize>();
+        let barrier = Barrier::with_completion(2, || {
+            let rx = rx.clone();
+            async move { rx.recv_async().await.unwrap() }
+        });
+
+        let a = barrier.wait();
+        pin!(a);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+
+        // The leader gives up while running the completion.
+        let mut b = Box::pin(barrier.wait());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+        drop(b);
+
+        // Another task takes its place and runs the completion again.
+        let c = barrier.wait();
+        pin!(c);
+        assert!(future::poll_once(c.as_mut()).await.is_none());
+
+        tx.send(1).unwrap();
+        let (a, c) = future::zip(a, c).await;
+        assert!(c.is_leader());
+        assert_eq!(*a.output(), 1);
+        assert_eq!(*c.output(), 1);
+    });
+}
+
+#[test]
+fn completion_cancellation_after_arrival() {
+    future::block_on(async {
+        let (tx, rx) = flume::unbounded::<usize>();
+        let barrier = Barrier::with_completion(2, || {
+            let rx = rx.clone();
+            async move { rx.recv_async().await.unwrap() }
+        });
+
+        let mut a = Box::pin(barrier.wait());
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        let mut b = Box::pin(barrier.wait());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // The first task stays counted once the leader started the completion.
+        drop(a);
+        drop(b);
+
+        let c = barrier.wait();
+        tx.send(1).unwrap();
+        let c = c.await;
+        assert!(c.is_leader());
+        assert_eq!(*c.output(), 1);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn completion_blocking() {
+    const N: usize = 4;
+
+    let (tx, rx) = flume::unbounded::<usize>();
+    let barrier = Arc::new(Barrier::with_completion(N, move || {
+        let rx = rx.clone();
+        async move { rx.recv_async().await.unwrap() }
+    }));
+
+    let handles = (0..N - 1)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || barrier.wait_blocking())
+        })
+        .collect::<Vec<_>>();
+
+    tx.send(5).unwrap();
+    let result = barrier.wait_blocking();
+
+    let leaders = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .chain(Some(result))
+        .inspect(|result| assert_eq!(*result.output(), 5))
+        .filter(|result| result.is_leader())
+        .count();
+    assert_eq!(leaders, 1);
+
+    // A leader running out of time gives up on the completion.
+    let barrier = Barrier::with_completion(1, future::pending::<()>);
+    assert!(barrier
+        .wait_blocking_timeout(std::time::Duration::from_millis(10))
+        .is_none());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention() {
+    const N: usize = 8;
+    const ROUNDS: usize = 500;
+
+    let phase = Arc::new(AtomicUsize::new(0));
+    let leaders = Arc::new(AtomicUsize::new(0));
+    let barrier = Arc::new(Barrier::with_completion(N, {
+        let phase = phase.clone();
+        move || future::ready(phase.fetch_add(1, Ordering::SeqCst))
+    }));
+
+    let handles = (0..N)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let leaders = leaders.clone();
+
+            thread::spawn(move || {
+                future::block_on(async {
+                    for round in 0..ROUNDS {
+                        let result = barrier.wait().await;
+                        assert_eq!(*result.output(), round);
+                        if result.is_leader() {
+                            leaders.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(phase.load(Ordering::SeqCst), ROUNDS);
+    assert_eq!(leaders.load(Ordering::SeqCst), ROUNDS);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention_blocking() {
+    const N: usize = 8;
+    const ROUNDS: usize = 500;
+
+    let barrier = Arc::new(Barrier::new(N));
+    let leaders = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..N)
+        .map(|i| {
+            let barrier = barrier.clone();
+            let leaders = leaders.clone();
+
+            thread::spawn(move || {
+                for _ in 0..ROUNDS {
+                    // Mix blocking and asynchronous waits.
+                    let result = if i % 2 == 0 {
+                        barrier.wait_blocking()
+                    } else {
+                        future::block_on(barrier.wait())
+                    };
+
+                    if result.is_leader() {
+                        leaders.fetch_add(1, Ordering::SeqCst);
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(leaders.load(Ordering::SeqCst), ROUNDS);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention_dynamic_parties() {
+    const N: usize = 8;
+    const ROUNDS: u64 = 200;
+
+    let barrier = Arc::new(Barrier::new(N));
+
+    let handles = (0..N as u64)
+        .map(|i| {
+            let barrier = barrier.clone();
+
+            thread::spawn(move || {
+                // Each party leaves after a different number of phases.
+                for _ in 0..ROUNDS + i * 10 {
+                    let phase = barrier.arrive();
+                    assert_eq!(barrier.await_phase_blocking(phase), phase + 1);
+                }
+                barrier.arrive_and_deregister();
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
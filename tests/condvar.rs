@@ -0,0 +1,222 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+
+use async_lock::{Condvar, Mutex};
+use futures_lite::{future, pin};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn notify_one_wakes_one() {
+    future::block_on(NotifyOneTest);
+}
+
+struct NotifyOneTest;
+
+impl Future for NotifyOneTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::new(());
+        let cvar = Condvar::new();
+
+        let w1 = cvar.wait(m.try_lock().unwrap());
+        pin!(w1);
+        assert!(w1.as_mut().poll(cx).is_pending());
+
+        // The mutex is released while waiting.
+        let w2 = cvar.wait(m.try_lock().unwrap());
+        pin!(w2);
+        assert!(w2.as_mut().poll(cx).is_pending());
+
+        cvar.notify_one();
+        let g1 = match w1.as_mut().poll(cx) {
+            Poll::Ready(g) => g,
+            Poll::Pending => panic!("first waiter was not woken"),
+        };
+        assert!(w2.as_mut().poll(cx).is_pending());
+
+        // The second waiter has to wait for the mutex as well.
+        cvar.notify_one();
+        assert!(w2.as_mut().poll(cx).is_pending());
+        drop(g1);
+        assert!(w2.poll(cx).is_ready());
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn notify_all_wakes_all() {
+    future::block_on(NotifyAllTest);
+}
+
+struct NotifyAllTest;
+
+impl Future for NotifyAllTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Arc::new(Mutex::new(0));
+        let cvar = Condvar::new();
+
+        let w1 = cvar.wait_arc(m.try_lock_arc().unwrap());
+        pin!(w1);
+        assert!(w1.as_mut().poll(cx).is_pending());
+
+        let w2 = cvar.wait_while_arc(m.try_lock_arc().unwrap(), |n| *n == 0);
+        pin!(w2);
+        assert!(w2.as_mut().poll(cx).is_pending());
+
+        *m.try_lock().unwrap() = 1;
+        cvar.notify_all();
+
+        let g1 = match w1.poll(cx) {
+            Poll::Ready(g) => g,
+            Poll::Pending => panic!("first waiter was not woken"),
+        };
+        drop(g1);
+        assert!(w2.poll(cx).is_ready());
+        assert_eq!(Arc::strong_count(&m), 1);
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn wait_while_checks_first() {
+    future::block_on(async {
+        let m = Mutex::new(5);
+        let cvar = Condvar::new();
+
+        let guard = cvar.wait_while(m.lock().await, |n| *n < 5).await;
+        assert_eq!(*guard, 5);
+    });
+}
+
+#[test]
+fn cancellation() {
+    future::block_on(CancellationTest);
+}
+
+struct CancellationTest;
+
+impl Future for CancellationTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::new(());
+        let cvar = Condvar::new();
+
+        // Dropping an unpolled future releases the lock.
+        drop(cvar.wait(m.try_lock().unwrap()));
+        assert!(m.try_lock().is_some());
+
+        // Dropping a notified waiter passes the notification on.
+        let mut w1 = Box::pin(cvar.wait(m.try_lock().unwrap()));
+        assert!(w1.as_mut().poll(cx).is_pending());
+        let w2 = cvar.wait(m.try_lock().unwrap());
+        pin!(w2);
+        assert!(w2.as_mut().poll(cx).is_pending());
+
+        cvar.notify_one();
+        drop(w1);
+        assert!(w2.as_mut().poll(cx).is_ready());
+
+        Poll::Ready(())
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn producer_consumer() {
+    const COUNT: usize = 1_000;
+
+    let state = Arc::new((Mutex::new(Vec::new()), Condvar::new()));
+
+    let consumers = (0..4)
+        .map(|_| {
+            let state = state.clone();
+            thread::spawn(move || {
+                future::block_on(async {
+                    let (queue, cvar) = &*state;
+                    let mut received = 0;
+
+                    loop {
+                        let mut queue = cvar.wait_while(queue.lock().await, |q| q.is_empty()).await;
+                        match queue.pop() {
+                            Some(Some(_)) => received += 1,
+                            _ => return received,
+                        }
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    future::block_on(async {
+        let (queue, cvar) = &*state;
+
+        for i in 0..COUNT {
+            queue.lock().await.insert(0, Some(i));
+            cvar.notify_one();
+        }
+
+        let mut queue = queue.lock().await;
+        for _ in 0..4 {
+            queue.insert(0, None);
+        }
+        cvar.notify_all();
+    });
+
+    let received: usize = consumers.into_iter().map(|t| t.join().unwrap()).sum();
+    assert_eq!(received, COUNT);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn smoke_blocking() {
+    let state = Arc::new((Mutex::new(false), Condvar::new()));
+    let state2 = state.clone();
+
+    let t = thread::spawn(move || {
+        let (m, cvar) = &*state2;
+        let guard = cvar.wait_while_blocking(m.lock_blocking(), |ready| !*ready);
+        assert!(*guard);
+    });
+
+    let (m, cvar) = &*state;
+    *m.lock_blocking() = true;
+    cvar.notify_all();
+    t.join().unwrap();
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn smoke_arc_blocking() {
+    let m = Arc::new(Mutex::new(false));
+    let cvar = Arc::new(Condvar::new());
+    let (m2, cvar2) = (m.clone(), cvar.clone());
+
+    let t = thread::spawn(move || {
+        let mut guard = m2.lock_arc_blocking();
+        while !*guard {
+            guard = cvar2.wait_arc_blocking(guard);
+        }
+        let guard = cvar2.wait_while_arc_blocking(guard, |_| false);
+        assert!(*guard);
+    });
+
+    *m.lock_blocking() = true;
+    cvar.notify_one();
+    t.join().unwrap();
+}
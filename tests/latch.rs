@@ -0,0 +1,77 @@
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::Arc;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::thread;
+
+use async_lock::Latch;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn smoke() {
+    future::block_on(async {
+        let latch = Latch::new(2);
+        let mut wait = Box::pin(latch.wait());
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+
+        latch.count_down();
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        latch.count_down();
+        wait.await;
+    });
+}
+
+#[test]
+fn stays_open() {
+    future::block_on(async {
+        let latch = Latch::new(0);
+        assert!(latch.is_open());
+        latch.wait().await;
+
+        latch.count_down();
+        assert_eq!(latch.count(), 0);
+        latch.wait().await;
+    });
+}
+
+#[test]
+fn many_waiters() {
+    future::block_on(async {
+        let latch = Latch::new(1);
+        let waiters = future::zip(latch.wait(), latch.wait());
+        let open = async {
+            future::yield_now().await;
+            latch.count_down();
+        };
+        future::zip(waiters, open).await;
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn threads() {
+    let latch = Arc::new(Latch::new(10));
+
+    let waiters = (0..4)
+        .map(|_| {
+            let latch = latch.clone();
+            thread::spawn(move || latch.wait_blocking())
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..10 {
+        let latch = latch.clone();
+        thread::spawn(move || latch.count_down());
+    }
+
+    for waiter in waiters {
+        waiter.join().unwrap();
+    }
+    assert!(latch.is_open());
+}
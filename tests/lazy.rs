@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::Arc;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::thread;
+
+use async_lock::{Lazy, TryLazy};
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn smoke() {
+    static VALUE: Lazy<usize> = Lazy::new(|| Box::pin(async { 5 }));
+
+    future::block_on(async {
+        assert_eq!(VALUE.get(), None);
+        assert_eq!(VALUE.force().await, &5);
+        assert_eq!(VALUE.get(), Some(&5));
+    });
+}
+
+#[test]
+fn initializes_once() {
+    let calls = AtomicUsize::new(0);
+    let lazy = Lazy::new(|| async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        future::yield_now().await;
+        1
+    });
+
+    future::block_on(async {
+        let (a, b) = future::zip(lazy.force(), lazy.force()).await;
+        assert_eq!((a, b), (&1, &1));
+        assert_eq!(lazy.force().await, &1);
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn cancellation() {
+    let calls = AtomicUsize::new(0);
+    let lazy = Lazy::new(|| async {
+        calls.fetch_add(1, Ordering::SeqCst);
+        future::yield_now().await;
+        1
+    });
+
+    future::block_on(async {
+        // Dropping the initializing task leaves the value uninitialized.
+        assert!(future::poll_once(Box::pin(lazy.force())).await.is_none());
+        assert_eq!(lazy.get(), None);
+
+        // The next caller runs the initializer again.
+        assert_eq!(lazy.force().await, &1);
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn try_lazy() {
+    let calls = AtomicUsize::new(0);
+    let lazy = TryLazy::new(|| async {
+        match calls.fetch_add(1, Ordering::SeqCst) {
+            0 => Err("failed"),
+            n => Ok(n),
+        }
+    });
+
+    future::block_on(async {
+        assert_eq!(lazy.force().await, Err("failed"));
+        assert_eq!(lazy.get(), None);
+        assert_eq!(lazy.force().await, Ok(&1));
+        assert_eq!(lazy.force().await, Ok(&1));
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn blocking() {
+    let lazy = TryLazy::new(|| async {
+        future::yield_now().await;
+        "1".parse::<i32>()
+    });
+    assert_eq!(lazy.force_blocking(), Ok(&1));
+
+    let lazy = Lazy::new(|| async {
+        future::yield_now().await;
+        1
+    });
+    assert_eq!(lazy.force_blocking(), &1);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let lazy = Arc::new(Lazy::new({
+        let calls = calls.clone();
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async {
+                future::yield_now().await;
+                7
+            }
+        }
+    }));
+
+    let handles = (0..8)
+        .map(|i| {
+            let lazy = lazy.clone();
+            thread::spawn(move || {
+                if i % 2 == 0 {
+                    *lazy.force_blocking()
+                } else {
+                    *future::block_on(lazy.force())
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 7);
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
@@ -1,11 +1,16 @@
 mod common;
 
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 #[cfg(not(target_family = "wasm"))]
 use std::thread;
 
-use async_lock::Mutex;
-use futures_lite::future;
+use async_lock::{
+    Fairness, MappedMutexGuard, MappedMutexGuardArc, Mutex, MutexGuard, MutexGuardArc,
+};
+use futures_lite::{future, pin};
 
 use common::check_yields_when_contended;
 
@@ -40,6 +45,28 @@ fn smoke_arc_blocking() {
     drop(m.lock_arc_blocking());
 }
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn lock_blocking_timeout() {
+    use std::time::{Duration, Instant};
+
+    let m = Mutex::new(());
+    let guard = m.lock_blocking_timeout(Duration::from_millis(10)).unwrap();
+    assert!(m.lock_blocking_timeout(Duration::from_millis(10)).is_none());
+    assert!(m
+        .lock_blocking_deadline(Instant::now() + Duration::from_millis(10))
+        .is_none());
+    drop(guard);
+    assert!(m.try_lock().is_some());
+
+    // Starved lock operations stop being counted once they expire.
+    let m = Mutex::with_fairness((), Fairness::fifo());
+    let guard = m.lock_blocking();
+    assert!(m.lock_blocking_timeout(Duration::from_millis(10)).is_none());
+    drop(guard);
+    assert!(m.try_lock().is_some());
+}
+
 #[test]
 fn try_lock() {
     let m = Mutex::new(());
@@ -109,3 +136,248 @@ fn yields_when_contended() {
     let m = Arc::new(m);
     check_yields_when_contended(m.try_lock_arc().unwrap(), m.lock_arc());
 }
+
+#[test]
+fn map() {
+    future::block_on(async {
+        let m = Mutex::new((1i32, 2i32));
+
+        let mut second = MutexGuard::map(m.lock().await, |pair| &mut pair.1);
+        *second += 10;
+        assert!(m.try_lock().is_none());
+        drop(second);
+
+        let guard = MutexGuard::try_map(m.lock().await, |_| None::<&mut i32>).unwrap_err();
+        assert_eq!(*guard, (1, 12));
+        let first = MutexGuard::try_map(guard, |pair| Some(&mut pair.0)).unwrap();
+        let first = MappedMutexGuard::map(first, |x| x);
+        assert_eq!(*first, 1);
+        drop(first);
+
+        assert!(m.try_lock().is_some());
+    });
+}
+
+#[test]
+fn map_arc() {
+    future::block_on(async {
+        let m = Arc::new(Mutex::new((1i32, 2i32)));
+
+        let mut second = MutexGuardArc::map(m.lock_arc().await, |pair| &mut pair.1);
+        *second += 10;
+        assert!(m.try_lock().is_none());
+        assert_eq!(Arc::strong_count(&m), 2);
+        drop(second);
+        assert_eq!(Arc::strong_count(&m), 1);
+
+        let guard = MutexGuardArc::try_map(m.lock_arc().await, |_| None::<&mut i32>).unwrap_err();
+        assert_eq!(*guard, (1, 12));
+        let first = MutexGuardArc::try_map(guard, |pair| Some(&mut pair.0)).unwrap();
+        let first = MappedMutexGuardArc::try_map(first, |x| Some(x)).unwrap();
+        assert_eq!(*first, 1);
+        drop(first);
+
+        assert!(m.try_lock().is_some());
+        assert_eq!(Arc::strong_count(&m), 1);
+    });
+}
+
+#[test]
+fn map_yields_when_contended() {
+    let m = Mutex::new((0i32, 0i32));
+    check_yields_when_contended(
+        MutexGuard::map(m.try_lock().unwrap(), |p| &mut p.0),
+        m.lock(),
+    );
+
+    let m = Arc::new(m);
+    check_yields_when_contended(
+        MutexGuardArc::map(m.try_lock_arc().unwrap(), |p| &mut p.1),
+        m.lock_arc(),
+    );
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fairness_contention() {
+    for fairness in [
+        Fairness::default(),
+        Fairness::eventual_attempts(1),
+        Fairness::fifo(),
+        Fairness::barging(),
+    ] {
+        let mutex = Arc::new(Mutex::with_fairness(0i32, fairness));
+        let num_threads = 10;
+        let num_iters = 100;
+
+        let handles = (0..num_threads)
+            .map(|_| {
+                let mutex = mutex.clone();
+                thread::spawn(move || {
+                    future::block_on(async {
+                        for _ in 0..num_iters {
+                            *mutex.lock().await += 1;
+                        }
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(*mutex.try_lock().unwrap(), num_threads * num_iters);
+    }
+}
+
+#[test]
+fn fairness_fifo() {
+    future::block_on(FifoTest);
+}
+
+struct FifoTest;
+
+impl Future for FifoTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::with_fairness((), Fairness::fifo());
+        let guard = m.try_lock().unwrap();
+
+        let l1 = m.lock();
+        pin!(l1);
+        assert!(l1.as_mut().poll(cx).is_pending());
+        let l2 = m.lock();
+        pin!(l2);
+        assert!(l2.as_mut().poll(cx).is_pending());
+
+        // The released lock is handed to the first waiter, nobody can barge in.
+        drop(guard);
+        assert!(m.try_lock().is_none());
+        assert!(l2.as_mut().poll(cx).is_pending());
+        let guard = match l1.as_mut().poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => panic!("first waiter didn't get the lock"),
+        };
+
+        drop(guard);
+        assert!(m.try_lock().is_none());
+        assert!(l2.as_mut().poll(cx).is_ready());
+        assert!(m.try_lock().is_some());
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn fairness_fifo_cancelled() {
+    future::block_on(FifoCancelledTest);
+}
+
+struct FifoCancelledTest;
+
+impl Future for FifoCancelledTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::with_fairness((), Fairness::fifo());
+        let guard = m.try_lock().unwrap();
+
+        let mut l1 = Box::pin(m.lock());
+        assert!(l1.as_mut().poll(cx).is_pending());
+        let mut l2 = Box::pin(m.lock());
+        assert!(l2.as_mut().poll(cx).is_pending());
+
+        // The lock handed to a cancelled waiter goes to the next one.
+        drop(guard);
+        drop(l1);
+        assert!(m.try_lock().is_none());
+        let guard = match l2.as_mut().poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => panic!("second waiter didn't get the lock"),
+        };
+
+        // With nobody left to take it, the lock is released.
+        let mut l3 = Box::pin(m.lock());
+        assert!(l3.as_mut().poll(cx).is_pending());
+        drop(guard);
+        drop(l3);
+        assert!(m.try_lock().is_some());
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn fairness_barging() {
+    future::block_on(BargingTest);
+}
+
+struct BargingTest;
+
+impl Future for BargingTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::with_fairness((), Fairness::barging());
+        let mut guard = m.try_lock().unwrap();
+
+        let l1 = m.lock();
+        pin!(l1);
+        assert!(l1.as_mut().poll(cx).is_pending());
+
+        // The waiter never stops newer lock operations from taking the lock.
+        for _ in 0..10 {
+            drop(guard);
+            guard = m.try_lock().unwrap();
+            assert!(l1.as_mut().poll(cx).is_pending());
+        }
+
+        drop(guard);
+        assert!(l1.as_mut().poll(cx).is_ready());
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn fairness_eventual_attempts() {
+    future::block_on(EventualAttemptsTest);
+}
+
+struct EventualAttemptsTest;
+
+impl Future for EventualAttemptsTest {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let m = Mutex::with_fairness((), Fairness::eventual_attempts(2));
+        let mut guard = m.try_lock().unwrap();
+
+        let l1 = m.lock();
+        pin!(l1);
+        assert!(l1.as_mut().poll(cx).is_pending());
+
+        // Newer lock operations can barge in until the waiter is starved.
+        for _ in 0..2 {
+            drop(guard);
+            guard = m.try_lock().unwrap();
+            assert!(l1.as_mut().poll(cx).is_pending());
+        }
+
+        drop(guard);
+        assert!(m.try_lock().is_none());
+        let guard = match l1.as_mut().poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => panic!("starved waiter didn't get the lock"),
+        };
+
+        // Once the starved waiter is done, barging is allowed again.
+        drop(guard);
+        assert!(m.try_lock().is_some());
+
+        Poll::Ready(())
+    }
+}
@@ -0,0 +1,161 @@
+#[cfg(not(target_family = "wasm"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(target_family = "wasm"))]
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+
+use async_lock::Notify;
+use futures_lite::{future, pin};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn stored_permit() {
+    future::block_on(async {
+        let notify = Notify::new();
+        assert!(future::poll_once(notify.notified()).await.is_none());
+
+        notify.notify_one();
+        notify.notify_one();
+        notify.notified().await;
+        assert!(future::poll_once(notify.notified()).await.is_none());
+    });
+}
+
+#[test]
+fn notify_one_wakes_each_waiter() {
+    future::block_on(async {
+        let notify = Notify::new();
+        let a = notify.notified();
+        let b = notify.notified();
+        pin!(a, b);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        notify.notify_one();
+        notify.notify_one();
+        future::zip(a, b).await;
+
+        // Nothing is left over.
+        assert!(future::poll_once(notify.notified()).await.is_none());
+    });
+}
+
+#[test]
+fn notify_waiters() {
+    future::block_on(async {
+        let notify = Notify::new();
+        let a = notify.notified();
+        let b = notify.notified();
+        pin!(b);
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // Futures wait from the moment they are created.
+        notify.notify_waiters();
+        future::zip(a, b).await;
+
+        // No permit is stored.
+        assert!(future::poll_once(notify.notified()).await.is_none());
+    });
+}
+
+#[test]
+fn enable() {
+    future::block_on(async {
+        let notify = Notify::new();
+        let notified = notify.notified();
+        pin!(notified);
+        notified.as_mut().enable();
+
+        notify.notify_one();
+        assert!(future::poll_once(notify.notified()).await.is_none());
+        notified.await;
+    });
+}
+
+#[test]
+fn cancelled_waiter() {
+    future::block_on(async {
+        let notify = Notify::new();
+
+        let mut a = Box::pin(notify.notified());
+        let b = notify.notified();
+        pin!(b);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // The notification meant for the cancelled waiter goes to the other one.
+        notify.notify_one();
+        drop(a);
+        b.await;
+
+        // A notification nobody consumed stays stored, but only once.
+        let mut a = Box::pin(notify.notified());
+        let mut b = Box::pin(notify.notified());
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+        notify.notify_one();
+        notify.notify_one();
+        drop(a);
+        drop(b);
+        notify.notified().await;
+        assert!(future::poll_once(notify.notified()).await.is_none());
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn blocking() {
+    let notify = Arc::new(Notify::new());
+    notify.notify_one();
+    notify.notified_blocking();
+
+    let handle = {
+        let notify = notify.clone();
+        thread::spawn(move || notify.notified_blocking())
+    };
+    notify.notify_one();
+    handle.join().unwrap();
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention() {
+    const WAITERS: usize = 8;
+    const ROUNDS: usize = 200;
+
+    let notify = Arc::new(Notify::new());
+    let received = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..WAITERS)
+        .map(|_| {
+            let notify = notify.clone();
+            let received = received.clone();
+            thread::spawn(move || {
+                for _ in 0..ROUNDS {
+                    future::block_on(notify.notified());
+                    received.fetch_add(1, Ordering::SeqCst);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // Every notification wakes up exactly one waiter, though some may get stored for a while.
+    for sent in 1..=WAITERS * ROUNDS {
+        notify.notify_one();
+        while received.load(Ordering::SeqCst) < sent {
+            thread::yield_now();
+        }
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(received.load(Ordering::SeqCst), WAITERS * ROUNDS);
+}
@@ -0,0 +1,163 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+
+use async_lock::futures::{GetOrInit, Wait};
+use async_lock::OnceCell;
+use futures_lite::{future, pin};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// A future that stores the named `OnceCell` futures and polls them by hand.
+struct Both<'a, F, Fut> {
+    init: Pin<Box<GetOrInit<'a, i32, F, Fut>>>,
+    wait: Pin<Box<Wait<'a, i32>>>,
+}
+
+impl<F, Fut> Future for Both<'_, F, Fut>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = i32>,
+{
+    type Output = (i32, i32);
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let init = *std::task::ready!(self.init.as_mut().poll(cx));
+        let wait = *std::task::ready!(self.wait.as_mut().poll(cx));
+        Poll::Ready((init, wait))
+    }
+}
+
+#[test]
+fn named_futures() {
+    future::block_on(async {
+        let cell = OnceCell::new();
+        let both = Both {
+            init: Box::pin(cell.get_or_init(|| async { 1 })),
+            wait: Box::pin(cell.wait()),
+        };
+        assert_eq!(both.await, (1, 1));
+
+        assert_eq!(cell.set(2).await, Err(2));
+        assert_eq!(cell.get_or_try_init(|| async { Err(()) }).await, Ok(&1));
+    });
+}
+
+#[test]
+fn cancelled_initializer() {
+    let calls = AtomicUsize::new(0);
+
+    future::block_on(async {
+        let cell = OnceCell::new();
+
+        let mut init = Box::pin(cell.get_or_init(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            future::yield_now().await;
+            1
+        }));
+        assert!(future::poll_once(init.as_mut()).await.is_none());
+
+        // Another initializer waits for the running one.
+        let other = cell.get_or_init(|| async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            2
+        });
+        pin!(other);
+        assert!(future::poll_once(other.as_mut()).await.is_none());
+
+        // Once the running one is dropped, the other one takes over.
+        drop(init);
+        assert_eq!(other.await, &2);
+    });
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn failed_initializer() {
+    future::block_on(async {
+        let cell = OnceCell::new();
+        assert_eq!(cell.get_or_try_init(|| async { Err(()) }).await, Err(()));
+        assert!(!cell.is_initialized());
+        assert_eq!(cell.set(1).await, Ok(&1));
+    });
+}
+
+#[test]
+fn arc() {
+    future::block_on(async {
+        let cell = Arc::new(OnceCell::new());
+
+        let wait = cell.wait_arc();
+        let set = cell.set_arc(1);
+        drop(cell.clone());
+
+        assert_eq!(*set.await.unwrap(), 1);
+        assert_eq!(*wait.await, 1);
+        assert_eq!(cell.set_arc(2).await.unwrap_err(), 2);
+        assert_eq!(*cell.get_or_init_arc(|| async { 3 }).await, 1);
+        let guard = cell
+            .get_or_try_init_arc(|| async { Err(()) })
+            .await
+            .unwrap();
+        assert_eq!(*guard, 1);
+
+        // The guard keeps the cell alive.
+        drop(cell);
+        assert_eq!(*guard.clone(), 1);
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn arc_static() {
+    let cell = Arc::new(OnceCell::new());
+
+    let waiter = {
+        let wait = cell.wait_arc();
+        thread::spawn(move || *future::block_on(wait))
+    };
+    let setter = {
+        let init = cell.get_or_init_arc(|| async { 5 });
+        thread::spawn(move || *future::block_on(init))
+    };
+
+    assert_eq!(setter.join().unwrap(), 5);
+    assert_eq!(waiter.join().unwrap(), 5);
+    assert_eq!(cell.get(), Some(&5));
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn blocking_waits_for_async_initializer() {
+    let cell = Arc::new(OnceCell::new());
+    let (tx, rx) = flume::bounded::<()>(0);
+
+    let initializer = {
+        let cell = cell.clone();
+        thread::spawn(move || {
+            *future::block_on(cell.get_or_init(|| async move {
+                rx.recv_async().await.unwrap();
+                1
+            }))
+        })
+    };
+
+    // Wait until the initializer is running.
+    while !cell.is_initialized() && tx.try_send(()).is_err() {
+        thread::yield_now();
+    }
+
+    assert_eq!(cell.get_or_init_blocking(|| 2), &1);
+    assert_eq!(cell.wait_blocking(), &1);
+    assert_eq!(initializer.join().unwrap(), 1);
+}
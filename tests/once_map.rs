@@ -0,0 +1,161 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+
+use async_lock::OnceMap;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn smoke() {
+    future::block_on(async {
+        let map = OnceMap::new();
+        assert_eq!(*map.get_or_init(1, || async { "one" }).await, "one");
+        assert_eq!(*map.get_or_init(2, || async { "two" }).await, "two");
+        assert_eq!(*map.get_or_init(1, || async { "uno" }).await, "one");
+        assert_eq!(map.get(&2).as_deref(), Some(&"two"));
+        assert_eq!(map.get(&3), None);
+    });
+}
+
+#[test]
+fn one_initializer_per_key() {
+    let calls = AtomicUsize::new(0);
+
+    future::block_on(async {
+        let map = OnceMap::new();
+        let init = |value| {
+            let calls = &calls;
+            move || async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                future::yield_now().await;
+                value
+            }
+        };
+
+        let (a, b) =
+            future::zip(map.get_or_init("a", init(1)), map.get_or_init("a", init(2))).await;
+        assert_eq!((*a, *b), (1, 1));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other keys don't wait on a running initializer.
+        let mut a = Box::pin(map.get_or_init("b", init(3)));
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert_eq!(*map.get_or_init("c", init(4)).await, 4);
+        assert_eq!(*a.await, 3);
+    });
+}
+
+#[test]
+fn remove() {
+    future::block_on(async {
+        let map = OnceMap::new();
+        let value = map.get_or_init(1, || async { "one" }).await;
+
+        assert_eq!(map.remove(&1), Some(value.clone()));
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(*value, "one");
+        assert_eq!(*map.get_or_init(1, || async { "uno" }).await, "uno");
+
+        // A running initialization completes, but isn't stored.
+        let mut init = Box::pin(map.get_or_init(2, || async {
+            future::yield_now().await;
+            "two"
+        }));
+        assert!(future::poll_once(init.as_mut()).await.is_none());
+        assert_eq!(map.remove(&2), None);
+        assert_eq!(*init.await, "two");
+        assert_eq!(map.get(&2), None);
+    });
+}
+
+#[test]
+fn failed_initializer() {
+    future::block_on(async {
+        let map = OnceMap::new();
+        assert_eq!(
+            map.get_or_try_init(1, || async { Err("failed") }).await,
+            Err("failed")
+        );
+        assert_eq!(map.get(&1), None);
+
+        let value = map.get_or_try_init(1, || async { Ok::<_, ()>(1) }).await;
+        assert_eq!(value.as_deref(), Ok(&1));
+
+        // A waiting task retries after a failure.
+        let (a, b) = future::zip(
+            map.get_or_try_init(2, || async {
+                future::yield_now().await;
+                Err("failed")
+            }),
+            map.get_or_try_init(2, || async { Ok::<_, &str>(2) }),
+        )
+        .await;
+        assert_eq!(a, Err("failed"));
+        assert_eq!(b.as_deref(), Ok(&2));
+    });
+}
+
+#[test]
+fn cancelled_initializer() {
+    future::block_on(async {
+        let map = OnceMap::new();
+        let key = Arc::new(1);
+
+        // The key is removed once the only initialization is cancelled...
+        let mut init = Box::pin(map.get_or_init(key.clone(), future::pending::<i32>));
+        assert!(future::poll_once(init.as_mut()).await.is_none());
+        assert_eq!(Arc::strong_count(&key), 3);
+        drop(init);
+        assert_eq!(Arc::strong_count(&key), 1);
+
+        // ...but kept while another task waits to retry it.
+        let mut init = Box::pin(map.get_or_init(key.clone(), future::pending::<i32>));
+        let mut retry = Box::pin(map.get_or_init(key.clone(), || async { 2 }));
+        assert!(future::poll_once(init.as_mut()).await.is_none());
+        assert!(future::poll_once(retry.as_mut()).await.is_none());
+        drop(init);
+        assert_eq!(*retry.await, 2);
+        assert_eq!(map.get(&key).as_deref(), Some(&2));
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contention() {
+    let map = Arc::new(OnceMap::new());
+    let calls = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..8)
+        .map(|_| {
+            let map = map.clone();
+            let calls = calls.clone();
+            thread::spawn(move || {
+                future::block_on(async {
+                    for key in 0..50 {
+                        let calls = calls.clone();
+                        let value = map
+                            .get_or_init(key, move || async move {
+                                calls.fetch_add(1, Ordering::SeqCst);
+                                key * 2
+                            })
+                            .await;
+                        assert_eq!(*value, key * 2);
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 50);
+}
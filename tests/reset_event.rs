@@ -0,0 +1,107 @@
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::Arc;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::thread;
+
+use async_lock::{AutoResetEvent, ManualResetEvent};
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn manual_reset() {
+    future::block_on(async {
+        let event = ManualResetEvent::new(false);
+        let mut a = Box::pin(event.wait());
+        let mut b = Box::pin(event.wait());
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        event.set();
+        future::zip(a, b).await;
+        assert!(event.is_set());
+
+        event.reset();
+        assert!(!event.is_set());
+        assert!(future::poll_once(event.wait()).await.is_none());
+    });
+}
+
+#[test]
+fn auto_reset() {
+    future::block_on(async {
+        let event = AutoResetEvent::new(false);
+        let mut a = Box::pin(event.wait());
+        let mut b = Box::pin(event.wait());
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // Each set lets one waiter through.
+        event.set();
+        event.set();
+        a.await;
+        assert!(!event.is_set());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        event.set();
+        b.await;
+        assert!(!event.is_set());
+    });
+}
+
+#[test]
+fn auto_reset_cancelled_waiter() {
+    future::block_on(async {
+        let event = AutoResetEvent::new(false);
+        let mut a = Box::pin(event.wait());
+        let mut b = Box::pin(event.wait());
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // The notification goes to the next waiter.
+        event.set();
+        drop(a);
+        b.await;
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn blocking() {
+    let manual = Arc::new(ManualResetEvent::new(false));
+    let auto = Arc::new(AutoResetEvent::new(false));
+    let passed = Arc::new(AtomicUsize::new(0));
+
+    let handles = (0..4)
+        .map(|_| {
+            let manual = manual.clone();
+            let auto = auto.clone();
+            let passed = passed.clone();
+            thread::spawn(move || {
+                manual.wait_blocking();
+                auto.wait_blocking();
+                passed.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+        .collect::<Vec<_>>();
+
+    manual.set();
+    for i in 1..=4 {
+        auto.set();
+        while passed.load(Ordering::SeqCst) < i {
+            thread::yield_now();
+        }
+    }
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(passed.load(Ordering::SeqCst), 4);
+}
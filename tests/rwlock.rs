@@ -13,8 +13,9 @@ use std::thread;
 use futures_lite::future;
 
 use async_lock::{
-    RwLock, RwLockReadGuard, RwLockReadGuardArc, RwLockUpgradableReadGuard,
-    RwLockUpgradableReadGuardArc,
+    MappedRwLockReadGuardArc, MappedRwLockWriteGuardArc, RwLock, RwLockPolicy, RwLockReadGuard,
+    RwLockReadGuardArc, RwLockUpgradableReadGuard, RwLockUpgradableReadGuardArc, RwLockWriteGuard,
+    RwLockWriteGuardArc,
 };
 
 #[cfg(target_family = "wasm")]
@@ -73,6 +74,28 @@ fn smoke_arc_blocking() {
     drop(lock.write_arc_blocking());
 }
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn write_blocking_timeout() {
+    use std::time::{Duration, Instant};
+
+    let lock = RwLock::new(());
+    let reader = lock.read_blocking();
+    assert!(lock
+        .write_blocking_timeout(Duration::from_millis(10))
+        .is_none());
+    assert!(lock
+        .write_blocking_deadline(Instant::now() + Duration::from_millis(10))
+        .is_none());
+
+    // The expired writer doesn't keep new readers out.
+    drop(lock.try_read().unwrap());
+    drop(reader);
+    assert!(lock
+        .write_blocking_timeout(Duration::from_millis(10))
+        .is_some());
+}
+
 #[test]
 fn try_write() {
     future::block_on(async {
@@ -575,7 +598,333 @@ fn arc_rwlock_refcounts() {
     });
 }
 
+#[test]
+fn map() {
+    future::block_on(async {
+        let rw = RwLock::new((1i32, vec![2i32]));
+
+        let first = RwLockReadGuard::map(rw.read().await, |pair| &pair.0);
+        assert_eq!(*first, 1);
+        assert!(rw.try_read().is_some());
+        assert!(rw.try_write().is_none());
+        drop(first);
+
+        let reader = RwLockReadGuard::try_map(rw.read().await, |pair| pair.1.get(1)).unwrap_err();
+        let second = RwLockReadGuard::try_map(reader, |pair| pair.1.first()).unwrap();
+        assert_eq!(*second, 2);
+        drop(second);
+
+        let mut writer = RwLockWriteGuard::map(rw.write().await, |pair| &mut pair.1);
+        writer.push(3);
+        assert!(rw.try_read().is_none());
+        let writer = RwLockWriteGuard::try_map(writer, |v| v.get_mut(5)).unwrap_err();
+        let mut last = RwLockWriteGuard::try_map(writer, |v| v.last_mut()).unwrap();
+        *last += 1;
+
+        let last = RwLockWriteGuard::downgrade(last);
+        assert_eq!(*last, 4);
+        assert!(rw.try_read().is_some());
+        drop(last);
+
+        assert_eq!(*rw.write().await, (1, vec![2, 4]));
+    });
+}
+
+#[test]
+fn map_arc() {
+    future::block_on(async {
+        let rw = Arc::new(RwLock::new((1i32, vec![2i32])));
+
+        let first = RwLockReadGuardArc::map(rw.read_arc().await, |pair| &pair.0);
+        assert_eq!(*first, 1);
+        assert_eq!(Arc::strong_count(&rw), 2);
+        assert!(rw.try_write().is_none());
+        drop(first);
+        assert_eq!(Arc::strong_count(&rw), 1);
+
+        let reader = RwLockReadGuardArc::try_map(rw.read_arc().await, |pair| pair.1.get(1));
+        let reader = reader.unwrap_err();
+        let inner = RwLockReadGuardArc::map(reader, |pair| &pair.1);
+        let inner = MappedRwLockReadGuardArc::try_map(inner, |v| v.get(1)).unwrap_err();
+        let second = MappedRwLockReadGuardArc::map(inner, |v| &v[0]);
+        assert_eq!(*second, 2);
+        drop(second);
+        assert_eq!(Arc::strong_count(&rw), 1);
+
+        let writer = RwLockWriteGuardArc::try_map(rw.write_arc().await, |pair| pair.1.get_mut(1));
+        let writer = writer.unwrap_err();
+        let mut inner = RwLockWriteGuardArc::map(writer, |pair| &mut pair.1);
+        inner.push(3);
+        assert!(rw.try_read().is_none());
+        assert_eq!(Arc::strong_count(&rw), 2);
+        let inner = MappedRwLockWriteGuardArc::try_map(inner, |v| v.get_mut(5)).unwrap_err();
+        let mut last = MappedRwLockWriteGuardArc::map(inner, |v| v.last_mut().unwrap());
+        *last += 1;
+        drop(last);
+        assert_eq!(Arc::strong_count(&rw), 1);
+
+        assert_eq!(*rw.write().await, (1, vec![2, 4]));
+    });
+}
+
 // We are testing that this compiles.
+#[test]
+fn policy_write_preferring() {
+    future::block_on(async {
+        let lock = RwLock::with_policy((), RwLockPolicy::write_preferring());
+        let reader = lock.read().await;
+
+        // A waiting writer keeps new readers out.
+        let writer = lock.write();
+        futures_lite::pin!(writer);
+        assert!(future::poll_once(writer.as_mut()).await.is_none());
+        assert!(lock.try_read().is_none());
+
+        drop(reader);
+        let writer = writer.await;
+
+        // Readers are let in after the writer is done.
+        let read = lock.read();
+        futures_lite::pin!(read);
+        assert!(future::poll_once(read.as_mut()).await.is_none());
+        drop(writer);
+        read.await;
+    });
+}
+
+#[test]
+fn policy_read_preferring() {
+    future::block_on(async {
+        let lock = RwLock::with_policy((), RwLockPolicy::read_preferring());
+        let reader = lock.read().await;
+
+        // A waiting writer doesn't keep new readers out.
+        let writer = lock.write();
+        futures_lite::pin!(writer);
+        assert!(future::poll_once(writer.as_mut()).await.is_none());
+        let reader2 = lock.try_read().unwrap();
+        drop(reader);
+        assert!(future::poll_once(writer.as_mut()).await.is_none());
+        let reader3 = lock.read().await;
+        drop(reader2);
+        assert!(future::poll_once(writer.as_mut()).await.is_none());
+
+        // The writer gets in once there are no readers left.
+        drop(reader3);
+        let writer = writer.await;
+        assert!(lock.try_read().is_none());
+        drop(writer);
+
+        // Upgrades wait for the other readers too.
+        let upgradable = lock.upgradable_read().await;
+        let reader = lock.read().await;
+        let upgrade = RwLockUpgradableReadGuard::upgrade(upgradable);
+        futures_lite::pin!(upgrade);
+        assert!(future::poll_once(upgrade.as_mut()).await.is_none());
+        drop(lock.try_read().unwrap());
+        drop(reader);
+        let writer = upgrade.await;
+        assert!(lock.try_read().is_none());
+        drop(writer);
+
+        // Cancelled upgrades release the lock.
+        let upgradable = lock.upgradable_read().await;
+        let reader = lock.read().await;
+        {
+            let upgrade = RwLockUpgradableReadGuard::upgrade(upgradable);
+            futures_lite::pin!(upgrade);
+            assert!(future::poll_once(upgrade.as_mut()).await.is_none());
+        }
+        drop(reader);
+        assert!(lock.try_write().is_some());
+    });
+}
+
+#[test]
+fn policy_phase_fair() {
+    future::block_on(async {
+        let lock = RwLock::with_policy((), RwLockPolicy::phase_fair());
+        let writer = lock.write().await;
+
+        // Readers blocked by a writer go before the next one.
+        let read = lock.read();
+        let write = lock.write();
+        futures_lite::pin!(read, write);
+        assert!(future::poll_once(read.as_mut()).await.is_none());
+        assert!(future::poll_once(write.as_mut()).await.is_none());
+
+        drop(writer);
+        assert!(future::poll_once(write.as_mut()).await.is_none());
+        assert!(lock.try_write().is_none());
+        let reader = read.await;
+
+        // Then the waiting writer keeps new readers out.
+        assert!(future::poll_once(write.as_mut()).await.is_none());
+        assert!(lock.try_read().is_none());
+        drop(reader);
+        let writer = write.await;
+
+        // Cancelled readers don't hold up the next writer.
+        {
+            let read = lock.read();
+            futures_lite::pin!(read);
+            assert!(future::poll_once(read.as_mut()).await.is_none());
+        }
+        drop(writer);
+        assert!(lock.try_write().is_some());
+
+        // Only the readers blocked when the writer unlocks are let in.
+        let writer = lock.write().await;
+        let mut read = Box::pin(lock.read());
+        let write = lock.write();
+        futures_lite::pin!(write);
+        assert!(future::poll_once(read.as_mut()).await.is_none());
+        drop(writer);
+        assert!(future::poll_once(write.as_mut()).await.is_none());
+
+        let read2 = lock.read();
+        futures_lite::pin!(read2);
+        assert!(future::poll_once(read2.as_mut()).await.is_none());
+
+        // A reader that was let in but is dropped without being polled again releases the lock.
+        drop(read);
+        let writer = write.await;
+        drop(writer);
+        read2.await;
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn policy_phase_fair_threads() {
+    const READERS: usize = 8;
+    const ROUNDS: usize = 200;
+
+    let lock = Arc::new(RwLock::with_policy(0usize, RwLockPolicy::phase_fair()));
+
+    let handles = (0..READERS)
+        .map(|_| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..ROUNDS {
+                    drop(lock.read_blocking());
+                }
+            })
+        })
+        .chain((0..2).map(|_| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..ROUNDS {
+                    *lock.write_blocking() += 1;
+                }
+            })
+        }))
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert_eq!(*lock.try_write().unwrap(), 2 * ROUNDS);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn policy_starvation() {
+    use std::sync::atomic::AtomicBool;
+
+    const ROUNDS: usize = 100;
+
+    // Under a steady stream of readers or writers, the other side makes progress unless the
+    // policy prefers the stream, and gets through once the stream stops either way.
+    for (policy, stream_readers, progress) in [
+        (RwLockPolicy::write_preferring(), true, true),
+        (RwLockPolicy::write_preferring(), false, false),
+        (RwLockPolicy::read_preferring(), true, false),
+        (RwLockPolicy::read_preferring(), false, true),
+        (RwLockPolicy::phase_fair(), true, true),
+        (RwLockPolicy::phase_fair(), false, true),
+    ] {
+        let lock = Arc::new(RwLock::with_policy(0usize, policy));
+        let done = Arc::new(AtomicBool::new(false));
+
+        let stream = (0..4)
+            .map(|_| {
+                let lock = lock.clone();
+                let done = done.clone();
+                thread::spawn(move || {
+                    while !done.load(Ordering::SeqCst) {
+                        if stream_readers {
+                            let _reader = lock.read_blocking();
+                            thread::yield_now();
+                        } else {
+                            *lock.write_blocking() += 1;
+                            thread::yield_now();
+                        }
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if progress {
+            for _ in 0..ROUNDS {
+                if stream_readers {
+                    *lock.write_blocking() += 1;
+                } else {
+                    drop(lock.read_blocking());
+                }
+            }
+        }
+
+        done.store(true, Ordering::SeqCst);
+        for handle in stream {
+            handle.join().unwrap();
+        }
+
+        if stream_readers {
+            *lock.write_blocking() += 1;
+        } else {
+            drop(lock.read_blocking());
+        }
+    }
+}
+
+/// Cancels a writer waiting for a reader, and checks that the lock is left as if it never came.
+fn write_cancelled_while_reading(policy: RwLockPolicy) {
+    future::block_on(async {
+        let lock = RwLock::with_policy((), policy);
+        let reader = lock.read().await;
+
+        {
+            let write = lock.write();
+            futures_lite::pin!(write);
+            assert!(future::poll_once(write.as_mut()).await.is_none());
+        }
+
+        drop(lock.try_read().unwrap());
+        drop(reader);
+        drop(lock.try_read().unwrap());
+        drop(lock.try_write().unwrap());
+    });
+}
+
+#[test]
+fn write_preferring_write_cancelled_while_reading() {
+    write_cancelled_while_reading(RwLockPolicy::write_preferring());
+}
+
+#[test]
+fn read_preferring_write_cancelled_while_reading() {
+    write_cancelled_while_reading(RwLockPolicy::read_preferring());
+}
+
+#[test]
+fn phase_fair_write_cancelled_while_reading() {
+    write_cancelled_while_reading(RwLockPolicy::phase_fair());
+}
+
 fn _covariance_test<'g>(guard: RwLockReadGuard<'g, &'static ()>) {
     let _: RwLockReadGuard<'g, &'g ()> = guard;
 }
@@ -9,11 +9,13 @@ use std::sync::{
 };
 use std::task::Context;
 use std::task::Poll;
+use std::task::{Wake, Waker};
 use std::thread;
 
 use common::check_yields_when_contended;
 
-use async_lock::Semaphore;
+use async_lock::futures::Acquire;
+use async_lock::{AcquireError, Semaphore};
 use futures_lite::{future, pin};
 
 #[test]
@@ -136,6 +138,25 @@ fn smoke_arc_blocking() {
     assert!(s.try_acquire().is_some());
 }
 
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn acquire_blocking_timeout() {
+    use std::time::{Duration, Instant};
+
+    let s = Semaphore::new(1);
+    let guard = s
+        .acquire_blocking_timeout(Duration::from_millis(10))
+        .unwrap();
+    assert!(s
+        .acquire_blocking_timeout(Duration::from_millis(10))
+        .is_none());
+    assert!(s
+        .acquire_blocking_deadline(Instant::now() + Duration::from_millis(10))
+        .is_none());
+    drop(guard);
+    assert!(s.try_acquire().is_some());
+}
+
 #[test]
 fn add_permits() {
     static COUNTER: AtomicUsize = AtomicUsize::new(0);
@@ -192,3 +213,404 @@ impl Future for AddPermitsTest {
         Poll::Ready(())
     }
 }
+
+#[test]
+fn acquire_many() {
+    future::block_on(async {
+        let s = Semaphore::new(5);
+
+        let g1 = s.acquire_many(3).await;
+        assert_eq!(g1.permits(), 3);
+        assert!(s.try_acquire_many(3).is_none());
+
+        let g2 = s.try_acquire_many(2).unwrap();
+        assert!(s.try_acquire().is_none());
+
+        drop(g1);
+        assert!(s.try_acquire_many(4).is_none());
+        drop(g2);
+        drop(s.acquire_many(5).await);
+
+        let s = Arc::new(s);
+        let g = s.acquire_many_arc(4).await;
+        assert_eq!(g.permits(), 4);
+        assert!(s.try_acquire_many_arc(2).is_none());
+        drop(g);
+        assert!(s.try_acquire_many_arc(5).is_some());
+    });
+}
+
+#[test]
+fn forget_many() {
+    let s = Arc::new(Semaphore::new(5));
+
+    s.try_acquire_many(2).unwrap().forget();
+    s.try_acquire_many_arc(2).unwrap().forget();
+    assert!(s.try_acquire_many(2).is_none());
+    assert!(s.try_acquire().is_some());
+    assert_eq!(Arc::strong_count(&s), 1);
+}
+
+#[test]
+fn acquire_many_mixed() {
+    future::block_on(AcquireManyMixedTest);
+}
+
+struct AcquireManyMixedTest;
+
+impl Future for AcquireManyMixedTest {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let s = Semaphore::new(2);
+        let g = s.try_acquire_many(2).unwrap();
+
+        let large = s.acquire_many(2);
+        pin!(large);
+        let small = s.acquire();
+        pin!(small);
+        assert!(large.as_mut().poll(cx).is_pending());
+        assert!(small.as_mut().poll(cx).is_pending());
+
+        // A single permit doesn't satisfy the large request, but it isn't lost either.
+        s.add_permits(1);
+        assert!(large.as_mut().poll(cx).is_pending());
+        let small = match small.poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => panic!("small request wasn't woken up"),
+        };
+
+        drop(small);
+        drop(g);
+        assert!(large.poll(cx).is_ready());
+
+        Poll::Ready(())
+    }
+}
+
+/// A waker counting how many times it was woken.
+struct CountingWaker(AtomicUsize);
+
+impl Wake for CountingWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn acquire_many_wakes_one() {
+    let s = Semaphore::new(0);
+    let wakers = (0..3)
+        .map(|_| Arc::new(CountingWaker(AtomicUsize::new(0))))
+        .collect::<Vec<_>>();
+    let wakes = || {
+        wakers
+            .iter()
+            .map(|waker| waker.0.load(Ordering::SeqCst))
+            .collect::<Vec<_>>()
+    };
+
+    let mut large = Box::pin(s.acquire_many(3));
+    let mut medium = Box::pin(s.acquire_many(2));
+    let mut small = Box::pin(s.acquire_many(2));
+    let poll = |i: usize, future: Pin<&mut Acquire<'_>>| {
+        let waker = Waker::from(wakers[i].clone());
+        match future.poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(guard) => guard.forget(),
+            Poll::Pending => return false,
+        }
+        true
+    };
+    assert!(!poll(0, large.as_mut()));
+    assert!(!poll(1, medium.as_mut()));
+    assert!(!poll(2, small.as_mut()));
+
+    // Only the first waiter is woken up by a release.
+    s.add_permits(2);
+    assert_eq!(wakes(), [1, 0, 0]);
+
+    // It can't use the permits, so it passes them on.
+    assert!(!poll(0, large.as_mut()));
+    assert_eq!(wakes(), [1, 1, 0]);
+    assert!(poll(1, medium.as_mut()));
+    assert_eq!(wakes(), [1, 1, 0]);
+
+    // The wakeup doesn't go around forever.
+    s.add_permits(1);
+    assert_eq!(wakes(), [1, 1, 1]);
+    assert!(!poll(2, small.as_mut()));
+    assert_eq!(wakes(), [2, 1, 1]);
+    assert!(!poll(0, large.as_mut()));
+    assert_eq!(wakes(), [2, 1, 2]);
+    assert!(!poll(2, small.as_mut()));
+    assert_eq!(wakes(), [2, 1, 2]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn acquire_many_stress() {
+    const COUNT: usize = 1_000;
+
+    let s = Arc::new(Semaphore::new(8));
+    let handles = (1..=8)
+        .map(|n| {
+            let s = s.clone();
+            thread::spawn(move || {
+                future::block_on(async {
+                    for _ in 0..COUNT {
+                        s.acquire_many(n).await;
+                    }
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(s.try_acquire_many(8).is_some());
+}
+
+#[test]
+fn close() {
+    future::block_on(async {
+        let s = Arc::new(Semaphore::new(1));
+        let guard = s.acquire_checked().await.unwrap();
+        assert!(!s.is_closed());
+
+        // Waiters are woken up when the semaphore is closed.
+        let waiter = s.acquire_checked();
+        let waiter_arc = s.acquire_arc_checked();
+        let waiter_many = s.acquire_many(2);
+        pin!(waiter, waiter_arc, waiter_many);
+        assert!(future::poll_once(waiter.as_mut()).await.is_none());
+        assert!(future::poll_once(waiter_arc.as_mut()).await.is_none());
+        assert!(future::poll_once(waiter_many.as_mut()).await.is_none());
+
+        assert!(s.close());
+        assert!(!s.close());
+        assert!(s.is_closed());
+        assert_eq!(waiter.await.unwrap_err(), AcquireError::Closed);
+        assert_eq!(waiter_arc.await.unwrap_err(), AcquireError::Closed);
+
+        // Unchecked operations never complete.
+        assert!(future::poll_once(waiter_many.as_mut()).await.is_none());
+        drop(guard);
+        assert!(s.try_acquire().is_none());
+        assert!(s.try_acquire_arc().is_none());
+        assert!(future::poll_once(s.acquire()).await.is_none());
+        assert!(s.acquire_checked().await.is_err());
+    });
+}
+
+#[test]
+fn close_many_checked() {
+    future::block_on(async {
+        let s = Arc::new(Semaphore::new(3));
+        let guard = s.acquire_many_checked(2).await.unwrap();
+
+        let waiter = s.acquire_many_checked(2);
+        let waiter_arc = s.acquire_many_arc_checked(3);
+        pin!(waiter, waiter_arc);
+        assert!(future::poll_once(waiter.as_mut()).await.is_none());
+        assert!(future::poll_once(waiter_arc.as_mut()).await.is_none());
+
+        s.close();
+        assert_eq!(waiter.await.unwrap_err(), AcquireError::Closed);
+        assert_eq!(waiter_arc.await.unwrap_err(), AcquireError::Closed);
+        drop(guard);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn close_blocking() {
+    let s = Arc::new(Semaphore::new(1));
+    let guard = s.acquire_checked_blocking().unwrap();
+    let (tx, rx) = mpsc::channel();
+
+    let handle = thread::spawn({
+        let s = s.clone();
+        move || {
+            tx.send(()).unwrap();
+            s.acquire_checked_blocking().map(drop)
+        }
+    });
+
+    rx.recv().unwrap();
+    s.close();
+    assert_eq!(handle.join().unwrap(), Err(AcquireError::Closed));
+    drop(guard);
+}
+
+#[test]
+fn reduce_permits() {
+    future::block_on(async {
+        let s = Semaphore::new(4);
+        let g1 = s.acquire_many(2).await;
+        let g2 = s.acquire().await;
+        assert_eq!(s.available_permits(), 1);
+
+        // Available permits are removed right away, the rest as they are released.
+        let reduce = s.reduce_permits(3);
+        pin!(reduce);
+        assert!(future::poll_once(reduce.as_mut()).await.is_none());
+        assert_eq!(s.available_permits(), 0);
+        drop(g1);
+        reduce.await;
+        assert_eq!(s.available_permits(), 0);
+        drop(g2);
+        assert_eq!(s.available_permits(), 1);
+
+        // Dropping the future gives the removed permits back.
+        let g = s.acquire().await;
+        s.add_permits(1);
+        {
+            let reduce = s.reduce_permits(2);
+            pin!(reduce);
+            assert!(future::poll_once(reduce.as_mut()).await.is_none());
+            assert_eq!(s.available_permits(), 0);
+        }
+        assert_eq!(s.available_permits(), 1);
+        drop(g);
+        assert_eq!(s.available_permits(), 2);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+fn try_add_permits() {
+    let s = Semaphore::new(usize::MAX - 1);
+    assert!(s.try_add_permits(1).is_ok());
+    assert!(s.try_add_permits(1).is_err());
+    assert_eq!(s.available_permits(), usize::MAX);
+
+    let s = Semaphore::new(0);
+    s.reduce_permits_blocking(0);
+    assert!(s.try_add_permits(usize::MAX).is_ok());
+}
+
+#[test]
+fn fair() {
+    future::block_on(FairTest);
+}
+
+struct FairTest;
+
+impl Future for FairTest {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let s = Semaphore::new_fair(2);
+        let g = s.try_acquire_many(2).unwrap();
+
+        let large = s.acquire_many(2);
+        pin!(large);
+        let small = s.acquire();
+        pin!(small);
+        assert!(large.as_mut().poll(cx).is_pending());
+        assert!(small.as_mut().poll(cx).is_pending());
+
+        // The released permit is kept for the large request at the head of the queue.
+        s.add_permits(1);
+        assert!(small.as_mut().poll(cx).is_pending());
+        assert!(s.try_acquire().is_none());
+
+        drop(g);
+        assert!(small.as_mut().poll(cx).is_pending());
+        let large = match large.poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => panic!("large request wasn't woken up"),
+        };
+        assert!(small.poll(cx).is_ready());
+
+        // Nobody is queued anymore.
+        drop(large);
+        assert!(s.try_acquire().is_some());
+
+        Poll::Ready(())
+    }
+}
+
+#[test]
+fn fair_cancel() {
+    future::block_on(async {
+        let s = Semaphore::new_fair(1);
+        let g = s.acquire().await;
+
+        {
+            let large = s.acquire_many(2);
+            pin!(large);
+            assert!(future::poll_once(large.as_mut()).await.is_none());
+            assert!(s.try_acquire().is_none());
+        }
+
+        // Cancelled operations leave the queue.
+        drop(g);
+        assert!(s.try_acquire().is_some());
+
+        // Closing the semaphore fails every checked operation in the queue.
+        let g = s.acquire().await;
+        let first = s.acquire_checked();
+        let second = s.acquire_checked();
+        pin!(first, second);
+        assert!(future::poll_once(first.as_mut()).await.is_none());
+        assert!(future::poll_once(second.as_mut()).await.is_none());
+        s.close();
+        assert!(first.await.is_err());
+        assert!(second.await.is_err());
+        drop(g);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fair_stress() {
+    const COUNT: usize = 1_000;
+
+    let s = Arc::new(Semaphore::new_fair(8));
+    let handles = (1..=8)
+        .map(|n| {
+            let s = s.clone();
+            thread::spawn(move || {
+                for _ in 0..COUNT {
+                    if n % 2 == 0 {
+                        s.acquire_many_blocking(n);
+                    } else {
+                        future::block_on(s.acquire_many(n));
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(s.try_acquire_many(8).is_some());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn fair_try_acquire_contended() {
+    const COUNT: usize = 10_000;
+
+    // With nobody queued and a permit for each thread, `try_acquire` never fails.
+    let s = Arc::new(Semaphore::new_fair(4));
+    let handles = (0..4)
+        .map(|_| {
+            let s = s.clone();
+            thread::spawn(move || {
+                for _ in 0..COUNT {
+                    assert!(s.try_acquire().is_some());
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(not(target_family = "wasm"))]
+use std::sync::Arc;
+#[cfg(not(target_family = "wasm"))]
+use std::thread;
+
+use async_lock::Singleflight;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn shared_execution() {
+    let calls = AtomicUsize::new(0);
+
+    future::block_on(async {
+        let group = Singleflight::new();
+        let call = |value| {
+            let calls = &calls;
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                future::yield_now().await;
+                value
+            }
+        };
+
+        let (a, b) = future::zip(group.call(1, call("a")), group.call(1, call("b"))).await;
+        assert_eq!((a, b), ("a", "a"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Other keys run separately.
+        let (a, b) = future::zip(group.call(1, call("a")), group.call(2, call("b"))).await;
+        assert_eq!((a, b), ("a", "b"));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    });
+}
+
+#[test]
+fn nothing_retained() {
+    future::block_on(async {
+        let group = Singleflight::new();
+        assert_eq!(group.call("key", async { 1 }).await, 1);
+        assert_eq!(group.call("key", async { 2 }).await, 2);
+
+        // Callers arriving after completion run again, even while others are still waking up.
+        let mut first = Box::pin(group.call("key", async {
+            future::yield_now().await;
+            3
+        }));
+        let mut second = Box::pin(group.call("key", async { 4 }));
+        assert!(future::poll_once(first.as_mut()).await.is_none());
+        assert!(future::poll_once(second.as_mut()).await.is_none());
+        assert_eq!(first.await, 3);
+        assert_eq!(group.call("key", async { 5 }).await, 5);
+        assert_eq!(second.await, 3);
+    });
+}
+
+#[test]
+fn cancelled_leader() {
+    future::block_on(async {
+        let group = Singleflight::new();
+
+        let mut leader = Box::pin(group.call("key", async {
+            future::yield_now().await;
+            1
+        }));
+        let mut follower = Box::pin(group.call("key", async { 2 }));
+        assert!(future::poll_once(leader.as_mut()).await.is_none());
+        assert!(future::poll_once(follower.as_mut()).await.is_none());
+
+        // The follower takes over.
+        drop(leader);
+        assert_eq!(follower.await, 2);
+
+        // An abandoned call without followers is forgotten.
+        let mut leader = Box::pin(group.call("key", async {
+            future::yield_now().await;
+            3
+        }));
+        assert!(future::poll_once(leader.as_mut()).await.is_none());
+        drop(leader);
+        assert_eq!(group.call("key", async { 4 }).await, 4);
+    });
+}
+
+#[cfg(not(target_family = "wasm"))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn panicking_leader() {
+    let group = Arc::new(Singleflight::new());
+    let (tx, rx) = flume::bounded::<()>(0);
+
+    let leader = {
+        let group = group.clone();
+        thread::spawn(move || {
+            future::block_on(group.call("key", async move {
+                rx.recv_async().await.unwrap();
+                panic!("leader failed");
+            }))
+        })
+    };
+
+    // Wait until the leader is running.
+    while tx.try_send(()).is_err() {
+        thread::yield_now();
+    }
+    let follower = future::block_on(group.call("key", async { 2 }));
+
+    assert!(leader.join().is_err());
+    assert_eq!(follower, 2);
+}
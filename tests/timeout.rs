@@ -0,0 +1,200 @@
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use async_lock::{Barrier, Fairness, Mutex, RwLock, Semaphore, Timer};
+use futures_lite::{future, pin};
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+/// A timer that has either already expired or never will.
+struct Expired(bool);
+
+impl Timer for Expired {
+    fn poll_expired(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// A timer that expires once the flag is set.
+struct Flag<'a>(&'a AtomicBool);
+
+impl Timer for Flag<'_> {
+    fn poll_expired(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0.load(Ordering::SeqCst) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[test]
+fn mutex() {
+    future::block_on(async {
+        let m = Mutex::new(());
+        let guard = m.lock_timeout(Expired(false)).await.unwrap();
+        assert!(m.lock_timeout(Expired(true)).await.is_err());
+        drop(guard);
+        assert!(m.lock_timeout(Expired(true)).await.is_ok());
+
+        // Starved lock operations stop being counted once they time out.
+        let m = Mutex::with_fairness((), Fairness::fifo());
+        let guard = m.lock().await;
+        assert!(m.lock_timeout(Expired(true)).await.is_err());
+        drop(guard);
+        assert!(m.try_lock().is_some());
+
+        // The operation is withdrawn on expiry, even though the future is still around.
+        let guard = m.lock().await;
+        let timeout = m.lock_timeout(Expired(true));
+        pin!(timeout);
+        assert!(timeout.as_mut().await.is_err());
+        drop(guard);
+        assert!(m.try_lock().is_some());
+    });
+}
+
+#[test]
+fn mutex_arc() {
+    future::block_on(async {
+        let m = Arc::new(Mutex::new(()));
+        let guard = m.lock_arc_timeout(Expired(false)).await.unwrap();
+        assert!(m.lock_arc_timeout(Expired(true)).await.is_err());
+        drop(guard);
+        assert!(m.lock_arc_timeout(Expired(true)).await.is_ok());
+    });
+}
+
+#[test]
+fn rwlock() {
+    future::block_on(async {
+        let lock = RwLock::new(());
+
+        let reader = lock.read_timeout(Expired(false)).await.unwrap();
+        assert!(lock.write_timeout(Expired(true)).await.is_err());
+
+        // The timed out writer doesn't keep new readers out.
+        drop(lock.try_read().unwrap());
+        drop(reader);
+
+        let writer = lock.write_timeout(Expired(false)).await.unwrap();
+        assert!(lock.read_timeout(Expired(true)).await.is_err());
+        drop(writer);
+        assert!(lock.try_write().is_some());
+    });
+}
+
+#[test]
+fn rwlock_arc() {
+    future::block_on(async {
+        let lock = Arc::new(RwLock::new(()));
+
+        let reader = lock.upgradable_read_timeout(Expired(false)).await.unwrap();
+        assert!(lock
+            .upgradable_read_arc_timeout(Expired(true))
+            .await
+            .is_err());
+        assert!(lock.write_arc_timeout(Expired(true)).await.is_err());
+        assert!(lock.read_arc_timeout(Expired(true)).await.is_ok());
+        drop(reader);
+
+        let writer = lock.write_arc_timeout(Expired(false)).await.unwrap();
+        assert!(lock.read_arc_timeout(Expired(true)).await.is_err());
+        assert!(lock.upgradable_read_timeout(Expired(true)).await.is_err());
+        drop(writer);
+
+        let reader = lock
+            .upgradable_read_arc_timeout(Expired(false))
+            .await
+            .unwrap();
+        assert!(lock.upgradable_read_timeout(Expired(true)).await.is_err());
+        drop(reader);
+        assert!(lock.try_write().is_some());
+    });
+}
+
+#[test]
+fn semaphore() {
+    future::block_on(async {
+        let s = Semaphore::new(1);
+        let guard = s.acquire_timeout(Expired(false)).await.unwrap();
+        assert!(s.acquire_timeout(Expired(true)).await.is_err());
+        drop(guard);
+        assert!(s.try_acquire().is_some());
+
+        let s = Arc::new(Semaphore::new(1));
+        let guard = s.acquire_arc_timeout(Expired(false)).await.unwrap();
+        assert!(s.acquire_arc_timeout(Expired(true)).await.is_err());
+        drop(guard);
+        assert!(s.try_acquire_arc().is_some());
+    });
+}
+
+#[test]
+fn barrier() {
+    future::block_on(async {
+        let barrier = Barrier::new(2);
+
+        // Timed out waiters are no longer counted.
+        assert!(barrier.wait_timeout(Expired(true)).await.is_err());
+        assert!(barrier.wait_timeout(Expired(true)).await.is_err());
+
+        let (a, b) = future::zip(
+            barrier.wait_timeout(Expired(false)),
+            barrier.wait_timeout(Expired(false)),
+        )
+        .await;
+        assert_ne!(a.unwrap().is_leader(), b.unwrap().is_leader());
+    });
+}
+
+#[test]
+fn barrier_completion() {
+    future::block_on(async {
+        let (tx, rx) = flume::unbounded::<usize>();
+        let barrier = Barrier::with_completion(2, || {
+            let rx = rx.clone();
+            async move { rx.recv_async().await.unwrap() }
+        });
+        let expired = AtomicBool::new(false);
+
+        let a = barrier.wait_timeout(Flag(&expired));
+        let b = barrier.wait();
+        pin!(a, b);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+        assert!(future::poll_once(b.as_mut()).await.is_none());
+
+        // The leader started the completion before the timer expired, so the phase is passed.
+        expired.store(true, Ordering::SeqCst);
+        assert!(future::poll_once(a.as_mut()).await.is_none());
+
+        tx.send(1).unwrap();
+        assert!(b.await.is_leader());
+        assert_eq!(*a.await.unwrap().output(), 1);
+    });
+}
+
+#[cfg(all(feature = "async-io", not(target_family = "wasm")))]
+#[test]
+fn async_io() {
+    use std::time::Duration;
+
+    future::block_on(async {
+        let m = Mutex::new(());
+        let guard = m.lock().await;
+        let timer = async_io::Timer::after(Duration::from_millis(10));
+        assert!(m.lock_timeout(timer).await.is_err());
+        drop(guard);
+        assert!(m.try_lock().is_some());
+    });
+}
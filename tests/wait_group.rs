@@ -0,0 +1,97 @@
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::sync::Arc;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::thread;
+
+use async_lock::WaitGroup;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn add_done() {
+    future::block_on(async {
+        let group = WaitGroup::new();
+        group.wait().await;
+
+        group.add(2);
+        assert!(future::poll_once(group.wait()).await.is_none());
+        group.done();
+        assert!(future::poll_once(group.wait()).await.is_none());
+        group.done();
+        group.wait().await;
+    });
+}
+
+#[test]
+fn tokens() {
+    future::block_on(async {
+        let group = WaitGroup::new();
+        let token = group.token();
+        let token2 = token.clone();
+        assert_eq!(group.count(), 2);
+
+        let mut wait = Box::pin(group.wait());
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+
+        drop(token);
+        assert!(future::poll_once(wait.as_mut()).await.is_none());
+        drop(token2);
+        wait.await;
+    });
+}
+
+#[test]
+fn reuse() {
+    future::block_on(async {
+        let group = WaitGroup::new();
+        drop(group.token());
+        group.wait().await;
+
+        let token = group.token();
+        assert!(future::poll_once(group.wait()).await.is_none());
+        drop(token);
+        group.wait().await;
+    });
+}
+
+#[test]
+#[should_panic = "no tasks left"]
+fn done_without_tasks() {
+    WaitGroup::new().done();
+}
+
+#[test]
+fn drop_token_after_extra_done() {
+    let group = WaitGroup::new();
+    let token = group.token();
+    group.done();
+    drop(token);
+    assert_eq!(group.count(), 0);
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn threads() {
+    let group = Arc::new(WaitGroup::new());
+    let finished = Arc::new(AtomicUsize::new(0));
+
+    for _ in 0..10 {
+        let token = group.token_arc();
+        let finished = finished.clone();
+        thread::spawn(move || {
+            let _token = token;
+            finished.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+
+    group.wait_blocking();
+    assert_eq!(finished.load(Ordering::SeqCst), 10);
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+use std::thread;
+
+use async_lock::Watch;
+use futures_lite::future;
+
+#[cfg(target_family = "wasm")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+#[cfg(target_family = "wasm")]
+wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
+
+#[test]
+fn smoke() {
+    future::block_on(async {
+        let watch = Watch::new(1);
+        let mut receiver = watch.subscribe();
+        assert!(!receiver.has_changed());
+        assert!(future::poll_once(receiver.changed()).await.is_none());
+
+        watch.send(2).await;
+        watch.send_modify(|value| *value += 1).await;
+        assert!(receiver.has_changed());
+
+        // Only the latest value is seen.
+        receiver.changed().await;
+        assert_eq!(*receiver.borrow().await, 3);
+        assert!(future::poll_once(receiver.changed()).await.is_none());
+    });
+}
+
+#[test]
+fn wakes_all_receivers() {
+    future::block_on(async {
+        let watch = Watch::new(0);
+        let mut a = watch.subscribe();
+        let mut b = a.clone();
+
+        let changed = future::zip(a.changed(), b.changed());
+        let send = async {
+            future::yield_now().await;
+            watch.send(1).await;
+        };
+        future::zip(changed, send).await;
+
+        assert!(!a.has_changed());
+        assert!(!b.has_changed());
+    });
+}
+
+#[test]
+fn late_subscriber() {
+    future::block_on(async {
+        let watch = Watch::new(0);
+        let mut early = watch.subscribe();
+        watch.send(1).await;
+
+        let mut late = watch.subscribe();
+        assert!(!late.has_changed());
+        assert!(future::poll_once(late.changed()).await.is_none());
+        early.changed().await;
+    });
+}
+
+#[test]
+fn unsized_value() {
+    future::block_on(async {
+        let watch: &Watch<[i32]> = &Watch::new([1, 2]);
+        let mut receiver = watch.subscribe();
+
+        watch.send_modify(|values| values.reverse()).await;
+        receiver.changed().await;
+        assert_eq!(*receiver.borrow().await, [2, 1]);
+    });
+}
+
+#[test]
+fn subscribe_arc() {
+    future::block_on(async {
+        let watch = Arc::new(Watch::new(0));
+        let mut receiver = watch.subscribe_arc();
+        let mut other = receiver.clone();
+        drop(watch);
+
+        let watch = async_lock::WatchReceiverArc::source(&receiver).clone();
+        watch.send(1).await;
+        receiver.changed().await;
+        assert!(!receiver.has_changed());
+        assert!(other.has_changed());
+        other.changed().await;
+        assert_eq!(*other.borrow().await, 1);
+    });
+}
+
+#[cfg(all(feature = "std", not(target_family = "wasm")))]
+#[test]
+#[cfg_attr(miri, ignore)]
+fn blocking() {
+    let watch = Arc::new(Watch::new(0));
+    let mut receiver = watch.subscribe();
+
+    let handle = {
+        let watch = watch.clone();
+        thread::spawn(move || {
+            for i in 1..=100 {
+                watch.send_blocking(i);
+            }
+        })
+    };
+
+    loop {
+        receiver.changed_blocking();
+        if *future::block_on(receiver.borrow()) == 100 {
+            break;
+        }
+    }
+    handle.join().unwrap();
+}